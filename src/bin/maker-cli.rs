@@ -1,9 +1,13 @@
-use std::{net::TcpStream, time::Duration};
+use std::{net::TcpStream, path::PathBuf, time::Duration};
 
-use clap::Parser;
+use bitcoin::{OutPoint, PublicKey};
+use clap::{Parser, ValueEnum};
 use coinswap::{
-    maker::{MakerError, RpcMsgReq, RpcMsgResp},
-    utill::{read_message, send_message, MIN_FEE_RATE},
+    maker::{
+        read_rpc_cookie, rpc_cookie_path, swap_tracker::MakerSwapPhase, MakerError, RpcMsgReq,
+        RpcMsgResp, RpcRequest,
+    },
+    utill::{get_maker_dir, read_message, send_message, MIN_FEE_RATE},
 };
 
 /// A simple command line app to operate the makerd server.
@@ -20,6 +24,12 @@ struct App {
     /// Sets the rpc-port of Makerd
     #[arg(long, short = 'p', default_value = "127.0.0.1:6103")]
     rpc_port: String,
+    /// Data directory of makerd, used to locate the RPC cookie file. Default value: "~/.coinswap/maker"
+    #[arg(long, short = 'd')]
+    data_directory: Option<PathBuf>,
+    /// RPC auth token. If not given, it is read from the `.cookie` file in the makerd data directory.
+    #[arg(long)]
+    rpc_auth: Option<String>,
     /// The command to execute
     #[command(subcommand)]
     command: Commands,
@@ -63,17 +73,176 @@ enum Commands {
     /// Show the data directory path
     ShowDataDir,
     /// Shutdown the makerd server
-    Stop,
-    /// Show all the fidelity bonds, current and previous, with an (index, {bond_proof, is_spent}) tuple.
+    Stop {
+        /// Stop accepting new swaps and shut down only after all in-flight swaps have
+        /// completed or been handed over to recovery.
+        #[arg(long)]
+        graceful: bool,
+    },
+    /// Stream maker events (swaps negotiated, funded, completed or dropped, recoveries,
+    /// bond renewals, low liquidity) as JSON lines until interrupted.
+    Subscribe,
+    /// Stop accepting new swaps (maintenance mode). In-flight swaps continue.
+    Pause,
+    /// Accept new swaps again after a pause.
+    Resume,
+    /// Show all the fidelity bonds, current and previous, with their status, bond value,
+    /// time to expiry and which one is advertised.
     ShowFidelity,
+    /// Create a new fidelity bond. It is advertised once confirmed, if it is the highest-value bond.
+    CreateFidelity {
+        /// Amount to lock in sats.
+        #[arg(long, short = 'a')]
+        amount: u64,
+        /// Timelock in blocks from the current height.
+        #[arg(long, short = 't')]
+        timelock: u32,
+        /// Feerate in sats/vByte. Defaults to 2 sats/vByte
+        #[arg(long, short = 'f')]
+        feerate: Option<f64>,
+    },
+    /// Redeem an expired fidelity bond back into the wallet and return the txid.
+    RedeemFidelity {
+        /// Index of the bond, as shown by show-fidelity.
+        #[arg(long, short = 'i')]
+        index: u32,
+        /// Feerate in sats/vByte. Defaults to 2 sats/vByte
+        #[arg(long, short = 'f')]
+        feerate: Option<f64>,
+    },
+    /// Choose which live fidelity bond is advertised to takers.
+    /// Without an index, goes back to advertising the highest-value bond.
+    SelectFidelity {
+        /// Index of the bond, as shown by show-fidelity.
+        #[arg(long, short = 'i')]
+        index: Option<u32>,
+    },
+    /// Register a fidelity bond locked to a key held in an offline wallet. Prints the
+    /// certificate the offline key must sign before the bond is advertised.
+    ImportFidelity {
+        /// Outpoint of the bond, as txid:vout.
+        #[arg(long, short = 'o')]
+        outpoint: OutPoint,
+        /// Public key of the offline key, in hex.
+        #[arg(long, short = 'k')]
+        pubkey: PublicKey,
+        /// Block height the bond is timelocked until.
+        #[arg(long, short = 'l')]
+        locktime: u32,
+    },
+    /// Show the certificate the offline key of a fidelity bond must sign.
+    FidelityCert {
+        /// Index of the bond, as shown by show-fidelity.
+        #[arg(long, short = 'i')]
+        index: u32,
+    },
+    /// Import the certificate signature of an offline fidelity bond.
+    CertifyFidelity {
+        /// Index of the bond, as shown by show-fidelity.
+        #[arg(long, short = 'i')]
+        index: u32,
+        /// Signature of the certificate message from signmessage (base64), or DER hex signature
+        /// of the certificate hash.
+        #[arg(long, short = 's')]
+        signature: String,
+    },
+    /// Export an unsigned PSBT redeeming an offline fidelity bond, to be signed by the
+    /// offline wallet once the bond expires.
+    ExportFidelityRedeem {
+        /// Index of the bond, as shown by show-fidelity.
+        #[arg(long, short = 'i')]
+        index: u32,
+        /// Address receiving the bond amount.
+        #[arg(long, short = 't')]
+        address: String,
+        /// Feerate in sats/vByte. Defaults to 2 sats/vByte
+        #[arg(long, short = 'f')]
+        feerate: Option<f64>,
+    },
     /// Sync the Maker wallet with the current blockchain state.
     SyncWallet,
+    /// List swaps known to the maker: in-flight, completed, dropped and recovering ones.
+    ListSwaps {
+        /// Only show swaps in this phase (active, taker-dropped, recovering, recovered, completed).
+        #[arg(long)]
+        phase: Option<MakerSwapPhase>,
+    },
+    /// Show phase, amounts, contract txids and recovery progress of a single swap.
+    ShowSwap {
+        /// The swap id.
+        #[arg(long, short = 'i')]
+        id: String,
+    },
+    /// Update the fee policy offered to takers, without restarting makerd.
+    /// Only swaps negotiated after the update are affected. The change is saved to config.toml.
+    /// Omitted values are left unchanged; without any flag, prints the current policy.
+    SetFees {
+        /// Fixed base fee per swap, in sats.
+        #[arg(long)]
+        base_fee: Option<u64>,
+        /// Fee percentage relative to the swap amount.
+        #[arg(long)]
+        amount_fee_pct: Option<f64>,
+        /// Fee percentage relative to the swap amount and timelock.
+        #[arg(long)]
+        time_fee_pct: Option<f64>,
+        /// Minimum swap amount, in sats.
+        #[arg(long)]
+        min_swap_amount: Option<u64>,
+    },
+    /// Show fees earned, mining fees paid and liquidity locked, per protocol and per day,
+    /// for the swaps started in the given range of UTC days.
+    Earnings {
+        /// First day included, as YYYY-MM-DD. Defaults to the first swap.
+        #[arg(long)]
+        from: Option<String>,
+        /// Last day included, as YYYY-MM-DD. Defaults to the latest swap.
+        #[arg(long)]
+        to: Option<String>,
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = ReportFormat::Json)]
+        format: ReportFormat,
+    },
+    /// Reload config.toml without restarting makerd. Fees, limits, nostr relays, required
+    /// confirmations and supported protocols are applied immediately; other changes are
+    /// reported as needing a restart.
+    ReloadConfig,
+}
+
+/// Output format of reports.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ReportFormat {
+    Json,
+    Csv,
 }
 
 fn main() -> Result<(), MakerError> {
     let cli = App::parse();
 
+    let auth = match cli.rpc_auth {
+        Some(token) => token,
+        None => {
+            let data_dir = cli.data_directory.unwrap_or_else(get_maker_dir);
+            read_rpc_cookie(&data_dir).map_err(|e| {
+                eprintln!(
+                    "Could not read RPC cookie at {}: {e}. Is makerd running? Use --rpc-auth to pass the token explicitly.",
+                    rpc_cookie_path(&data_dir).display()
+                );
+                MakerError::IO(e)
+            })?
+        }
+    };
+
     let stream = TcpStream::connect(cli.rpc_port)?;
+    let send_rpc_req = |stream, msg| {
+        send_rpc_req(
+            stream,
+            RpcRequest {
+                auth: auth.clone(),
+                msg,
+            },
+        )
+    };
 
     match cli.command {
         Commands::SendPing => {
@@ -117,28 +286,165 @@ fn main() -> Result<(), MakerError> {
         Commands::ShowDataDir => {
             send_rpc_req(stream, RpcMsgReq::GetDataDir)?;
         }
-        Commands::Stop => {
-            send_rpc_req(stream, RpcMsgReq::Stop)?;
+        Commands::Stop { graceful } => {
+            send_rpc_req(stream, RpcMsgReq::Stop { graceful })?;
+        }
+        Commands::Subscribe => {
+            subscribe(
+                stream,
+                RpcRequest {
+                    auth,
+                    msg: RpcMsgReq::Subscribe,
+                },
+            )?;
+        }
+        Commands::Pause => {
+            send_rpc_req(stream, RpcMsgReq::Pause)?;
+        }
+        Commands::Resume => {
+            send_rpc_req(stream, RpcMsgReq::Resume)?;
         }
         Commands::ShowFidelity => {
             send_rpc_req(stream, RpcMsgReq::ListFidelity)?;
         }
+        Commands::CreateFidelity {
+            amount,
+            timelock,
+            feerate,
+        } => {
+            send_rpc_req(
+                stream,
+                RpcMsgReq::CreateFidelity {
+                    amount,
+                    timelock,
+                    feerate: feerate.unwrap_or(MIN_FEE_RATE),
+                },
+            )?;
+        }
+        Commands::RedeemFidelity { index, feerate } => {
+            send_rpc_req(
+                stream,
+                RpcMsgReq::RedeemFidelity {
+                    index,
+                    feerate: feerate.unwrap_or(MIN_FEE_RATE),
+                },
+            )?;
+        }
+        Commands::SelectFidelity { index } => {
+            send_rpc_req(stream, RpcMsgReq::SelectFidelity { index })?;
+        }
+        Commands::ImportFidelity {
+            outpoint,
+            pubkey,
+            locktime,
+        } => {
+            send_rpc_req(
+                stream,
+                RpcMsgReq::ImportFidelity {
+                    outpoint,
+                    pubkey,
+                    locktime,
+                },
+            )?;
+        }
+        Commands::FidelityCert { index } => {
+            send_rpc_req(stream, RpcMsgReq::FidelityCert { index })?;
+        }
+        Commands::CertifyFidelity { index, signature } => {
+            send_rpc_req(stream, RpcMsgReq::CertifyFidelity { index, signature })?;
+        }
+        Commands::ExportFidelityRedeem {
+            index,
+            address,
+            feerate,
+        } => {
+            send_rpc_req(
+                stream,
+                RpcMsgReq::ExportFidelityRedeem {
+                    index,
+                    address,
+                    feerate: feerate.unwrap_or(MIN_FEE_RATE),
+                },
+            )?;
+        }
         Commands::SyncWallet => {
             send_rpc_req(stream, RpcMsgReq::SyncWallet)?;
         }
+        Commands::ListSwaps { phase } => {
+            send_rpc_req(stream, RpcMsgReq::ListSwaps { phase })?;
+        }
+        Commands::ShowSwap { id } => {
+            send_rpc_req(stream, RpcMsgReq::GetSwap { swap_id: id })?;
+        }
+        Commands::SetFees {
+            base_fee,
+            amount_fee_pct,
+            time_fee_pct,
+            min_swap_amount,
+        } => {
+            send_rpc_req(
+                stream,
+                RpcMsgReq::SetFeePolicy {
+                    base_fee,
+                    amount_relative_fee_pct: amount_fee_pct,
+                    time_relative_fee_pct: time_fee_pct,
+                    min_swap_amount,
+                },
+            )?;
+        }
+        Commands::Earnings { from, to, format } => {
+            let response = rpc_call(
+                stream,
+                RpcRequest {
+                    auth,
+                    msg: RpcMsgReq::Earnings { from, to },
+                },
+            )?;
+            match (response, format) {
+                (RpcMsgResp::EarningsResp(report), ReportFormat::Csv) => {
+                    print!("{}", report.to_csv())
+                }
+                (response, _) => println!("{response}"),
+            }
+        }
+        Commands::ReloadConfig => {
+            send_rpc_req(stream, RpcMsgReq::ReloadConfig)?;
+        }
     }
 
     Ok(())
 }
 
-fn send_rpc_req(mut stream: TcpStream, req: RpcMsgReq) -> Result<(), MakerError> {
+/// Opens an event subscription and prints every event until makerd closes the connection.
+fn subscribe(mut stream: TcpStream, req: RpcRequest) -> Result<(), MakerError> {
+    stream.set_write_timeout(Some(Duration::from_secs(20)))?;
+    send_message(&mut stream, &req)?;
+
+    loop {
+        let response_bytes = match read_message(&mut stream) {
+            Ok(bytes) => bytes,
+            // makerd shut down or dropped the subscription.
+            Err(_) => return Ok(()),
+        };
+        match serde_cbor::from_slice(&response_bytes)? {
+            RpcMsgResp::Subscribed => eprintln!("Subscribed, waiting for events..."),
+            response => println!("{response}"),
+        }
+    }
+}
+
+fn rpc_call(mut stream: TcpStream, req: RpcRequest) -> Result<RpcMsgResp, MakerError> {
     // stream.set_read_timeout(Some(Duration::from_secs(20)))?;
     stream.set_write_timeout(Some(Duration::from_secs(20)))?;
 
     send_message(&mut stream, &req)?;
 
     let response_bytes = read_message(&mut stream)?;
-    let response: RpcMsgResp = serde_cbor::from_slice(&response_bytes)?;
+    Ok(serde_cbor::from_slice(&response_bytes)?)
+}
+
+fn send_rpc_req(stream: TcpStream, req: RpcRequest) -> Result<(), MakerError> {
+    let response = rpc_call(stream, req)?;
 
     if matches!(response, RpcMsgResp::Pong) {
         println!("success");
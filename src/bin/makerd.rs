@@ -3,7 +3,10 @@ use clap::Parser;
 use coinswap::{
     maker::{bind_port_retry, start_server, MakerError, MakerServer, MakerServerConfig},
     utill::{parse_proxy_auth, setup_maker_logger},
-    wallet::{BackendConfig, BitcoindBackend, ElectrumBackend, ElectrumConfig, RPCConfig},
+    wallet::{
+        BackendConfig, BitcoindBackend, BlockchainBackend, ElectrumBackend, ElectrumConfig,
+        RPCConfig,
+    },
 };
 use std::{path::PathBuf, sync::Arc};
 
@@ -136,13 +139,53 @@ fn main() -> Result<(), MakerError> {
     match config.backend {
         BackendConfig::Electrum(_) => {
             let maker = Arc::new(MakerServer::<ElectrumBackend>::init(config)?);
+            reload_on_sighup(maker.clone())?;
             start_server(maker)?;
         }
         BackendConfig::Bitcoind(_) => {
             let maker = Arc::new(MakerServer::<BitcoindBackend>::init(config)?);
+            reload_on_sighup(maker.clone())?;
             start_server(maker)?;
         }
     }
 
     Ok(())
 }
+
+/// Reloads `config.toml` whenever makerd receives a SIGHUP.
+#[cfg(unix)]
+fn reload_on_sighup<B: BlockchainBackend + 'static>(
+    maker: Arc<MakerServer<B>>,
+) -> Result<(), MakerError> {
+    use signal_hook::{consts::SIGHUP, iterator::Signals};
+
+    let mut signals = Signals::new([SIGHUP])?;
+    std::thread::Builder::new()
+        .name("SIGHUP handler".to_string())
+        .spawn(move || {
+            for _ in signals.forever() {
+                log::info!("Received SIGHUP, reloading config.toml");
+                match maker.reload_config() {
+                    Ok(reload) => {
+                        if !reload.restart_required.is_empty() {
+                            log::warn!(
+                                "Restart makerd to apply: {}",
+                                reload.restart_required.join(", ")
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("Config reload failed, keeping the running config: {e:?}")
+                    }
+                }
+            }
+        })?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn reload_on_sighup<B: BlockchainBackend + 'static>(
+    _maker: Arc<MakerServer<B>>,
+) -> Result<(), MakerError> {
+    Ok(())
+}
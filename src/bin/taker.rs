@@ -4,11 +4,14 @@ use clap::Parser;
 use coinswap::{
     protocol::ProtocolVersion,
     taker::{
-        error::TakerError, format_state, MakerOfferCandidate, MakerState, SwapParams, Taker,
+        error::TakerError,
+        format_state,
+        swap_tracker::{SwapPhase, SwapRecord},
+        MakerFeeInfo, MakerOfferCandidate, MakerState, SwapHistoryEntry, SwapParams, Taker,
         TakerInitConfig,
     },
     utill::{parse_proxy_auth, setup_taker_logger, UTXO},
-    wallet::{AddressType, RPCConfig, Wallet},
+    wallet::{AddressType, ContractRecoveryPlan, RPCConfig, SwapStatus, SweepDestination, Wallet},
 };
 use log::LevelFilter;
 use serde_json::{json, to_string_pretty};
@@ -145,6 +148,26 @@ enum Commands {
         /// When set, these makers are used directly instead of auto-discovery.
         #[clap(long = "maker-address")]
         maker_addresses: Vec<String>,
+        /// Pay the received coins to this address, xpub or descriptor instead of sweeping them
+        /// into the wallet. A bare xpub is read as `wpkh(<xpub>/0/*)`; only single-key `wpkh` and
+        /// `tr` descriptors of the form `<xpub>/<chain>/*` are supported.
+        #[clap(long)]
+        destination: Option<String>,
+        /// Derivation index of the first address paid when `--destination` is an xpub or descriptor.
+        #[clap(long, default_value = "0")]
+        destination_index: u32,
+        /// Abort the swap before funding if maker fees would exceed this many sats.
+        /// Defaults to `max_total_fee` in the taker config.
+        #[clap(long)]
+        max_total_fee: Option<u64>,
+        /// Abort the swap before funding if maker fees would exceed this percentage of the amount.
+        /// Defaults to `max_fee_pct` in the taker config.
+        #[clap(long)]
+        max_fee_pct: Option<f64>,
+        /// Split the amount at random into this many independent routes, each through its own
+        /// makers, and run them concurrently. Needs `--makers` times as many makers per route.
+        #[clap(long, default_value = "1")]
+        routes: usize,
         /// Automatically select UTXOs instead of interactive picker.
         #[clap(long)]
         auto_select: bool,
@@ -158,8 +181,60 @@ enum Commands {
         #[clap(long)]
         hotpath: bool,
     },
+    /// Estimate the fees, received amount and worst-case recovery time of a coinswap from the
+    /// cached offerbook, without contacting any maker or committing funds.
+    Quote {
+        /// Maker count to swap with.
+        #[clap(long, short = 'm', default_value = "2")]
+        makers: usize,
+        /// Swap amount in sats.
+        #[clap(long, short = 'a', default_value = "20000")]
+        amount: u64,
+        /// Protocol version to use: "legacy" or "taproot"
+        #[clap(long, default_value = "legacy")]
+        protocol: String,
+        /// Price a route through these maker addresses (host:port) instead of picking makers.
+        /// Each must have a cached offer. Can be repeated.
+        #[clap(long = "maker-address")]
+        maker_addresses: Vec<String>,
+        /// Cap on maker fees in sats. Defaults to `max_total_fee` in the taker config.
+        #[clap(long)]
+        max_total_fee: Option<u64>,
+        /// Cap on maker fees as a percentage of the amount. Defaults to `max_fee_pct` in the taker config.
+        #[clap(long)]
+        max_fee_pct: Option<f64>,
+    },
     /// Recover from all failed swaps
-    Recover,
+    Recover {
+        /// Only recover the contracts of this swap (see `history`).
+        #[clap(long)]
+        swap_id: Option<String>,
+        /// List each live contract, the spend path it would use, the block height at which
+        /// it becomes spendable and the recovery transaction, without broadcasting anything.
+        #[clap(long)]
+        dry_run: bool,
+    },
+    /// Resume a swap interrupted during finalization with the same makers, falling back to
+    /// recovery if it can't be finished.
+    Resume {
+        /// Swap ID, as shown by `history`.
+        #[clap(long)]
+        swap_id: String,
+    },
+
+    /// List past and in-flight swaps with their status, amounts, fees, makers and duration.
+    History {
+        /// Print the history as JSON instead of a table.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Show the detailed state of a single swap: its phase, per-maker progress and
+    /// how each contract was resolved.
+    SwapStatus {
+        /// Swap ID, as shown by `history` or when the swap was prepared.
+        #[clap(long)]
+        id: String,
+    },
 
     /// Backup the selected wallet.
     ///
@@ -294,13 +369,174 @@ fn display_offer<B: coinswap::wallet::BlockchainBackend>(
     ))
 }
 
+/// Print the fee breakdown of a route, one entry per hop.
+fn display_route_hops(makers: &[MakerFeeInfo]) {
+    for (i, maker) in makers.iter().enumerate() {
+        println!("  Hop {}: {} ({:?})", i, maker.address, maker.protocol);
+        println!(
+            "         Fees: base={} sats, amt={:.4}%, time={:.6}%",
+            maker.base_fee, maker.amount_relative_fee_pct, maker.time_relative_fee_pct
+        );
+        println!(
+            "         Locktime: {} blocks, Estimated fee: {} sats",
+            maker.locktime, maker.estimated_fee_sats
+        );
+    }
+}
+
+/// Short plain-text label for a swap's outcome, falling back to its tracked phase.
+fn status_label(entry: &SwapHistoryEntry) -> String {
+    match (&entry.status, entry.phase) {
+        (Some(SwapStatus::Success), _) => "success".to_string(),
+        (Some(SwapStatus::RecoveryHashlock), _) => "recovered (hashlock)".to_string(),
+        (Some(SwapStatus::RecoveryTimelock), _) => "recovered (timelock)".to_string(),
+        (Some(SwapStatus::Failed), _) => "failed".to_string(),
+        (None, Some(SwapPhase::Completed)) => "success".to_string(),
+        (None, Some(SwapPhase::Failed)) => "failed".to_string(),
+        (None, Some(phase)) => format!("in progress ({phase})"),
+        (None, None) => "unknown".to_string(),
+    }
+}
+
+fn format_optional_sats(value: Option<u64>) -> String {
+    value
+        .map(|sats| sats.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Print the swap history as a table.
+fn display_history(history: &[SwapHistoryEntry]) {
+    println!(
+        "{:<16}  {:<28}  {:>10}  {:>10}  {:>8}  {:>8}  MAKERS",
+        "SWAP ID", "STATUS", "SENT", "RECEIVED", "FEE", "DURATION"
+    );
+    for entry in history {
+        println!(
+            "{:<16}  {:<28}  {:>10}  {:>10}  {:>8}  {:>7.0}s  {}",
+            entry.swap_id,
+            status_label(entry),
+            entry.send_amount_sat,
+            format_optional_sats(entry.received_amount_sat),
+            format_optional_sats(entry.fee_paid_sat),
+            entry.duration_seconds,
+            entry.makers.join(", "),
+        );
+    }
+}
+
+/// Print the detailed state of a single swap.
+fn display_swap_status(record: Option<&SwapRecord>, entry: Option<&SwapHistoryEntry>) {
+    let (swap_id, status) = match (record, entry) {
+        (_, Some(entry)) => (entry.swap_id.as_str(), status_label(entry)),
+        (Some(record), None) => (record.swap_id.as_str(), record.phase.to_string()),
+        (None, None) => return,
+    };
+    println!("Swap ID:   {swap_id}");
+    println!("Status:    {status}");
+    if let Some(entry) = entry {
+        println!("Sent:      {} sats", entry.send_amount_sat);
+        println!(
+            "Received:  {}",
+            format_optional_sats(entry.received_amount_sat)
+        );
+        println!("Fee paid:  {}", format_optional_sats(entry.fee_paid_sat));
+        println!("Duration:  {:.0}s", entry.duration_seconds);
+        if let Some(error) = &entry.error {
+            println!("Error:     {error}");
+        }
+    }
+
+    let Some(record) = record else {
+        println!("\nNo tracked state for this swap (it ended before any funds were committed).");
+        return;
+    };
+
+    println!();
+    println!("Phase:     {}", record.phase);
+    if let Some(failed_at) = record.failed_at_phase {
+        println!("Failed at: {failed_at}");
+    }
+    println!("Protocol:  {:?}", record.protocol);
+
+    println!("\nMakers (N=negotiated | exchange milestones | K=privkey received, F=forwarded):");
+    for (i, maker) in record.makers.iter().enumerate() {
+        println!("  Hop {i}: {maker}");
+    }
+
+    println!("\nContracts (recovery phase: {}):", record.recovery.phase);
+    for (label, txids, outcomes) in [
+        (
+            "outgoing",
+            &record.outgoing_contract_txids,
+            &record.recovery.outgoing,
+        ),
+        (
+            "incoming",
+            &record.incoming_contract_txids,
+            &record.recovery.incoming,
+        ),
+        (
+            "watchonly",
+            &record.watchonly_contract_txids,
+            &record.recovery.watchonly,
+        ),
+    ] {
+        for txid in txids {
+            match outcomes.iter().find(|o| &o.contract_txid == txid) {
+                Some(outcome) => match outcome.spending_txid {
+                    Some(spending) => println!(
+                        "  {label:<9} {txid}  {} (spent by {spending})",
+                        outcome.resolution
+                    ),
+                    None => println!("  {label:<9} {txid}  {}", outcome.resolution),
+                },
+                None => println!("  {label:<9} {txid}  Pending"),
+            }
+        }
+    }
+}
+
+/// Print how a live contract would be recovered.
+fn display_recovery_plan(plan: &ContractRecoveryPlan) {
+    let spendable = match plan.spendable_at_height {
+        Some(height) if plan.blocks_remaining > 0 => {
+            format!("at height {height} (in {} blocks)", plan.blocks_remaining)
+        }
+        Some(height) => format!("now (matured at height {height})"),
+        None => "now".to_string(),
+    };
+    println!(
+        r#"
+    Contract       : {outpoint}
+    Swap ID        : {swap_id}
+    Amount         : {amount} sats
+    Spend Path     : {spend_path}
+    Spendable      : {spendable}"#,
+        outpoint = plan.outpoint,
+        swap_id = plan.swap_id.as_deref().unwrap_or("-"),
+        amount = plan.amount.to_sat(),
+        spend_path = plan.spend_path,
+    );
+    match &plan.recovery_tx {
+        Some(tx) => {
+            println!("    Recovery Txid  : {}", tx.compute_txid());
+            println!(
+                "    Recovery Tx    : {}",
+                bitcoin::consensus::encode::serialize_hex(tx)
+            );
+        }
+        None => println!("    Recovery Tx    : could not be built (see debug.log)"),
+    }
+}
+
 fn main() -> Result<(), TakerError> {
     let args = Cli::parse();
     setup_taker_logger(
         LevelFilter::from_str(&args.verbosity).unwrap(),
         matches!(
             args.command,
-            Commands::Recover
+            Commands::Recover { .. }
+                | Commands::Resume { .. }
                 | Commands::FetchOffers
                 | Commands::Backup { .. }
                 | Commands::Restore { .. }
@@ -334,6 +570,16 @@ fn main() -> Result<(), TakerError> {
         backend: backend.clone(),
         tor_auth_password: args.tor_auth.clone(),
         password: args.password.clone(),
+        // Targeted and dry-run recovery must not sweep every contract on startup, and a
+        // targeted resume must not resume or recover other swaps.
+        auto_recover: !matches!(
+            args.command,
+            Commands::Recover {
+                swap_id: Some(_),
+                ..
+            } | Commands::Recover { dry_run: true, .. }
+                | Commands::Resume { .. }
+        ),
         ..TakerInitConfig::default()
     };
 
@@ -508,11 +754,17 @@ fn run_commands<B: coinswap::wallet::BlockchainBackend>(
             amount,
             protocol,
             maker_addresses,
+            destination,
+            destination_index,
+            max_total_fee,
+            max_fee_pct,
+            routes,
             auto_select,
             yes,
             #[cfg(feature = "hotpath")]
             hotpath,
         } => {
+            taker.resume_interrupted_swaps();
             let protocol_version = parse_protocol(protocol)?;
 
             let manually_selected_outpoints =
@@ -538,6 +790,22 @@ fn run_commands<B: coinswap::wallet::BlockchainBackend>(
             if !maker_addresses.is_empty() {
                 swap_params.preferred_makers = Some(maker_addresses.clone());
             }
+            if let Some(destination) = destination {
+                let network = taker.get_wallet().read().unwrap().network();
+                swap_params = swap_params.with_destination(SweepDestination::parse(
+                    destination,
+                    network,
+                    *destination_index,
+                )?);
+            }
+            if let Some(max_total_fee) = max_total_fee {
+                swap_params = swap_params.with_max_total_fee(Amount::from_sat(*max_total_fee));
+            }
+            if let Some(max_fee_pct) = max_fee_pct {
+                swap_params = swap_params.with_max_fee_pct(*max_fee_pct);
+            }
+            swap_params = swap_params.with_routes(*routes);
+            let destination = swap_params.destination.clone();
 
             // Phase 1: Prepare — discover makers, negotiate, get fee summary.
             let summary = taker.prepare_coinswap(swap_params)?;
@@ -547,20 +815,28 @@ fn run_commands<B: coinswap::wallet::BlockchainBackend>(
             println!("Protocol:  {:?}", summary.protocol);
             println!("Sending:   {}", summary.send_amount);
             println!();
-            for (i, maker) in summary.makers.iter().enumerate() {
-                println!("  Hop {}: {} ({:?})", i, maker.address, maker.protocol);
-                println!(
-                    "         Fees: base={} sats, amt={:.4}%, time={:.6}%",
-                    maker.base_fee, maker.amount_relative_fee_pct, maker.time_relative_fee_pct
-                );
-                println!(
-                    "         Locktime: {} blocks, Estimated fee: {} sats",
-                    maker.locktime, maker.estimated_fee_sats
-                );
+            if summary.routes.is_empty() {
+                display_route_hops(&summary.makers);
+            } else {
+                for (i, route) in summary.routes.iter().enumerate() {
+                    println!(
+                        "Route {}: {} (id {})",
+                        i + 1,
+                        route.send_amount,
+                        route.swap_id
+                    );
+                    display_route_hops(&route.makers);
+                }
             }
             println!();
             println!("Total estimated fee: {}", summary.total_estimated_fee);
             println!("Estimated receive:   {}", summary.estimated_receive_amount);
+            if let Some(budget) = summary.fee_budget {
+                println!("Fee budget:          {budget}");
+            }
+            if destination.is_external() {
+                println!("Paid to:             {destination}");
+            }
             println!("==================================\n");
 
             // In integration tests, skip the confirmation prompt.
@@ -609,8 +885,105 @@ fn run_commands<B: coinswap::wallet::BlockchainBackend>(
                 run.finish_and_print();
             }
         }
-        Commands::Recover => {
-            taker.recover_active_swap()?;
+        Commands::Recover { swap_id, dry_run } => {
+            if *dry_run {
+                let plans = taker.recovery_plan(swap_id.as_deref())?;
+                if plans.is_empty() {
+                    println!("No live contracts to recover.");
+                }
+                for plan in &plans {
+                    display_recovery_plan(plan);
+                }
+            } else if let Some(swap_id) = swap_id {
+                taker.recover_swap(swap_id)?;
+            } else {
+                taker.resume_interrupted_swaps();
+                taker.recover_active_swap()?;
+            }
+        }
+        Commands::Resume { swap_id } => {
+            taker.resume_swap(swap_id)?;
+        }
+        Commands::Quote {
+            makers,
+            amount,
+            protocol,
+            maker_addresses,
+            max_total_fee,
+            max_fee_pct,
+        } => {
+            let mut params = SwapParams::new(
+                parse_protocol(protocol)?,
+                Amount::from_sat(*amount),
+                *makers,
+            );
+            if !maker_addresses.is_empty() {
+                params = params.with_preferred_makers(maker_addresses.clone());
+            }
+            if let Some(max_total_fee) = max_total_fee {
+                params = params.with_max_total_fee(Amount::from_sat(*max_total_fee));
+            }
+            if let Some(max_fee_pct) = max_fee_pct {
+                params = params.with_max_fee_pct(*max_fee_pct);
+            }
+            let quote = taker.quote_coinswap(&params)?;
+
+            println!("\n========== Swap Quote ==========");
+            println!("Protocol:  {:?}", quote.protocol);
+            println!("Sending:   {}", quote.send_amount);
+            println!();
+            for (i, maker) in quote.makers.iter().enumerate() {
+                println!("  Hop {}: {}", i, maker.address);
+                println!(
+                    "         Fees: base={} sats, amt={:.4}%, time={:.6}%",
+                    maker.base_fee, maker.amount_relative_fee_pct, maker.time_relative_fee_pct
+                );
+                println!(
+                    "         Locktime: {} blocks, Estimated fee: {} sats",
+                    maker.locktime, maker.estimated_fee_sats
+                );
+            }
+            println!();
+            println!("Maker fees:          {}", quote.total_maker_fee);
+            println!(
+                "Mining fees:         {} funding + {} sweep, at {:.2} sat/vB",
+                quote.funding_fee, quote.sweep_fee, quote.feerate
+            );
+            println!("Total cost:          {}", quote.total_cost());
+            println!("Estimated receive:   {}", quote.estimated_receive_amount);
+            if let Some(budget) = quote.fee_budget {
+                let note = if quote.exceeds_fee_budget() {
+                    " (exceeded, the swap would be refused)"
+                } else {
+                    ""
+                };
+                println!("Fee budget:          {budget}{note}");
+            }
+            println!(
+                "Worst-case recovery: {} blocks after funding (~{} hours)",
+                quote.worst_case_recovery_blocks,
+                quote.worst_case_recovery_secs().div_ceil(3600)
+            );
+            println!("================================\n");
+            println!("Offers are from the local offerbook and may have changed since it was last synced.");
+        }
+        Commands::History { json } => {
+            let history = taker.swap_history()?;
+            if *json {
+                println!("{}", to_string_pretty(&history)?);
+            } else if history.is_empty() {
+                println!("No swaps found.");
+            } else {
+                display_history(&history);
+            }
+        }
+        Commands::SwapStatus { id } => {
+            let record = taker.swap_status(id);
+            let entry = taker.swap_history()?.into_iter().find(|e| &e.swap_id == id);
+            if record.is_none() && entry.is_none() {
+                return Err(TakerError::General(format!("No swap found with ID {id}")));
+            }
+            display_swap_status(record.as_ref(), entry.as_ref());
         }
         Commands::Backup { encrypt } => {
             let wallet = taker.get_wallet().read().unwrap();
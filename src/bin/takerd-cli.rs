@@ -0,0 +1,132 @@
+use std::{net::TcpStream, path::PathBuf, time::Duration};
+
+use clap::Parser;
+use coinswap::{
+    protocol::ProtocolVersion,
+    taker::{
+        error::TakerError,
+        rpc::{read_rpc_cookie, rpc_cookie_path, RpcMsgReq, RpcMsgResp, RpcRequest},
+    },
+    utill::{get_taker_dir, read_message, send_message},
+};
+
+/// A simple command line app to operate the takerd daemon.
+///
+/// The app works as an RPC client for takerd: it queries the wallet and swap history, and
+/// schedules, lists and cancels coinswaps.
+///
+/// For more detailed usage information, please refer: <https://github.com/citadel-tech/coinswap/blob/master/docs/takerd.md>
+///
+/// This is early beta, and there are known and unknown bugs. Please report issues at: <https://github.com/citadel-tech/coinswap/issues>
+#[derive(Parser, Debug)]
+#[command(version = option_env ! ("CARGO_PKG_VERSION").unwrap_or("unknown"),
+author = option_env ! ("CARGO_PKG_AUTHORS").unwrap_or(""))]
+struct App {
+    /// Sets the rpc-port of takerd
+    #[arg(long, short = 'p', default_value = "127.0.0.1:6203")]
+    rpc_port: String,
+    /// Data directory of takerd, used to locate the RPC cookie file. Default value: "~/.coinswap/taker"
+    #[arg(long, short = 'd')]
+    data_directory: Option<PathBuf>,
+    /// RPC auth token. If not given, it is read from the `.cookie` file in the takerd data directory.
+    #[arg(long)]
+    rpc_auth: Option<String>,
+    /// The command to execute
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Parser, Debug)]
+enum Commands {
+    /// Sends a ping to takerd. Will return a pong.
+    SendPing,
+    /// Get total wallet balances of different categories.
+    GetBalances,
+    /// Gets a new bitcoin receiving address
+    GetNewAddress,
+    /// List past and in-flight swaps, as JSON.
+    History,
+    /// Schedule a coinswap.
+    ScheduleSwap {
+        /// Swap amount in sats.
+        #[arg(long, short = 'a')]
+        amount: u64,
+        /// Number of makers to route through. Swapping with less than 2 makers is not allowed.
+        #[arg(long, short = 'm', default_value = "2")]
+        makers: usize,
+        /// Protocol version to use: "legacy" or "taproot"
+        #[arg(long, default_value = "legacy")]
+        protocol: ProtocolVersion,
+        /// Seconds to wait before starting the swap.
+        #[arg(long, default_value = "0")]
+        delay: u64,
+    },
+    /// List scheduled swaps, including finished and cancelled ones.
+    ListScheduled,
+    /// Cancel a scheduled swap that has not started yet.
+    CancelScheduled {
+        /// Schedule entry ID, as shown by `list-scheduled`.
+        #[arg(long)]
+        id: u64,
+    },
+    /// Shutdown takerd once the swap in progress, if any, has finished.
+    Stop,
+}
+
+fn main() -> Result<(), TakerError> {
+    let cli = App::parse();
+
+    let auth = match cli.rpc_auth {
+        Some(token) => token,
+        None => {
+            let data_dir = cli.data_directory.unwrap_or_else(get_taker_dir);
+            read_rpc_cookie(&data_dir).map_err(|e| {
+                eprintln!(
+                    "Could not read RPC cookie at {}: {e}. Is takerd running? Use --rpc-auth to pass the token explicitly.",
+                    rpc_cookie_path(&data_dir).display()
+                );
+                TakerError::IO(e)
+            })?
+        }
+    };
+
+    let msg = match cli.command {
+        Commands::SendPing => RpcMsgReq::Ping,
+        Commands::GetBalances => RpcMsgReq::Balances,
+        Commands::GetNewAddress => RpcMsgReq::NewAddress,
+        Commands::History => RpcMsgReq::History,
+        Commands::ScheduleSwap {
+            amount,
+            makers,
+            protocol,
+            delay,
+        } => RpcMsgReq::ScheduleSwap {
+            amount,
+            makers,
+            protocol,
+            delay_secs: delay,
+        },
+        Commands::ListScheduled => RpcMsgReq::ListScheduled,
+        Commands::CancelScheduled { id } => RpcMsgReq::CancelScheduled { id },
+        Commands::Stop => RpcMsgReq::Stop,
+    };
+
+    let stream = TcpStream::connect(cli.rpc_port)?;
+    send_rpc_req(stream, RpcRequest { auth, msg })
+}
+
+fn send_rpc_req(mut stream: TcpStream, req: RpcRequest) -> Result<(), TakerError> {
+    stream.set_write_timeout(Some(Duration::from_secs(20)))?;
+    send_message(&mut stream, &req)?;
+
+    let response_bytes = read_message(&mut stream)?;
+    let response: RpcMsgResp = serde_cbor::from_slice(&response_bytes)?;
+
+    if matches!(response, RpcMsgResp::Pong) {
+        println!("success");
+    } else {
+        println!("{response}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use bitcoind::bitcoincore_rpc::Auth;
+use clap::Parser;
+use coinswap::{
+    taker::{
+        daemon::{start_takerd, TakerDaemon, TakerdConfig},
+        error::TakerError,
+        Taker, TakerInitConfig,
+    },
+    utill::{get_taker_dir, parse_proxy_auth, setup_taker_logger},
+    wallet::{
+        BackendConfig, BitcoindBackend, BlockchainBackend, ElectrumBackend, ElectrumConfig,
+        RPCConfig,
+    },
+};
+use log::LevelFilter;
+use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+/// Coinswap Taker Daemon
+///
+/// Keeps the taker wallet open, the offerbook synced and unfinished swaps under recovery, and
+/// runs coinswaps from a schedule. Swaps are scheduled with takerd-cli, or automatically for
+/// every incoming deposit when `auto_swap` is enabled in `takerd.toml`.
+///
+/// For more detailed usage information, please refer: <https://github.com/citadel-tech/coinswap/blob/master/docs/takerd.md>
+///
+/// This is early beta, and there are known and unknown bugs. Please report issues at: <https://github.com/citadel-tech/coinswap/issues>
+#[derive(Parser, Debug)]
+#[clap(version = option_env ! ("CARGO_PKG_VERSION").unwrap_or("unknown"),
+author = option_env ! ("CARGO_PKG_AUTHORS").unwrap_or(""))]
+struct Cli {
+    /// Optional data directory. Default value: "~/.coinswap/taker"
+    #[clap(long, short = 'd')]
+    data_directory: Option<PathBuf>,
+    /// Bitcoin Core RPC address:port value. Ignored when `--electrum-url` is set.
+    #[clap(
+        name = "ADDRESS:PORT",
+        long,
+        short = 'r',
+        default_value = "127.0.0.1:38332"
+    )]
+    pub rpc: String,
+    /// Bitcoin Core ZMQ address:port value. Ignored when `--electrum-url` is set.
+    #[clap(
+        name = "ZMQ",
+        long,
+        short = 'z',
+        default_value = "tcp://127.0.0.1:28332"
+    )]
+    pub zmq: String,
+    /// Bitcoin Core RPC authentication string. Ex: username:password.
+    /// Ignored when `--electrum-url` is set.
+    #[clap(name="USER:PASSWORD",short='a',long, value_parser = parse_proxy_auth, default_value = "user:password")]
+    pub auth: (String, String),
+    #[clap(long, short = 't')]
+    pub tor_auth: Option<String>,
+    /// Electrum server URL (e.g. `tcp://localhost:50001`). When set, the wallet
+    /// is initialised against an Electrum backend instead of Bitcoin Core.
+    #[clap(name = "ELECTRUM_URL", long)]
+    pub electrum_url: Option<String>,
+    /// Sets the taker wallet's name. If the wallet file already exists, it will load that wallet. Default: taker-wallet
+    #[clap(name = "WALLET", long, short = 'w')]
+    pub wallet_name: Option<String>,
+    /// Optional Password for the encryption of the wallet.
+    #[clap(name = "PASSWORD", long, short = 'p')]
+    pub password: Option<String>,
+    /// Sets the verbosity level of debug.log file
+    #[arg(long, short = 'v', value_parser = ["off", "error", "warn", "info", "debug", "trace"], default_value = "info")]
+    pub verbosity: String,
+}
+
+fn main() -> Result<(), TakerError> {
+    let args = Cli::parse();
+    setup_taker_logger(
+        LevelFilter::from_str(&args.verbosity).unwrap(),
+        true,
+        args.data_directory.clone(),
+    );
+
+    let data_dir = args.data_directory.unwrap_or_else(get_taker_dir);
+    let takerd_config = TakerdConfig::new(Some(&data_dir.join("takerd.toml")))?;
+
+    let wallet_name = args
+        .wallet_name
+        .unwrap_or_else(|| "taker-wallet".to_string());
+    let backend = match args.electrum_url {
+        Some(url) => BackendConfig::Electrum(ElectrumConfig { url, wallet_name }),
+        None => BackendConfig::Bitcoind(RPCConfig {
+            url: args.rpc,
+            auth: Auth::UserPass(args.auth.0, args.auth.1),
+            wallet_name,
+            zmq_addr: args.zmq,
+        }),
+    };
+
+    let config = TakerInitConfig {
+        data_dir: Some(data_dir),
+        backend,
+        tor_auth_password: args.tor_auth,
+        password: args.password,
+        ..TakerInitConfig::default()
+    };
+
+    match config.backend {
+        BackendConfig::Electrum(_) => run::<ElectrumBackend>(config, takerd_config),
+        BackendConfig::Bitcoind(_) => run::<BitcoindBackend>(config, takerd_config),
+    }
+}
+
+fn run<B: BlockchainBackend + 'static>(
+    config: TakerInitConfig,
+    takerd_config: TakerdConfig,
+) -> Result<(), TakerError> {
+    let taker = Taker::<B>::init(config)?;
+    let daemon = Arc::new(TakerDaemon::new(taker, takerd_config)?);
+    start_takerd(daemon)
+}
@@ -0,0 +1,510 @@
+//! Runtime fault injection for adversarial testing.
+//!
+//! A fault scenario is a list of rules, each naming a point of the maker or taker swap flow and
+//! the action to take when it is reached: drop the connection, delay, corrupt a field of the
+//! message, or broadcast the contract transactions early. Scenarios are plain JSON, so
+//! adversarial runs need no special build:
+//!
+//! ```json
+//! {
+//!   "faults": [
+//!     { "role": "maker", "at": { "recv": "ProofOfFunding" }, "action": "drop" },
+//!     { "role": "maker", "at": { "send": "RespContractSigsForSender" }, "action": { "corrupt": "sigs" } },
+//!     { "role": "taker", "at": { "step": "funds_broadcast" }, "action": "broadcast_contracts" },
+//!     { "role": "taker", "at": { "send": "ProofOfFunding" }, "action": { "delay": 30 }, "times": 1 }
+//!   ]
+//! }
+//! ```
+//!
+//! `makerd` and the taker load the scenario from the [`FAULTS_ENV`] environment variable, which
+//! holds either the JSON itself or the path of a file containing it. Scenarios are refused on
+//! mainnet.
+//!
+//! Message points are named after the variants of
+//! [`TakerToMakerMessage`](crate::protocol::common_messages::TakerToMakerMessage) and
+//! [`MakerToTakerMessage`](crate::protocol::common_messages::MakerToTakerMessage). A maker checks
+//! `recv` before handling a message, `handled` after handling it and before replying, and `send`
+//! before replying. A taker checks `send` before sending and `recv` before processing the reply.
+
+use std::{
+    fmt, fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use bitcoin::Network;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+/// Environment variable holding the fault scenario, inline or as a file path.
+pub const FAULTS_ENV: &str = "COINSWAP_FAULTS";
+
+/// Side of the swap a fault rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultRole {
+    /// The maker server.
+    Maker,
+    /// The taker.
+    Taker,
+}
+
+/// Steps of the swap flow that are not tied to a protocol message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultStep {
+    /// Maker: creating a fidelity bond. `corrupt` uses a timelock too short for takers to accept.
+    FidelityBond,
+    /// Maker: about to broadcast its funding. `drop` saves the swapcoins without broadcasting.
+    FundingBroadcast,
+    /// Taker: makers selected, before the swap is negotiated.
+    MakersSelected,
+    /// Taker: all funding broadcast and contracts signed, before finalizing. `drop` abandons the
+    /// swap and recovers.
+    FundsBroadcast,
+}
+
+/// Point of the swap flow a fault rule fires at.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultPoint {
+    /// A message was received, before it is processed.
+    Recv(String),
+    /// A received message was handled, before the reply is sent. Maker only.
+    Handled(String),
+    /// A message is about to be sent.
+    Send(String),
+    /// A step of the swap flow.
+    Step(FaultStep),
+}
+
+impl fmt::Display for FaultPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Recv(msg) => write!(f, "recv {msg}"),
+            Self::Handled(msg) => write!(f, "handled {msg}"),
+            Self::Send(msg) => write!(f, "send {msg}"),
+            Self::Step(step) => write!(f, "step {step:?}"),
+        }
+    }
+}
+
+/// What to do when a fault rule fires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultAction {
+    /// Close the connection, or abandon the swap at a step.
+    Drop,
+    /// Sleep for this many seconds, then carry on.
+    Delay(u64),
+    /// Replace the named field of the message with a different, well-formed value.
+    Corrupt(String),
+    /// Broadcast our contract transactions, then close the connection.
+    BroadcastContracts,
+}
+
+/// A single fault: where it fires, what it does and how often.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaultRule {
+    /// Side the rule applies to.
+    pub role: FaultRole,
+    /// Point the rule fires at.
+    pub at: FaultPoint,
+    /// Action taken when it fires.
+    pub action: FaultAction,
+    /// Number of times the rule fires. Unlimited if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub times: Option<u32>,
+}
+
+impl FaultRule {
+    /// Creates a rule firing every time `at` is reached.
+    pub fn new(role: FaultRole, at: FaultPoint, action: FaultAction) -> Self {
+        Self {
+            role,
+            at,
+            action,
+            times: None,
+        }
+    }
+}
+
+/// A set of fault rules, as loaded from JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaultScenario {
+    /// Rules, checked in order.
+    pub faults: Vec<FaultRule>,
+}
+
+impl FaultScenario {
+    /// Parses a scenario from JSON.
+    pub fn from_json(json: &str) -> Result<Self, FaultError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Reads a scenario from a JSON file.
+    pub fn from_file(path: &Path) -> Result<Self, FaultError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Reads the scenario from [`FAULTS_ENV`], if set. The variable holds the JSON itself when it
+    /// starts with `{`, and a file path otherwise.
+    pub fn from_env() -> Result<Option<Self>, FaultError> {
+        match std::env::var(FAULTS_ENV) {
+            Ok(value) if value.trim().is_empty() => Ok(None),
+            Ok(value) if value.trim_start().starts_with('{') => Self::from_json(&value).map(Some),
+            Ok(path) => Self::from_file(Path::new(&path)).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+/// Errors loading a fault scenario.
+#[derive(Debug)]
+pub enum FaultError {
+    /// The scenario file could not be read.
+    IO(std::io::Error),
+    /// The scenario is not valid JSON or does not match the expected format.
+    Json(serde_json::Error),
+    /// Fault scenarios are not allowed on mainnet.
+    Mainnet,
+}
+
+impl fmt::Display for FaultError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IO(e) => write!(f, "cannot read fault scenario: {e}"),
+            Self::Json(e) => write!(f, "invalid fault scenario: {e}"),
+            Self::Mainnet => write!(f, "fault scenarios are not allowed on mainnet"),
+        }
+    }
+}
+
+impl std::error::Error for FaultError {}
+
+impl From<std::io::Error> for FaultError {
+    fn from(value: std::io::Error) -> Self {
+        Self::IO(value)
+    }
+}
+
+impl From<serde_json::Error> for FaultError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+/// The fault rules of one maker or taker, with how often each has fired.
+///
+/// The default injector has no rules and never fires. Clones share the rules and their fire
+/// counts, so a rule limited by `times` fires that often across all of them.
+#[derive(Debug, Default, Clone)]
+pub struct FaultInjector {
+    rules: Arc<Vec<(FaultRule, AtomicU32)>>,
+}
+
+impl FaultInjector {
+    /// Keeps the rules of `scenario` that apply to `role`.
+    pub fn new(role: FaultRole, scenario: FaultScenario) -> Self {
+        Self {
+            rules: Arc::new(
+                scenario
+                    .faults
+                    .into_iter()
+                    .filter(|rule| rule.role == role)
+                    .map(|rule| (rule, AtomicU32::new(0)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Loads the rules for `role` from [`FAULTS_ENV`]. Fails if a scenario is set on mainnet.
+    pub fn from_env(role: FaultRole, network: Network) -> Result<Self, FaultError> {
+        let Some(scenario) = FaultScenario::from_env()? else {
+            return Ok(Self::default());
+        };
+        if network == Network::Bitcoin {
+            return Err(FaultError::Mainnet);
+        }
+        let injector = Self::new(role, scenario);
+        if !injector.is_empty() {
+            log::warn!(
+                "Fault injection enabled: {} rule(s) for the {:?}",
+                injector.rules.len(),
+                role
+            );
+        }
+        Ok(injector)
+    }
+
+    /// Whether there are no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Fires the rules matching `point`, in order. Delays are slept here; the first other action
+    /// is returned for the caller to carry out.
+    pub fn fire(&self, point: &FaultPoint) -> Option<FaultAction> {
+        self.fire_with(point, |_| false)
+    }
+
+    /// Fires the rules matching a message point built from the message name by `point`.
+    /// Corruptions are applied to `message` here; `drop` and `broadcast_contracts` are returned.
+    pub fn fire_message<M: Serialize + DeserializeOwned>(
+        &self,
+        point: impl Fn(String) -> FaultPoint,
+        message: &mut M,
+    ) -> Option<FaultAction> {
+        if self.is_empty() {
+            return None;
+        }
+        let point = point(message_name(message)?);
+        self.fire_with(&point, |field| {
+            if !corrupt_field(message, field) {
+                log::warn!("Fault at {point}: cannot corrupt field `{field}`");
+            }
+            true
+        })
+    }
+
+    /// Fires the rules matching `point`, giving corruptions to `corrupt`, which returns whether
+    /// it carried them out.
+    fn fire_with(
+        &self,
+        point: &FaultPoint,
+        mut corrupt: impl FnMut(&str) -> bool,
+    ) -> Option<FaultAction> {
+        for (rule, fired) in self.rules.iter() {
+            if rule.at != *point || !Self::take_shot(rule, fired) {
+                continue;
+            }
+            log::warn!("Fault injected at {point}: {:?}", rule.action);
+            match &rule.action {
+                FaultAction::Delay(secs) => thread::sleep(Duration::from_secs(*secs)),
+                FaultAction::Corrupt(field) if corrupt(field) => {}
+                action => return Some(action.clone()),
+            }
+        }
+        None
+    }
+
+    fn take_shot(rule: &FaultRule, fired: &AtomicU32) -> bool {
+        match rule.times {
+            None => true,
+            Some(times) => fired
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                    (n < times).then_some(n + 1)
+                })
+                .is_ok(),
+        }
+    }
+}
+
+/// Name of the enum variant a protocol message is.
+pub fn message_name<M: Serialize>(message: &M) -> Option<String> {
+    match serde_json::to_value(message).ok()? {
+        Value::String(name) => Some(name),
+        Value::Object(map) if map.len() == 1 => map.keys().next().cloned(),
+        _ => None,
+    }
+}
+
+/// Replaces the first field named `field` in `message` with a different value that still
+/// deserializes. Returns false if there is no such field or no such value was found.
+fn corrupt_field<M: Serialize + DeserializeOwned>(message: &mut M, field: &str) -> bool {
+    let Ok(value) = serde_json::to_value(&*message) else {
+        return false;
+    };
+    // Try a few candidates, as not every mutation gives a valid key or signature.
+    for attempt in 0..15 {
+        let mut candidate = value.clone();
+        let Some(target) = find_field(&mut candidate, field) else {
+            return false;
+        };
+        mutate(target, attempt);
+        if let Ok(corrupted) = serde_json::from_value(candidate) {
+            *message = corrupted;
+            return true;
+        }
+    }
+    false
+}
+
+fn find_field<'a>(value: &'a mut Value, field: &str) -> Option<&'a mut Value> {
+    match value {
+        Value::Object(map) => {
+            if map.contains_key(field) {
+                return map.get_mut(field);
+            }
+            map.values_mut().find_map(|v| find_field(v, field))
+        }
+        Value::Array(items) => items.iter_mut().find_map(|v| find_field(v, field)),
+        _ => None,
+    }
+}
+
+/// Changes the first scalar inside `value`, differently for each `attempt`.
+fn mutate(value: &mut Value, attempt: u8) {
+    match value {
+        Value::Bool(b) => *b = !*b,
+        Value::Number(n) => {
+            *value = match n.as_u64() {
+                Some(u) => Value::from(u ^ (1 << attempt)),
+                None => Value::from(n.as_f64().unwrap_or_default() + 1.0 + attempt as f64),
+            }
+        }
+        Value::String(s) => {
+            // Flip the last character to another hex digit, keeping hex encodings valid.
+            let last = s.pop().unwrap_or('0');
+            let digit = last.to_digit(16).unwrap_or(0) as u8;
+            let flipped = (digit ^ (attempt + 1)) & 0xf;
+            s.push(char::from_digit(flipped as u32, 16).unwrap_or('0'));
+        }
+        Value::Array(items) => {
+            if let Some(first) = items.first_mut() {
+                mutate(first, attempt);
+            }
+        }
+        Value::Object(map) => {
+            if let Some(first) = map.values_mut().next() {
+                mutate(first, attempt);
+            }
+        }
+        Value::Null => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Sigs {
+        id: String,
+        sigs: Vec<u8>,
+        count: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum Message {
+        Ping,
+        Sigs(Sigs),
+    }
+
+    #[test]
+    fn test_scenario_parsing() {
+        let json = r#"{"faults": [
+            {"role": "maker", "at": {"recv": "ProofOfFunding"}, "action": "drop"},
+            {"role": "taker", "at": {"step": "funds_broadcast"}, "action": "broadcast_contracts"},
+            {"role": "maker", "at": {"send": "Offer"}, "action": {"delay": 3}, "times": 2},
+            {"role": "maker", "at": {"handled": "SwapDetails"}, "action": {"corrupt": "sigs"}}
+        ]}"#;
+        let scenario = FaultScenario::from_json(json).unwrap();
+        assert_eq!(scenario.faults.len(), 4);
+        assert_eq!(
+            scenario.faults[2],
+            FaultRule {
+                role: FaultRole::Maker,
+                at: FaultPoint::Send("Offer".to_string()),
+                action: FaultAction::Delay(3),
+                times: Some(2),
+            }
+        );
+        assert!(FaultScenario::from_json(r#"{"faults": [{"role": "maker"}]}"#).is_err());
+
+        let injector = FaultInjector::new(FaultRole::Taker, scenario);
+        assert_eq!(injector.rules.len(), 1);
+        assert_eq!(
+            injector.fire(&FaultPoint::Step(FaultStep::FundsBroadcast)),
+            Some(FaultAction::BroadcastContracts)
+        );
+        assert_eq!(
+            injector.fire(&FaultPoint::Step(FaultStep::MakersSelected)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rule_fires_limited_times() {
+        let mut rule = FaultRule::new(
+            FaultRole::Maker,
+            FaultPoint::Recv("Ping".to_string()),
+            FaultAction::Drop,
+        );
+        rule.times = Some(2);
+        let injector = FaultInjector::new(FaultRole::Maker, FaultScenario { faults: vec![rule] });
+
+        let mut message = Message::Ping;
+        for expected in [Some(FaultAction::Drop), Some(FaultAction::Drop), None] {
+            assert_eq!(
+                injector.fire_message(FaultPoint::Recv, &mut message),
+                expected
+            );
+        }
+        assert_eq!(injector.fire_message(FaultPoint::Send, &mut message), None);
+    }
+
+    #[test]
+    fn test_clones_share_fire_counts() {
+        let mut rule = FaultRule::new(
+            FaultRole::Taker,
+            FaultPoint::Step(FaultStep::FundsBroadcast),
+            FaultAction::Drop,
+        );
+        rule.times = Some(1);
+        let injector = FaultInjector::new(FaultRole::Taker, FaultScenario { faults: vec![rule] });
+        let clone = injector.clone();
+
+        let point = FaultPoint::Step(FaultStep::FundsBroadcast);
+        assert_eq!(clone.fire(&point), Some(FaultAction::Drop));
+        assert_eq!(injector.fire(&point), None);
+        assert_eq!(clone.fire(&point), None);
+    }
+
+    #[test]
+    fn test_corrupt_message_field() {
+        let injector = FaultInjector::new(
+            FaultRole::Maker,
+            FaultScenario {
+                faults: vec![
+                    FaultRule::new(
+                        FaultRole::Maker,
+                        FaultPoint::Send("Sigs".to_string()),
+                        FaultAction::Corrupt("sigs".to_string()),
+                    ),
+                    FaultRule::new(
+                        FaultRole::Maker,
+                        FaultPoint::Send("Sigs".to_string()),
+                        FaultAction::Corrupt("id".to_string()),
+                    ),
+                    FaultRule::new(
+                        FaultRole::Maker,
+                        FaultPoint::Send("Sigs".to_string()),
+                        FaultAction::Corrupt("missing".to_string()),
+                    ),
+                ],
+            },
+        );
+        let original = Sigs {
+            id: "ab12".to_string(),
+            sigs: vec![7, 8],
+            count: 2,
+        };
+        let mut message = Message::Sigs(original.clone());
+        assert_eq!(injector.fire_message(FaultPoint::Send, &mut message), None);
+
+        let Message::Sigs(corrupted) = message else {
+            panic!("message variant changed");
+        };
+        assert_ne!(corrupted.sigs[0], original.sigs[0]);
+        assert_eq!(corrupted.sigs[1], original.sigs[1]);
+        assert_ne!(corrupted.id, original.id);
+        assert_eq!(corrupted.id.len(), original.id.len());
+        assert_eq!(corrupted.count, original.count);
+    }
+}
@@ -4,6 +4,7 @@ pub extern crate bitcoin;
 pub extern crate bitcoind;
 
 pub mod error;
+pub mod fault;
 pub mod fee_estimation;
 pub mod maker;
 pub mod nostr_coinswap;
@@ -0,0 +1,406 @@
+//! Maker earnings accounting.
+//!
+//! Aggregates the swaps recorded in the [`MakerSwapTracker`](super::MakerSwapTracker) into a
+//! profit and loss report: fees earned, mining fees paid on the maker's own transactions, and
+//! the liquidity kept locked in contracts. Results are broken down per protocol and per UTC
+//! day, where a swap is accounted for on the day it started.
+//!
+//! A swap earns its fee (incoming minus outgoing contract value) when it completes, or when
+//! the incoming contracts are swept through the hashlock path during recovery. Mining fees
+//! count every transaction whose fee comes out of the maker's coins: outgoing funding,
+//! sweeps, recovery spends, and Legacy contract transactions on the incoming side (or on the
+//! outgoing side when it was reclaimed through the timelock path). Transactions that never
+//! made it on-chain cost nothing and are skipped.
+
+use std::collections::BTreeMap;
+
+use bitcoin::Txid;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    error::MakerError,
+    swap_tracker::{MakerSwapPhase, MakerSwapRecord},
+};
+use crate::{
+    protocol::common_messages::ProtocolVersion,
+    wallet::{BlockchainBackend, Wallet},
+};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Earnings over a set of swaps.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EarningsRow {
+    /// UTC day (`YYYY-MM-DD`), unset for totals over the whole range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Protocol, unset for totals across protocols.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<ProtocolVersion>,
+    /// Number of swaps.
+    pub swaps: u64,
+    /// Swap fees earned, in satoshis.
+    pub fee_earned_sat: u64,
+    /// Mining fees paid, in satoshis.
+    pub mining_fee_sat: u64,
+    /// Fees earned minus mining fees paid, in satoshis.
+    pub net_sat: i64,
+    /// Outgoing liquidity locked in contracts, in BTC times hours locked.
+    pub liquidity_btc_hours: f64,
+}
+
+impl EarningsRow {
+    fn add(&mut self, swap: &SwapEarnings) {
+        self.swaps += 1;
+        self.fee_earned_sat += swap.fee_earned_sat;
+        self.mining_fee_sat += swap.mining_fee_sat;
+        self.net_sat = self.fee_earned_sat as i64 - self.mining_fee_sat as i64;
+        self.liquidity_btc_hours += swap.liquidity_btc_hours;
+    }
+
+    fn to_csv_line(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{:.4}",
+            self.date.as_deref().unwrap_or("all"),
+            self.protocol
+                .map_or("all".to_string(), |p| format!("{p:?}")),
+            self.swaps,
+            self.fee_earned_sat,
+            self.mining_fee_sat,
+            self.net_sat,
+            self.liquidity_btc_hours
+        )
+    }
+}
+
+/// Profit and loss report over a date range.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EarningsReport {
+    /// First day included (`YYYY-MM-DD`), unset for no lower bound.
+    pub from: Option<String>,
+    /// Last day included (`YYYY-MM-DD`), unset for no upper bound.
+    pub to: Option<String>,
+    /// Totals over the whole range.
+    pub total: EarningsRow,
+    /// Totals per protocol.
+    pub by_protocol: Vec<EarningsRow>,
+    /// Totals per day and protocol, oldest first.
+    pub by_day: Vec<EarningsRow>,
+}
+
+impl EarningsReport {
+    /// Renders the report as CSV: one line per day and protocol, then per-protocol totals
+    /// and the grand total, with `all` in the aggregated columns.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "date,protocol,swaps,fee_earned_sat,mining_fee_sat,net_sat,liquidity_btc_hours\n",
+        );
+        for row in self
+            .by_day
+            .iter()
+            .chain(&self.by_protocol)
+            .chain([&self.total])
+        {
+            csv.push_str(&row.to_csv_line());
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Accounting figures of a single swap.
+#[derive(Debug, Clone)]
+struct SwapEarnings {
+    protocol: ProtocolVersion,
+    started_at: u64,
+    fee_earned_sat: u64,
+    mining_fee_sat: u64,
+    liquidity_btc_hours: f64,
+}
+
+/// Builds the earnings report for the swaps started between `from` and `to` (inclusive
+/// `YYYY-MM-DD` UTC days). In-flight swaps are left out, their costs are not final yet.
+pub fn earnings_report<B: BlockchainBackend>(
+    wallet: &Wallet<B>,
+    records: &[MakerSwapRecord],
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<EarningsReport, MakerError> {
+    let from_day = from.map(parse_date).transpose()?;
+    let to_day = to.map(parse_date).transpose()?;
+    if let (Some(from_day), Some(to_day)) = (from_day, to_day) {
+        if from_day > to_day {
+            return Err(MakerError::General("--from must not be after --to"));
+        }
+    }
+
+    let swaps = records
+        .iter()
+        .filter(|r| r.phase != MakerSwapPhase::Active)
+        .filter(|r| {
+            let day = r.created_at / SECONDS_PER_DAY;
+            from_day.is_none_or(|from| day >= from) && to_day.is_none_or(|to| day <= to)
+        })
+        .map(|r| swap_earnings(wallet, r))
+        .collect::<Vec<_>>();
+
+    Ok(aggregate(
+        from_day.map(format_date),
+        to_day.map(format_date),
+        &swaps,
+    ))
+}
+
+fn aggregate(from: Option<String>, to: Option<String>, swaps: &[SwapEarnings]) -> EarningsReport {
+    let mut total = EarningsRow::default();
+    let mut by_protocol = BTreeMap::new();
+    let mut by_day = BTreeMap::new();
+
+    for swap in swaps {
+        let day = swap.started_at / SECONDS_PER_DAY;
+        let rank = protocol_rank(swap.protocol);
+        total.add(swap);
+        by_protocol
+            .entry(rank)
+            .or_insert_with(|| EarningsRow {
+                protocol: Some(swap.protocol),
+                ..Default::default()
+            })
+            .add(swap);
+        by_day
+            .entry((day, rank))
+            .or_insert_with(|| EarningsRow {
+                date: Some(format_date(day)),
+                protocol: Some(swap.protocol),
+                ..Default::default()
+            })
+            .add(swap);
+    }
+
+    EarningsReport {
+        from,
+        to,
+        total,
+        by_protocol: by_protocol.into_values().collect(),
+        by_day: by_day.into_values().collect(),
+    }
+}
+
+fn protocol_rank(protocol: ProtocolVersion) -> u8 {
+    match protocol {
+        ProtocolVersion::Legacy => 0,
+        ProtocolVersion::Taproot => 1,
+    }
+}
+
+fn swap_earnings<B: BlockchainBackend>(
+    wallet: &Wallet<B>,
+    record: &MakerSwapRecord,
+) -> SwapEarnings {
+    let mining_fee_sat = fee_paying_txids(record)
+        .iter()
+        .filter_map(|txid| match wallet.transaction_fee(txid) {
+            Ok(fee) => Some(fee.to_sat()),
+            Err(e) => {
+                log::debug!("[{}] No fee accounted for {txid}: {e:?}", record.swap_id);
+                None
+            }
+        })
+        .sum();
+
+    SwapEarnings {
+        protocol: record.protocol,
+        started_at: record.created_at,
+        fee_earned_sat: fee_earned(record),
+        mining_fee_sat,
+        liquidity_btc_hours: liquidity_btc_hours(record),
+    }
+}
+
+/// Fee earned by a swap, zero unless it completed or was recovered through the hashlock path.
+pub(crate) fn fee_earned(record: &MakerSwapRecord) -> u64 {
+    if record.phase == MakerSwapPhase::Completed || !record.recovery.incoming_swept.is_empty() {
+        record
+            .incoming_amount_sat
+            .saturating_sub(record.outgoing_amount_sat)
+    } else {
+        0
+    }
+}
+
+fn liquidity_btc_hours(record: &MakerSwapRecord) -> f64 {
+    if !record.funding_broadcast {
+        return 0.0;
+    }
+    let locked_secs = record.updated_at.saturating_sub(record.created_at);
+    record.outgoing_amount_sat as f64 / 100_000_000.0 * locked_secs as f64 / 3600.0
+}
+
+/// Transactions of a swap whose mining fee is borne by the maker.
+fn fee_paying_txids(record: &MakerSwapRecord) -> Vec<Txid> {
+    let mut txids = Vec::new();
+    if record.funding_broadcast {
+        txids.extend(&record.outgoing_funding_txids);
+    }
+    if record.protocol == ProtocolVersion::Legacy {
+        // Taproot incoming contracts are the taker's funding, and Taproot outgoing
+        // contracts are already counted as our funding.
+        txids.extend(&record.incoming_contract_txids);
+        if !record.recovery.outgoing_recovered.is_empty() {
+            txids.extend(&record.outgoing_contract_txids);
+        }
+    }
+    txids.extend(&record.sweep_txids);
+    txids.extend(&record.recovery.incoming_swept);
+    txids.extend(&record.recovery.outgoing_recovered);
+
+    let mut unique = Vec::with_capacity(txids.len());
+    for txid in txids {
+        if !unique.contains(&txid) {
+            unique.push(txid);
+        }
+    }
+    unique
+}
+
+/// Parses a `YYYY-MM-DD` date into days since the Unix epoch.
+fn parse_date(date: &str) -> Result<u64, MakerError> {
+    let invalid =
+        || MakerError::General(format!("Invalid date {date}, expected YYYY-MM-DD").leak());
+    let mut parts = date.splitn(3, '-');
+    let mut next = || -> Result<u64, MakerError> {
+        parts
+            .next()
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(invalid)
+    };
+    let (year, month, day) = (next()?, next()?, next()?);
+    if year < 1970 || !(1..=12).contains(&month) || day == 0 {
+        return Err(invalid());
+    }
+    let days = days_from_civil(year, month, day);
+    if format_date(days) != format!("{year:04}-{month:02}-{day:02}") {
+        return Err(invalid());
+    }
+    Ok(days)
+}
+
+/// Formats days since the Unix epoch as `YYYY-MM-DD`.
+fn format_date(days: u64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+// Gregorian calendar conversions, after Howard Hinnant's `days_from_civil` and
+// `civil_from_days`, restricted to dates from 1970 on.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swap(protocol: ProtocolVersion, day: u64, fee: u64, mining_fee: u64) -> SwapEarnings {
+        SwapEarnings {
+            protocol,
+            started_at: day * SECONDS_PER_DAY + 3600,
+            fee_earned_sat: fee,
+            mining_fee_sat: mining_fee,
+            liquidity_btc_hours: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_date_round_trip() {
+        assert_eq!(parse_date("1970-01-01").unwrap(), 0);
+        assert_eq!(parse_date("2024-02-29").unwrap(), 19_782);
+        assert_eq!(format_date(19_782), "2024-02-29");
+        assert_eq!(format_date(20_000), "2024-10-04");
+        for date in [
+            "2023-02-29",
+            "2024-13-01",
+            "2024-1-1x",
+            "1969-12-31",
+            "yesterday",
+        ] {
+            assert!(parse_date(date).is_err(), "{} should be rejected", date);
+        }
+    }
+
+    #[test]
+    fn test_aggregate_per_protocol_and_day() {
+        let swaps = [
+            swap(ProtocolVersion::Taproot, 2, 1_000, 300),
+            swap(ProtocolVersion::Legacy, 1, 500, 700),
+            swap(ProtocolVersion::Taproot, 1, 2_000, 400),
+            swap(ProtocolVersion::Taproot, 1, 1_000, 100),
+        ];
+        let report = aggregate(None, None, &swaps);
+
+        assert_eq!(report.total.swaps, 4);
+        assert_eq!(report.total.fee_earned_sat, 4_500);
+        assert_eq!(report.total.mining_fee_sat, 1_500);
+        assert_eq!(report.total.net_sat, 3_000);
+        assert_eq!(report.total.liquidity_btc_hours, 2.0);
+
+        let legacy = &report.by_protocol[0];
+        assert_eq!(legacy.protocol, Some(ProtocolVersion::Legacy));
+        assert_eq!(legacy.net_sat, -200);
+        assert_eq!(report.by_protocol[1].swaps, 3);
+
+        let days: Vec<_> = report
+            .by_day
+            .iter()
+            .map(|r| (r.date.clone().unwrap(), r.protocol.unwrap(), r.swaps))
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                ("1970-01-02".to_string(), ProtocolVersion::Legacy, 1),
+                ("1970-01-02".to_string(), ProtocolVersion::Taproot, 2),
+                ("1970-01-03".to_string(), ProtocolVersion::Taproot, 1),
+            ]
+        );
+        assert_eq!(report.by_day[1].fee_earned_sat, 3_000);
+    }
+
+    #[test]
+    fn test_csv_output() {
+        let report = aggregate(
+            Some("1970-01-02".to_string()),
+            None,
+            &[swap(ProtocolVersion::Legacy, 1, 500, 700)],
+        );
+        assert_eq!(
+            report.to_csv(),
+            "date,protocol,swaps,fee_earned_sat,mining_fee_sat,net_sat,liquidity_btc_hours\n\
+             1970-01-02,Legacy,1,500,700,-200,0.5000\n\
+             all,Legacy,1,500,700,-200,0.5000\n\
+             all,all,1,500,700,-200,0.5000\n"
+        );
+    }
+}
@@ -5,40 +5,60 @@ use std::{
     io::Write,
     path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex, RwLock,
     },
     thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
 
-use bitcoin::{bip32::ChainCode, Amount, Network, OutPoint, PublicKey, Transaction};
+use bitcoin::{
+    bip32::ChainCode, secp256k1::SecretKey, Amount, Network, OutPoint, PublicKey, Transaction, Txid,
+};
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    fault::{FaultAction, FaultInjector, FaultPoint, FaultRole, FaultStep},
     nostr_coinswap::NOSTR_RELAYS,
-    protocol::common_messages::{FidelityProof, ProtocolVersion, SwapDetails},
-    utill::{get_maker_dir, parse_field, parse_toml, MIN_FEE_RATE},
+    protocol::common_messages::{
+        FidelityProof, PowChallenge, ProtocolVersion, SwapDetails, SwapPrivkey, MAX_POW_DIFFICULTY,
+    },
+    utill::{get_maker_dir, parse_checked_address, parse_field, parse_toml, MIN_FEE_RATE},
     wallet::{
+        parse_cert_signature,
         swapcoin::{IncomingSwapCoin, OutgoingSwapCoin},
-        AddressType, BackendConfig, BitcoindBackend, BlockchainBackend, FidelityError, RPCConfig,
-        Wallet, WalletError, MAX_FIDELITY_TIMELOCK, MIN_FIDELITY_TIMELOCK,
+        AddressType, BackendConfig, BitcoindBackend, BlockchainBackend, FidelityBondStatus,
+        FidelityCertRequest, FidelityError, RPCConfig, Wallet, WalletError, MAX_FIDELITY_TIMELOCK,
+        MIN_FIDELITY_TIMELOCK,
     },
     watch_tower::service::WatchService,
 };
 
-#[cfg(feature = "integration-test")]
-pub use super::handlers::MakerBehavior;
-
 use super::{
     error::MakerError,
+    events::{MakerEvent, MakerEventBus},
     handlers::{ConnectionState, Maker as MakerTrait, MakerConfig, SwapPhase},
+    liquidity::LiquidityPolicy,
+    pricing::{DynamicPricing, PricingInputs},
     rpc::server::MakerRpc,
-    swap_tracker::MakerSwapTracker,
+    swap_tracker::{MakerSwapPhase, MakerSwapRecord, MakerSwapTracker},
 };
 
 /// Minimum swap amount in satoshis.
 pub const MIN_SWAP_AMOUNT: u64 = 10_000;
 
+/// How long the maker serves the same proof-of-work challenge, letting takers reuse their
+/// solution across offerbook syncs.
+const POW_CHALLENGE_LIFETIME: Duration = Duration::from_secs(10 * 60);
+
+/// Total amount of the swaps not completed yet, which still lock liquidity.
+fn in_flight_amount(swaps: &HashMap<String, SwapState>) -> Amount {
+    swaps
+        .values()
+        .filter(|state| state.phase != SwapPhase::Completed)
+        .fold(Amount::ZERO, |total, state| total + state.swap_amount)
+}
+
 /// Swap state tracked per swap_id (persisted across connections).
 #[derive(Debug, Clone)]
 struct SwapState {
@@ -63,6 +83,8 @@ struct SwapState {
     contract_feerate: f64,
     /// Reserved UTXOs for this swap (prevents concurrent double-spending).
     reserve_utxo: Vec<OutPoint>,
+    /// Fee policy of the offer this swap was negotiated against.
+    fee_policy: Option<FeePolicy>,
     /// Last activity timestamp.
     last_activity: Instant,
     /// Time when this swap was accepted by the maker.
@@ -82,6 +104,7 @@ impl Default for SwapState {
             funding_broadcast: false,
             contract_feerate: 0.0,
             reserve_utxo: Vec::new(),
+            fee_policy: None,
             last_activity: Instant::now(),
             swap_start_time: Instant::now(),
         }
@@ -97,6 +120,10 @@ pub struct MakerServerConfig {
     pub network_port: u16,
     /// RPC port for maker-cli commands.
     pub rpc_port: u16,
+    /// Port for the JSON-RPC 2.0 over HTTP interface. `0` disables it.
+    pub json_rpc_port: u16,
+    /// Port for the Prometheus metrics exporter. `0` disables it.
+    pub metrics_port: u16,
     /// Base fee in satoshis per swap.
     pub base_fee: u64,
     /// Amount-relative fee percentage.
@@ -105,6 +132,38 @@ pub struct MakerServerConfig {
     pub time_relative_fee_pct: f64,
     /// Minimum swap amount in satoshis.
     pub min_swap_amount: u64,
+    /// Satoshis of spendable balance never offered for swaps.
+    pub liquidity_reserve: u64,
+    /// Largest single swap in satoshis. `0` means no cap.
+    pub max_swap_size: u64,
+    /// Largest total amount in satoshis across concurrent swaps. `0` means no cap.
+    pub max_inflight_liquidity: u64,
+    /// Significant digits kept in the advertised maximum swap size. `0` disables rounding.
+    pub offer_size_precision: u32,
+    /// Price offers from liquidity utilization, ongoing swaps and feerate. See [`DynamicPricing`].
+    pub dynamic_fees: bool,
+    /// Fee increase at full liquidity utilization, as a fraction of the configured fees.
+    pub fee_utilization_factor: f64,
+    /// Fee increase per swap in progress, as a fraction of the configured fees.
+    pub fee_ongoing_swap_factor: f64,
+    /// Feerate in sats/vByte at which the configured base fee is charged unchanged.
+    pub fee_reference_feerate: f64,
+    /// Lowest multiplier dynamic pricing applies to the configured fees.
+    pub min_fee_multiplier: f64,
+    /// Highest multiplier dynamic pricing applies to the configured fees.
+    pub max_fee_multiplier: f64,
+    /// Maximum concurrent taker connections. `0` means no limit.
+    pub max_connections: usize,
+    /// Maximum concurrent swaps. `0` means no limit.
+    pub max_concurrent_swaps: usize,
+    /// Seconds a connection may spend in each handshake phase, until the swap details are
+    /// accepted. `0` means no limit.
+    pub handshake_timeout: u64,
+    /// Seconds a negotiated swap may hold its liquidity before the maker funds it. `0` means
+    /// no limit.
+    pub reservation_timeout: u64,
+    /// Proof-of-work difficulty, in leading zero bits, required in the hello exchange. `0` disables it.
+    pub pow_difficulty: u8,
     /// Required confirmations for funding transactions.
     pub required_confirms: u32,
     /// Supported protocol versions.
@@ -113,6 +172,9 @@ pub struct MakerServerConfig {
     pub fidelity_amount: u64,
     /// Fidelity bond timelock in blocks.
     pub fidelity_timelock: u32,
+    /// Never fund fidelity bonds from the hot wallet: wait for a bond locked to an offline key
+    /// to be imported and certified instead.
+    pub offline_fidelity_bond: bool,
     /// Bitcoin network.
     pub network: Network,
     /// Selects between Bitcoin Core (`RPCConfig`) and Electrum (`ElectrumConfig`)
@@ -135,14 +197,32 @@ impl Default for MakerServerConfig {
             data_dir: PathBuf::from("./data"),
             network_port: 6102,
             rpc_port: 6103,
+            json_rpc_port: 0,
+            metrics_port: 0,
             base_fee: 500,
             amount_relative_fee_pct: 0.0025,
             time_relative_fee_pct: 0.0001,
             min_swap_amount: 10_000,
+            liquidity_reserve: 0,
+            max_swap_size: 0,
+            max_inflight_liquidity: 0,
+            offer_size_precision: 2,
+            dynamic_fees: false,
+            fee_utilization_factor: 1.0,
+            fee_ongoing_swap_factor: 0.05,
+            fee_reference_feerate: 10.0,
+            min_fee_multiplier: 1.0,
+            max_fee_multiplier: 3.0,
+            max_connections: 64,
+            max_concurrent_swaps: 16,
+            handshake_timeout: 60,
+            reservation_timeout: 3600,
+            pow_difficulty: 0,
             required_confirms: 1,
             supported_protocols: vec![ProtocolVersion::Legacy, ProtocolVersion::Taproot],
             fidelity_amount: 10_000,   // 0.05 BTC
             fidelity_timelock: 15_000, // ~6 months (MAX_FIDELITY_TIMELOCK)
+            offline_fidelity_bond: false,
             network: Network::Regtest,
             backend: BackendConfig::Bitcoind(RPCConfig::default()),
             control_port: 9051,
@@ -206,9 +286,81 @@ impl MakerServerConfig {
             });
         }
 
+        let max_swap_size = parse_field(
+            config_map.get("max_swap_size"),
+            default_config.max_swap_size,
+        );
+        if max_swap_size != 0 && max_swap_size < min_swap_amount {
+            log::warn!(
+                "Configured max_swap_size {max_swap_size} is below min_swap_amount {min_swap_amount} sats, no swap will be offered"
+            );
+        }
+
+        let dynamic_pricing = DynamicPricing {
+            utilization_factor: parse_field(
+                config_map.get("fee_utilization_factor"),
+                default_config.fee_utilization_factor,
+            ),
+            ongoing_swap_factor: parse_field(
+                config_map.get("fee_ongoing_swap_factor"),
+                default_config.fee_ongoing_swap_factor,
+            ),
+            reference_feerate: parse_field(
+                config_map.get("fee_reference_feerate"),
+                default_config.fee_reference_feerate,
+            ),
+            min_multiplier: parse_field(
+                config_map.get("min_fee_multiplier"),
+                default_config.min_fee_multiplier,
+            ),
+            max_multiplier: parse_field(
+                config_map.get("max_fee_multiplier"),
+                default_config.max_fee_multiplier,
+            ),
+        };
+        if let Err(e) = dynamic_pricing.validate() {
+            log::error!("Invalid dynamic pricing config: {e}");
+            return Err(WalletError::General(e.to_string()));
+        }
+
+        let pow_difficulty = parse_field(
+            config_map.get("pow_difficulty"),
+            default_config.pow_difficulty,
+        );
+        if pow_difficulty > MAX_POW_DIFFICULTY {
+            log::error!(
+                "Configured pow_difficulty {pow_difficulty} is above {MAX_POW_DIFFICULTY}, takers would refuse it"
+            );
+            return Err(WalletError::General(format!(
+                "pow_difficulty must be at most {MAX_POW_DIFFICULTY}"
+            )));
+        }
+
+        let supported_protocols = match parse_list(config_map.get("supported_protocols")) {
+            Some(names) => {
+                let protocols = names
+                    .iter()
+                    .map(|name| parse_protocol(name))
+                    .collect::<Option<Vec<_>>>()
+                    .filter(|protocols| !protocols.is_empty())
+                    .ok_or_else(|| {
+                        WalletError::General(format!(
+                            "Invalid supported_protocols: {names:?}, expected Legacy and/or Taproot"
+                        ))
+                    })?;
+                protocols
+            }
+            None => default_config.supported_protocols,
+        };
+
         Ok(MakerServerConfig {
             network_port: parse_field(config_map.get("network_port"), default_config.network_port),
             rpc_port: parse_field(config_map.get("rpc_port"), default_config.rpc_port),
+            json_rpc_port: parse_field(
+                config_map.get("json_rpc_port"),
+                default_config.json_rpc_port,
+            ),
+            metrics_port: parse_field(config_map.get("metrics_port"), default_config.metrics_port),
             base_fee: parse_field(config_map.get("base_fee"), default_config.base_fee),
             amount_relative_fee_pct: parse_field(
                 config_map.get("amount_relative_fee_pct"),
@@ -219,6 +371,42 @@ impl MakerServerConfig {
                 default_config.time_relative_fee_pct,
             ),
             min_swap_amount,
+            liquidity_reserve: parse_field(
+                config_map.get("liquidity_reserve"),
+                default_config.liquidity_reserve,
+            ),
+            max_swap_size,
+            max_inflight_liquidity: parse_field(
+                config_map.get("max_inflight_liquidity"),
+                default_config.max_inflight_liquidity,
+            ),
+            offer_size_precision: parse_field(
+                config_map.get("offer_size_precision"),
+                default_config.offer_size_precision,
+            ),
+            dynamic_fees: parse_field(config_map.get("dynamic_fees"), default_config.dynamic_fees),
+            fee_utilization_factor: dynamic_pricing.utilization_factor,
+            fee_ongoing_swap_factor: dynamic_pricing.ongoing_swap_factor,
+            fee_reference_feerate: dynamic_pricing.reference_feerate,
+            min_fee_multiplier: dynamic_pricing.min_multiplier,
+            max_fee_multiplier: dynamic_pricing.max_multiplier,
+            max_connections: parse_field(
+                config_map.get("max_connections"),
+                default_config.max_connections,
+            ),
+            max_concurrent_swaps: parse_field(
+                config_map.get("max_concurrent_swaps"),
+                default_config.max_concurrent_swaps,
+            ),
+            handshake_timeout: parse_field(
+                config_map.get("handshake_timeout"),
+                default_config.handshake_timeout,
+            ),
+            reservation_timeout: parse_field(
+                config_map.get("reservation_timeout"),
+                default_config.reservation_timeout,
+            ),
+            pow_difficulty,
             required_confirms: parse_field(
                 config_map.get("required_confirms"),
                 default_config.required_confirms,
@@ -228,19 +416,24 @@ impl MakerServerConfig {
                 default_config.fidelity_amount,
             ),
             fidelity_timelock,
+            offline_fidelity_bond: parse_field(
+                config_map.get("offline_fidelity_bond"),
+                default_config.offline_fidelity_bond,
+            ),
             control_port: parse_field(config_map.get("control_port"), default_config.control_port),
             socks_port: parse_field(config_map.get("socks_port"), default_config.socks_port),
             tor_auth_password: parse_field(
                 config_map.get("tor_auth_password"),
                 default_config.tor_auth_password,
             ),
+            supported_protocols,
+            nostr_relays: parse_list(config_map.get("nostr_relays"))
+                .unwrap_or(default_config.nostr_relays),
             // Runtime fields — not read from config file
             data_dir: default_config.data_dir,
             network: default_config.network,
             backend: default_config.backend,
             password: default_config.password,
-            supported_protocols: default_config.supported_protocols,
-            nostr_relays: default_config.nostr_relays,
         })
     }
 
@@ -254,6 +447,10 @@ impl MakerServerConfig {
 network_port = {}
 # RPC port for maker-cli operations
 rpc_port = {}
+# Port for the JSON-RPC 2.0 over HTTP interface on localhost (0 disables it)
+json_rpc_port = {}
+# Port for the Prometheus metrics exporter on localhost (0 disables it)
+metrics_port = {}
 # Socks port for Tor proxy
 socks_port = {}
 # Control port for Tor interface
@@ -262,10 +459,20 @@ control_port = {}
 tor_auth_password = {}
 # Minimum amount in satoshis that can be swapped
 min_swap_amount = {}
+# Satoshis of spendable balance never offered for swaps
+liquidity_reserve = {}
+# Largest single swap in satoshis (0 for no cap)
+max_swap_size = {}
+# Largest total amount in satoshis locked in concurrent swaps (0 for no cap)
+max_inflight_liquidity = {}
+# Significant digits kept in the advertised maximum swap size (0 disables rounding)
+offer_size_precision = {}
 # Fidelity Bond amount in satoshis
 fidelity_amount = {}
 # Fidelity Bond timelock in blocks (must be between {} and {})
 fidelity_timelock = {}
+# Only advertise fidelity bonds locked to an offline key, never fund one from the hot wallet
+offline_fidelity_bond = {}
 # A fixed base fee charged by the Maker for providing its services (in satoshis)
 base_fee = {}
 # A percentage fee based on the swap amount
@@ -274,21 +481,68 @@ amount_relative_fee_pct = {}
 time_relative_fee_pct = {}
 # Required confirmations for funding transactions
 required_confirms = {}
+# Price offers from liquidity utilization, ongoing swaps and feerate
+dynamic_fees = {}
+# Fee increase at full liquidity utilization, as a fraction of the configured fees
+fee_utilization_factor = {}
+# Fee increase per swap in progress, as a fraction of the configured fees
+fee_ongoing_swap_factor = {}
+# Feerate in sats/vByte at which the base fee is charged unchanged
+fee_reference_feerate = {}
+# Lowest multiplier dynamic pricing applies to the configured fees
+min_fee_multiplier = {}
+# Highest multiplier dynamic pricing applies to the configured fees
+max_fee_multiplier = {}
+# Maximum concurrent taker connections (0 for no limit)
+max_connections = {}
+# Maximum concurrent swaps (0 for no limit)
+max_concurrent_swaps = {}
+# Seconds a connection may spend in each handshake phase (0 for no limit)
+handshake_timeout = {}
+# Seconds a negotiated swap may hold liquidity before the maker funds it (0 for no limit)
+reservation_timeout = {}
+# Proof-of-work difficulty in leading zero bits required from takers (0 disables it, at most {})
+pow_difficulty = {}
+# Protocols offered to takers
+supported_protocols = {}
+# Nostr relays fidelity bonds are announced on
+nostr_relays = {}
 ",
             self.network_port,
             self.rpc_port,
+            self.json_rpc_port,
+            self.metrics_port,
             self.socks_port,
             self.control_port,
             self.tor_auth_password,
             self.min_swap_amount,
+            self.liquidity_reserve,
+            self.max_swap_size,
+            self.max_inflight_liquidity,
+            self.offer_size_precision,
             self.fidelity_amount,
             MIN_FIDELITY_TIMELOCK,
             MAX_FIDELITY_TIMELOCK,
             self.fidelity_timelock,
+            self.offline_fidelity_bond,
             self.base_fee,
             self.amount_relative_fee_pct,
             self.time_relative_fee_pct,
             self.required_confirms,
+            self.dynamic_fees,
+            self.fee_utilization_factor,
+            self.fee_ongoing_swap_factor,
+            self.fee_reference_feerate,
+            self.min_fee_multiplier,
+            self.max_fee_multiplier,
+            self.max_connections,
+            self.max_concurrent_swaps,
+            self.handshake_timeout,
+            self.reservation_timeout,
+            MAX_POW_DIFFICULTY,
+            self.pow_difficulty,
+            format_list(self.supported_protocols.iter().map(|p| format!("{p:?}"))),
+            format_list(self.nostr_relays.iter()),
         );
 
         std::fs::create_dir_all(path.parent().expect("Config path should not be root"))?;
@@ -299,6 +553,235 @@ required_confirms = {}
     }
 }
 
+/// Parses a list value, written either as a TOML array of strings or comma-separated.
+fn parse_list(value: Option<&String>) -> Option<Vec<String>> {
+    let value = value?.trim();
+    let value = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(value);
+    Some(
+        value
+            .split(',')
+            .map(|item| item.trim().trim_matches('"').to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
+
+/// Formats a list as a TOML array of strings.
+fn format_list<T: std::fmt::Display>(items: impl Iterator<Item = T>) -> String {
+    let items = items.map(|item| format!("\"{item}\"")).collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
+}
+
+fn parse_protocol(name: &str) -> Option<ProtocolVersion> {
+    match name.to_ascii_lowercase().as_str() {
+        "legacy" => Some(ProtocolVersion::Legacy),
+        "taproot" => Some(ProtocolVersion::Taproot),
+        _ => None,
+    }
+}
+
+/// Outcome of [`MakerServer::reload_config`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigReload {
+    /// Changed settings now in effect.
+    pub applied: Vec<String>,
+    /// Changed settings that only take effect after a restart.
+    pub restart_required: Vec<String>,
+}
+
+fn changed_fields(fields: &[(&str, bool)]) -> Vec<String> {
+    fields
+        .iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Merge a config freshly read from `config.toml` into the running one.
+///
+/// Settings that need a restart keep their `live` values in `new`, and those taken from the
+/// command line are carried over. Returns which changed settings apply now and which wait
+/// for a restart.
+fn merge_reloaded_config(live: &MakerServerConfig, new: &mut MakerServerConfig) -> ConfigReload {
+    // Set on the command line, not in the file.
+    new.data_dir = live.data_dir.clone();
+    new.network = live.network;
+    new.backend = live.backend.clone();
+    new.password = live.password.clone();
+
+    let restart_required = changed_fields(&[
+        ("network_port", new.network_port != live.network_port),
+        ("rpc_port", new.rpc_port != live.rpc_port),
+        ("json_rpc_port", new.json_rpc_port != live.json_rpc_port),
+        ("metrics_port", new.metrics_port != live.metrics_port),
+        ("socks_port", new.socks_port != live.socks_port),
+        ("control_port", new.control_port != live.control_port),
+        (
+            "tor_auth_password",
+            new.tor_auth_password != live.tor_auth_password,
+        ),
+        (
+            "fidelity_amount",
+            new.fidelity_amount != live.fidelity_amount,
+        ),
+        (
+            "fidelity_timelock",
+            new.fidelity_timelock != live.fidelity_timelock,
+        ),
+    ]);
+    new.network_port = live.network_port;
+    new.rpc_port = live.rpc_port;
+    new.json_rpc_port = live.json_rpc_port;
+    new.metrics_port = live.metrics_port;
+    new.socks_port = live.socks_port;
+    new.control_port = live.control_port;
+    new.tor_auth_password = live.tor_auth_password.clone();
+    new.fidelity_amount = live.fidelity_amount;
+    new.fidelity_timelock = live.fidelity_timelock;
+
+    let applied = changed_fields(&[
+        ("base_fee", new.base_fee != live.base_fee),
+        (
+            "amount_relative_fee_pct",
+            new.amount_relative_fee_pct != live.amount_relative_fee_pct,
+        ),
+        (
+            "time_relative_fee_pct",
+            new.time_relative_fee_pct != live.time_relative_fee_pct,
+        ),
+        (
+            "min_swap_amount",
+            new.min_swap_amount != live.min_swap_amount,
+        ),
+        (
+            "liquidity_reserve",
+            new.liquidity_reserve != live.liquidity_reserve,
+        ),
+        ("max_swap_size", new.max_swap_size != live.max_swap_size),
+        (
+            "max_inflight_liquidity",
+            new.max_inflight_liquidity != live.max_inflight_liquidity,
+        ),
+        (
+            "offer_size_precision",
+            new.offer_size_precision != live.offer_size_precision,
+        ),
+        ("dynamic_fees", new.dynamic_fees != live.dynamic_fees),
+        (
+            "fee_utilization_factor",
+            new.fee_utilization_factor != live.fee_utilization_factor,
+        ),
+        (
+            "fee_ongoing_swap_factor",
+            new.fee_ongoing_swap_factor != live.fee_ongoing_swap_factor,
+        ),
+        (
+            "fee_reference_feerate",
+            new.fee_reference_feerate != live.fee_reference_feerate,
+        ),
+        (
+            "min_fee_multiplier",
+            new.min_fee_multiplier != live.min_fee_multiplier,
+        ),
+        (
+            "max_fee_multiplier",
+            new.max_fee_multiplier != live.max_fee_multiplier,
+        ),
+        (
+            "max_connections",
+            new.max_connections != live.max_connections,
+        ),
+        (
+            "max_concurrent_swaps",
+            new.max_concurrent_swaps != live.max_concurrent_swaps,
+        ),
+        (
+            "handshake_timeout",
+            new.handshake_timeout != live.handshake_timeout,
+        ),
+        (
+            "reservation_timeout",
+            new.reservation_timeout != live.reservation_timeout,
+        ),
+        ("pow_difficulty", new.pow_difficulty != live.pow_difficulty),
+        (
+            "required_confirms",
+            new.required_confirms != live.required_confirms,
+        ),
+        (
+            "supported_protocols",
+            new.supported_protocols != live.supported_protocols,
+        ),
+        ("nostr_relays", new.nostr_relays != live.nostr_relays),
+        (
+            "offline_fidelity_bond",
+            new.offline_fidelity_bond != live.offline_fidelity_bond,
+        ),
+    ]);
+
+    ConfigReload {
+        applied,
+        restart_required,
+    }
+}
+
+/// Fee policy advertised in the maker's offer.
+///
+/// Can be changed at runtime via the `SetFeePolicy` RPC. Each swap pins the policy of the
+/// offer it was negotiated against, so updates only apply to swaps negotiated afterwards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeePolicy {
+    /// Base fee in satoshis per swap.
+    pub base_fee: u64,
+    /// Amount-relative fee percentage.
+    pub amount_relative_fee_pct: f64,
+    /// Time-relative fee percentage.
+    pub time_relative_fee_pct: f64,
+    /// Minimum swap amount in satoshis.
+    pub min_swap_amount: u64,
+}
+
+impl FeePolicy {
+    /// Total fee for a swap of `amount` with the given refund `timelock`.
+    pub fn swap_fee(&self, amount: Amount, timelock: u32) -> Amount {
+        let total_fee = self.base_fee as f64
+            + (amount.to_sat() as f64 * self.amount_relative_fee_pct) / 100.00
+            + (amount.to_sat() as f64 * timelock as f64 * self.time_relative_fee_pct) / 100.00;
+        Amount::from_sat(total_fee.ceil() as u64)
+    }
+
+    /// Check the policy is sane, mirroring the checks takers apply to offers.
+    pub fn validate(&self) -> Result<(), MakerError> {
+        for pct in [self.amount_relative_fee_pct, self.time_relative_fee_pct] {
+            if !pct.is_finite() || !(0.0..100.0).contains(&pct) {
+                return Err(MakerError::General(
+                    "Relative fee percentages must be within [0, 100)",
+                ));
+            }
+        }
+        if self.min_swap_amount < MIN_SWAP_AMOUNT {
+            return Err(MakerError::General(
+                "Minimum swap amount is below protocol minimum",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl From<&MakerServerConfig> for FeePolicy {
+    fn from(config: &MakerServerConfig) -> Self {
+        FeePolicy {
+            base_fee: config.base_fee,
+            amount_relative_fee_pct: config.amount_relative_fee_pct,
+            time_relative_fee_pct: config.time_relative_fee_pct,
+            min_swap_amount: config.min_swap_amount,
+        }
+    }
+}
+
 /// Thread pool for managing background threads.
 pub struct ThreadPool {
     threads: Mutex<Vec<JoinHandle<()>>>,
@@ -348,16 +831,33 @@ impl ThreadPool {
 
 /// Maker server, parameterised by the wallet's blockchain backend `B`.
 pub struct MakerServer<B: BlockchainBackend = BitcoindBackend> {
-    /// Configuration.
+    /// Configuration the server was started with. Settings that can be reloaded at runtime
+    /// are read from [`MakerServer::live_config`] instead.
     pub config: MakerServerConfig,
+    /// Current configuration, updated by [`MakerServer::reload_config`].
+    live_config: RwLock<MakerServerConfig>,
     /// Wallet.
     pub wallet: Arc<RwLock<Wallet<B>>>,
     /// Shutdown flag.
     pub shutdown: AtomicBool,
     /// Is setup complete flag.
     pub is_setup_complete: AtomicBool,
+    /// Paused flag. While set, new swaps are rejected but in-flight ones continue.
+    pub paused: AtomicBool,
+    /// Set by a graceful stop: shut down once no swaps are in flight.
+    pub graceful_shutdown: AtomicBool,
     /// Highest fidelity proof.
     pub highest_fidelity_proof: RwLock<Option<FidelityProof>>,
+    /// Address the fidelity proofs commit to, known once the bond is set up.
+    maker_address: RwLock<Option<String>>,
+    /// Bond chosen by the operator to advertise. `None` advertises the highest-value bond.
+    selected_fidelity_index: RwLock<Option<u32>>,
+    /// Live fee policy served in new offers.
+    fee_policy: RwLock<FeePolicy>,
+    /// Backend feerate estimate in sats/vByte used by dynamic pricing.
+    pub(crate) market_feerate: RwLock<Option<f64>>,
+    /// Proof-of-work challenge served to takers and when it was created.
+    pow_challenge: Mutex<Option<(Instant, PowChallenge)>>,
     /// Ongoing swap states by swap_id.
     ongoing_swaps: Mutex<HashMap<String, SwapState>>,
     /// Watch service for contract monitoring.
@@ -368,11 +868,12 @@ pub struct MakerServer<B: BlockchainBackend = BitcoindBackend> {
     pub data_dir: PathBuf,
     /// Persistent swap tracker for recovery progress.
     pub swap_tracker: Mutex<MakerSwapTracker>,
-    /// Nostr relay URLs for fidelity bond broadcasting.
-    pub nostr_relays: Vec<String>,
-    /// Test-only behavior override.
-    #[cfg(feature = "integration-test")]
-    pub behavior: MakerBehavior,
+    /// Real-time events streamed to RPC subscribers.
+    pub events: MakerEventBus,
+    /// Number of open taker connections.
+    pub active_connections: AtomicUsize,
+    /// Faults injected into the swap flow, for adversarial testing.
+    pub faults: FaultInjector,
 }
 
 /// Idle swap data returned by [`MakerServer::drain_idle_swaps`].
@@ -402,17 +903,18 @@ impl<B: BlockchainBackend> MakerServer<B> {
     pub fn setup_fidelity_bond(&self, maker_address: &str) -> Result<FidelityProof, MakerError> {
         use bitcoin::absolute::LockTime;
 
-        let highest_index = self
-            .wallet
-            .read()
-            .map_err(|_| MakerError::General("Failed to lock wallet"))?
-            .get_highest_fidelity_index()
-            .map_err(MakerError::Wallet)?;
+        *self.maker_address.write()? = Some(maker_address.to_string());
 
-        let mut proof = self
-            .highest_fidelity_proof
-            .write()
-            .map_err(|_| MakerError::General("Failed to lock fidelity proof"))?;
+        self.refresh_fidelity_bonds()?;
+        if self.live_config().offline_fidelity_bond {
+            self.wait_for_offline_fidelity_bond(maker_address)?;
+        }
+
+        let highest_index = self.fidelity_index_to_advertise()?;
+
+        // The advertised proof is only locked to update it: waiting for funds below must not
+        // block RPC calls reading it.
+        let mut proof = None;
 
         if let Some(i) = highest_index {
             // Existing fidelity bond found
@@ -452,7 +954,16 @@ impl<B: BlockchainBackend> MakerServer<B> {
                 bond_value
             );
 
-            *proof = Some(highest_proof);
+            proof = Some(highest_proof);
+        } else if let Some((index, txid)) = self.pending_fidelity_bond()? {
+            // A bond was broadcast but has not confirmed yet, don't create another one.
+            log::info!(
+                "[{}] Waiting for pending fidelity bond {} to confirm: {}",
+                self.config.network_port,
+                index,
+                txid
+            );
+            proof = Some(self.finalize_fidelity_bond(index, txid, maker_address)?);
         } else {
             // Need to create new fidelity bond
             log::info!("No active Fidelity Bonds found. Creating one.");
@@ -470,19 +981,20 @@ impl<B: BlockchainBackend> MakerServer<B> {
 
             // Set locktime for test (950 blocks) or production
             #[cfg(feature = "integration-test")]
-            let locktime = {
-                use super::handlers::MakerBehavior;
-                let offset = if self.behavior == MakerBehavior::InvalidFidelityTimelock {
-                    log::warn!("Test behavior: using invalid (too short) fidelity timelock");
-                    10
-                } else {
-                    950
-                };
-                LockTime::from_height(current_height + offset).map_err(WalletError::Locktime)?
-            };
+            let timelock = 950;
             #[cfg(not(feature = "integration-test"))]
-            let locktime = LockTime::from_height(self.config.fidelity_timelock + current_height)
-                .map_err(WalletError::Locktime)?;
+            let timelock = self.config.fidelity_timelock;
+            let timelock = if matches!(
+                self.faults.fire(&FaultPoint::Step(FaultStep::FidelityBond)),
+                Some(FaultAction::Corrupt(_))
+            ) {
+                log::warn!("Using an invalid (too short) fidelity timelock");
+                10
+            } else {
+                timelock
+            };
+            let locktime =
+                LockTime::from_height(current_height + timelock).map_err(WalletError::Locktime)?;
 
             log::info!(
                 "Fidelity timelock {:?} blocks",
@@ -550,54 +1062,397 @@ impl<B: BlockchainBackend> MakerServer<B> {
                         }
                     }
                     Ok((index, txid)) => {
-                        // Wait for confirmation without holding the write lock.
-                        log::info!(
-                            "[{}] Fidelity bond broadcast, waiting for confirmation: {}",
-                            self.config.network_port,
-                            txid
-                        );
-                        let conf_height = self
-                            .wallet
-                            .read()
-                            .map_err(|_| MakerError::General("Failed to lock wallet"))?
-                            .wait_for_tx_confirmation(&[txid], 1, Some(&self.shutdown), None)
-                            .map_err(MakerError::Wallet)?;
-
-                        // Re-acquire write lock briefly to finalize
-                        self.wallet
-                            .write()
-                            .map_err(|_| MakerError::General("Failed to lock wallet"))?
-                            .update_fidelity_bond_conf_details(index, conf_height)
-                            .map_err(MakerError::Wallet)?;
-
-                        log::info!(
-                            "[{}] Successfully created fidelity bond",
-                            self.config.network_port
-                        );
-                        let highest_proof = self
-                            .wallet
-                            .read()
-                            .map_err(|_| MakerError::General("Failed to lock wallet"))?
-                            .generate_fidelity_proof(index, maker_address)
-                            .map_err(MakerError::Wallet)?;
-
-                        *proof = Some(highest_proof);
-
-                        log::info!("Sync at end:----setup_fidelity_bond----");
-                        self.wallet
-                            .write()
-                            .map_err(|_| MakerError::General("Failed to lock wallet"))?
-                            .sync_and_save()
-                            .map_err(MakerError::Wallet)?;
+                        proof = Some(self.finalize_fidelity_bond(index, txid, maker_address)?);
                         break;
                     }
                 }
             }
         }
 
-        proof
+        let proof = proof.ok_or(MakerError::General("No fidelity proof after setup"))?;
+        *self
+            .highest_fidelity_proof
+            .write()
+            .map_err(|_| MakerError::General("Failed to lock fidelity proof"))? =
+            Some(proof.clone());
+        Ok(proof)
+    }
+
+    /// Pick up bonds created or imported over RPC that confirmed since the last check, and
+    /// offline-key bonds redeemed by their key holder.
+    fn refresh_fidelity_bonds(&self) -> Result<(), MakerError> {
+        let mut wallet = self.wallet.write()?;
+        wallet.confirm_pending_fidelity_bonds()?;
+        wallet.mark_redeemed_external_fidelity_bonds()?;
+        Ok(())
+    }
+
+    /// Wait until a fidelity bond can be advertised, without creating one from the hot wallet.
+    ///
+    /// Offline-key bonds are registered with `maker-cli import-fidelity` and become advertisable
+    /// once confirmed and certified with `maker-cli certify-fidelity`.
+    fn wait_for_offline_fidelity_bond(&self, maker_address: &str) -> Result<(), MakerError> {
+        const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+        const LOG_INTERVAL: Duration = Duration::from_secs(300);
+        let mut last_log: Option<Instant> = None;
+
+        while !self.shutdown.load(Ordering::Relaxed) {
+            let error = match self.fidelity_index_to_advertise()? {
+                Some(index) => match self
+                    .wallet
+                    .read()?
+                    .generate_fidelity_proof(index, maker_address)
+                {
+                    Ok(_) => return Ok(()),
+                    Err(e) => format!("bond {index} can't be advertised: {e}"),
+                },
+                None => "no fidelity bond to advertise".to_string(),
+            };
+            if last_log.is_none_or(|at| at.elapsed() >= LOG_INTERVAL) {
+                log::info!(
+                    "[{}] Waiting for an offline fidelity bond, {error}. Import one with `maker-cli import-fidelity` and certify it with `maker-cli certify-fidelity`.",
+                    self.config.network_port
+                );
+                last_log = Some(Instant::now());
+            }
+            thread::sleep(CHECK_INTERVAL);
+            self.refresh_fidelity_bonds()?;
+        }
+
+        Err(MakerError::General(
+            "Shutdown while waiting for an offline fidelity bond",
+        ))
+    }
+
+    /// An unspent bond that was broadcast but is not confirmed yet, as `(index, txid)`.
+    ///
+    /// External-key bonds without a certificate are skipped, no proof can be made for them.
+    fn pending_fidelity_bond(&self) -> Result<Option<(u32, Txid)>, MakerError> {
+        let wallet = self.wallet.read()?;
+        let current_height = wallet.rpc.get_block_count().map_err(WalletError::Rpc)? as u32;
+        Ok(wallet
+            .get_fidelity_bonds()
+            .iter()
+            .enumerate()
+            .find(|(_, bond)| {
+                bond.status(current_height) == FidelityBondStatus::Unconfirmed
+                    && (!bond.external_key || bond.external_cert.is_some())
+            })
+            .map(|(i, bond)| (i as u32, bond.outpoint().txid)))
+    }
+
+    /// Wait for a broadcast fidelity bond to confirm, record it and generate its proof.
+    fn finalize_fidelity_bond(
+        &self,
+        index: u32,
+        txid: Txid,
+        maker_address: &str,
+    ) -> Result<FidelityProof, MakerError> {
+        // Wait for confirmation without holding the write lock.
+        log::info!(
+            "[{}] Fidelity bond broadcast, waiting for confirmation: {}",
+            self.config.network_port,
+            txid
+        );
+        let conf_height = self
+            .wallet
+            .read()
+            .map_err(|_| MakerError::General("Failed to lock wallet"))?
+            .wait_for_tx_confirmation(&[txid], 1, Some(&self.shutdown), None)
+            .map_err(MakerError::Wallet)?;
+
+        // Re-acquire write lock briefly to finalize
+        self.wallet
+            .write()
+            .map_err(|_| MakerError::General("Failed to lock wallet"))?
+            .update_fidelity_bond_conf_details(index, conf_height)
+            .map_err(MakerError::Wallet)?;
+
+        log::info!(
+            "[{}] Successfully created fidelity bond",
+            self.config.network_port
+        );
+        let highest_proof = self
+            .wallet
+            .read()
+            .map_err(|_| MakerError::General("Failed to lock wallet"))?
+            .generate_fidelity_proof(index, maker_address)
+            .map_err(MakerError::Wallet)?;
+        self.events.emit(MakerEvent::BondRenewed {
+            index,
+            outpoint: highest_proof.bond.outpoint(),
+            amount_sat: highest_proof.bond.amount.to_sat(),
+            locktime: highest_proof.bond.lock_time.to_consensus_u32(),
+        });
+
+        log::info!("Sync at end:----setup_fidelity_bond----");
+        self.wallet
+            .write()
+            .map_err(|_| MakerError::General("Failed to lock wallet"))?
+            .sync_and_save()
+            .map_err(MakerError::Wallet)?;
+
+        Ok(highest_proof)
+    }
+
+    /// Index of the bond to advertise: the operator's selection while it is live, otherwise
+    /// the highest-value bond.
+    fn fidelity_index_to_advertise(&self) -> Result<Option<u32>, MakerError> {
+        let wallet = self.wallet.read()?;
+        let mut selected = self.selected_fidelity_index.write()?;
+        if let Some(i) = *selected {
+            let current_height = wallet.rpc.get_block_count().map_err(WalletError::Rpc)? as u32;
+            match wallet
+                .get_fidelity_bonds()
+                .get(i as usize)
+                .map(|bond| bond.status(current_height))
+            {
+                Some(FidelityBondStatus::Live) => return Ok(Some(i)),
+                status => {
+                    log::warn!(
+                        "[{}] Selected fidelity bond {} is no longer live ({:?}), advertising the highest-value bond",
+                        self.config.network_port,
+                        i,
+                        status
+                    );
+                    *selected = None;
+                }
+            }
+        }
+        wallet
+            .get_highest_fidelity_index()
+            .map_err(MakerError::Wallet)
+    }
+
+    /// Index of the fidelity bond currently advertised in offers, if any.
+    pub fn advertised_fidelity_index(&self) -> Result<Option<u32>, MakerError> {
+        Ok(self
+            .highest_fidelity_proof
+            .read()?
+            .as_ref()
+            .map(|proof| proof.bond.bond_index))
+    }
+
+    /// Create and broadcast a fidelity bond of `amount`, timelocked for `timelock` blocks.
+    ///
+    /// Returns the bond index and funding txid. Once confirmed, the bond is picked up at the
+    /// next fidelity bond check and advertised if it is the highest-value (or selected) one.
+    pub fn create_fidelity_bond(
+        &self,
+        amount: Amount,
+        timelock: u32,
+        feerate: f64,
+    ) -> Result<(u32, Txid), MakerError> {
+        use bitcoin::absolute::LockTime;
+
+        if !(MIN_FIDELITY_TIMELOCK..=MAX_FIDELITY_TIMELOCK).contains(&timelock) {
+            return Err(MakerError::General(
+                format!(
+                    "Invalid fidelity timelock: {timelock} blocks. Accepted range is [{MIN_FIDELITY_TIMELOCK}-{MAX_FIDELITY_TIMELOCK}] blocks."
+                )
+                .leak(),
+            ));
+        }
+        let maker_address = self
+            .maker_address
+            .read()?
+            .clone()
+            .ok_or(MakerError::General(
+                "Fidelity bond setup has not completed yet",
+            ))?;
+
+        let mut wallet = self.wallet.write()?;
+        let current_height = wallet.rpc.get_block_count().map_err(WalletError::Rpc)? as u32;
+        let locktime =
+            LockTime::from_height(current_height + timelock).map_err(WalletError::Locktime)?;
+        let (index, txid) = wallet.create_fidelity(
+            amount,
+            locktime,
+            Some(&maker_address),
+            feerate,
+            AddressType::P2TR,
+        )?;
+        wallet.save_to_disk()?;
+
+        log::info!(
+            "[{}] Fidelity bond {} of {} sats broadcast, locked until height {}: {}",
+            self.config.network_port,
+            index,
+            amount.to_sat(),
+            locktime,
+            txid
+        );
+        Ok((index, txid))
+    }
+
+    /// Redeem the expired fidelity bond at `index`, sweeping it back into the wallet.
+    pub fn redeem_fidelity_bond(&self, index: u32, feerate: f64) -> Result<Txid, MakerError> {
+        let mut wallet = self.wallet.write()?;
+        let current_height = wallet.rpc.get_block_count().map_err(WalletError::Rpc)? as u32;
+        let bond = wallet
+            .get_fidelity_bonds()
+            .get(index as usize)
+            .ok_or(WalletError::Fidelity(FidelityError::BondDoesNotExist))?;
+
+        match bond.status(current_height) {
+            FidelityBondStatus::Expired => {}
+            FidelityBondStatus::Redeemed => {
+                return Err(WalletError::Fidelity(FidelityError::BondAlreadyRedeemed).into())
+            }
+            status => {
+                return Err(MakerError::General(
+                    format!(
+                        "Fidelity bond {index} is {status}, it can be redeemed in {} blocks",
+                        bond.blocks_to_expiry(current_height) + 1
+                    )
+                    .leak(),
+                ))
+            }
+        }
+
+        let txid = wallet
+            .redeem_fidelity(index, feerate, AddressType::P2TR)?
+            .ok_or(WalletError::Fidelity(FidelityError::BondAlreadyRedeemed))?;
+        wallet.save_to_disk()?;
+
+        log::info!(
+            "[{}] Fidelity bond {} redeemed: {}",
+            self.config.network_port,
+            index,
+            txid
+        );
+        Ok(txid)
+    }
+
+    /// Advertise the live bond at `index`, or the highest-value bond again with `None`.
+    ///
+    /// The selection lasts until the bond expires or makerd restarts. Returns the index of
+    /// the bond advertised from now on.
+    pub fn select_fidelity_bond(&self, index: Option<u32>) -> Result<u32, MakerError> {
+        let maker_address = self
+            .maker_address
+            .read()?
+            .clone()
+            .ok_or(MakerError::General(
+                "Fidelity bond setup has not completed yet",
+            ))?;
+
+        if let Some(i) = index {
+            let wallet = self.wallet.read()?;
+            let current_height = wallet.rpc.get_block_count().map_err(WalletError::Rpc)? as u32;
+            let status = wallet
+                .get_fidelity_bonds()
+                .get(i as usize)
+                .ok_or(WalletError::Fidelity(FidelityError::BondDoesNotExist))?
+                .status(current_height);
+            if status != FidelityBondStatus::Live {
+                return Err(MakerError::General(
+                    format!("Fidelity bond {i} is {status}, only live bonds can be advertised")
+                        .leak(),
+                ));
+            }
+        }
+        *self.selected_fidelity_index.write()? = index;
+
+        let advertised = self
+            .fidelity_index_to_advertise()?
+            .ok_or(MakerError::General("No live fidelity bond to advertise"))?;
+        let proof = self
+            .wallet
+            .read()?
+            .generate_fidelity_proof(advertised, &maker_address)?;
+        *self.highest_fidelity_proof.write()? = Some(proof);
+
+        log::info!(
+            "[{}] Advertising fidelity bond {}",
+            self.config.network_port,
+            advertised
+        );
+        Ok(advertised)
+    }
+
+    /// Register a fidelity bond locked to an offline key, returning the certificate its key
+    /// must sign before the bond can be advertised.
+    ///
+    /// `locktime` is the absolute block height of the bond's timelock.
+    pub fn import_fidelity_bond(
+        &self,
+        outpoint: OutPoint,
+        pubkey: PublicKey,
+        locktime: u32,
+    ) -> Result<FidelityCertRequest, MakerError> {
+        use bitcoin::absolute::LockTime;
+
+        let maker_address = self.fidelity_maker_address()?;
+        let locktime = LockTime::from_height(locktime).map_err(WalletError::Locktime)?;
+
+        let mut wallet = self.wallet.write()?;
+        let index = wallet.import_fidelity_bond(outpoint, pubkey, locktime)?;
+        let request = wallet.fidelity_cert_request(index, &maker_address)?;
+
+        log::info!(
+            "[{}] Offline fidelity bond {} imported at {}, locked until height {}",
+            self.config.network_port,
+            index,
+            outpoint,
+            locktime
+        );
+        Ok(request)
+    }
+
+    /// The certificate the offline key of the bond at `index` must sign for this maker.
+    pub fn fidelity_cert_request(&self, index: u32) -> Result<FidelityCertRequest, MakerError> {
+        let maker_address = self.fidelity_maker_address()?;
+        Ok(self
+            .wallet
+            .read()?
+            .fidelity_cert_request(index, &maker_address)?)
+    }
+
+    /// Import the certificate signature of the offline bond at `index`, either from
+    /// `signmessage` (base64) or as a DER hex signature.
+    ///
+    /// The bond is advertised at the next fidelity bond check if it is the highest-value one,
+    /// or right away with [`MakerServer::select_fidelity_bond`].
+    pub fn certify_fidelity_bond(&self, index: u32, signature: &str) -> Result<(), MakerError> {
+        let maker_address = self.fidelity_maker_address()?;
+        let cert_sig = parse_cert_signature(signature)?;
+        self.wallet
+            .write()?
+            .import_fidelity_cert(index, &maker_address, cert_sig)?;
+
+        log::info!(
+            "[{}] Certificate of offline fidelity bond {} imported",
+            self.config.network_port,
+            index
+        );
+        Ok(())
+    }
+
+    /// Unsigned PSBT, in base64, sweeping the offline bond at `index` to `address`, to be signed
+    /// by its key holder once the bond expires.
+    pub fn export_fidelity_redeem(
+        &self,
+        index: u32,
+        address: &str,
+        feerate: f64,
+    ) -> Result<String, MakerError> {
+        let destination = parse_checked_address(address, self.config.network)?;
+        let psbt = self
+            .wallet
+            .read()?
+            .fidelity_redeem_psbt(index, &destination, feerate)?;
+        Ok(psbt.to_string())
+    }
+
+    /// The maker address fidelity certificates are signed for.
+    fn fidelity_maker_address(&self) -> Result<String, MakerError> {
+        self.maker_address
+            .read()?
             .clone()
-            .ok_or(MakerError::General("No fidelity proof after setup"))
+            .ok_or(MakerError::General(
+                "Fidelity bond setup has not started yet",
+            ))
     }
 
     /// Check if maker has enough liquidity for swaps.
@@ -621,16 +1476,15 @@ impl<B: BlockchainBackend> MakerServer<B> {
                 .sync_and_save()
                 .map_err(MakerError::Wallet)?;
 
-            let offer_max_size = self
-                .wallet
-                .read()
-                .map_err(|_| MakerError::General("Failed to lock wallet"))?
-                .store
-                .offer_maxsize;
+            let offer_max_size = self.advertised_max_size()?;
 
-            let min_required = self.config.min_swap_amount;
+            let min_required = self.current_fee_policy().min_swap_amount;
 
             if offer_max_size < min_required {
+                self.events.emit(MakerEvent::LowLiquidity {
+                    available_sat: offer_max_size,
+                    required_sat: min_required,
+                });
                 log::warn!(
                     "Low Swap Liquidity | Min: {min_required} sats | Available: {offer_max_size} sats. Add funds to {addr:?}"
                 );
@@ -681,6 +1535,36 @@ impl<B: BlockchainBackend> MakerServer<B> {
         idle
     }
 
+    /// Atomically remove negotiated swaps idle for longer than `timeout` that the maker has
+    /// not funded yet, releasing their liquidity reservation. Returns their swap ids.
+    pub fn drain_stale_reservations(&self, timeout: Duration) -> Vec<String> {
+        let mut swaps = self.ongoing_swaps.lock().unwrap();
+        let stale_ids: Vec<String> = swaps
+            .iter()
+            .filter(|(_, state)| {
+                state.last_activity.elapsed() > timeout && state.outgoing_swapcoins.is_empty()
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &stale_ids {
+            swaps.remove(id);
+        }
+        stale_ids
+    }
+
+    /// Mark a swap in progress as active, e.g. on a taker keepalive. Returns whether the swap
+    /// is known.
+    pub fn touch_swap(&self, swap_id: &str) -> bool {
+        let mut swaps = self.ongoing_swaps.lock().unwrap();
+        match swaps.get_mut(swap_id) {
+            Some(state) => {
+                state.last_activity = Instant::now();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Remove a completed swap's entry from `ongoing_swaps`.
     pub fn remove_swap_state(&self, swap_id: &str) {
         let mut swaps = self.ongoing_swaps.lock().unwrap();
@@ -692,6 +1576,178 @@ impl<B: BlockchainBackend> MakerServer<B> {
         !self.ongoing_swaps.lock().unwrap().is_empty()
     }
 
+    /// Number of swaps currently in progress.
+    pub fn ongoing_swap_count(&self) -> usize {
+        self.ongoing_swaps.lock().unwrap().len()
+    }
+
+    /// Phases of the swaps currently in progress.
+    pub fn ongoing_swap_phases(&self) -> Vec<SwapPhase> {
+        self.ongoing_swaps
+            .lock()
+            .unwrap()
+            .values()
+            .map(|state| state.phase)
+            .collect()
+    }
+
+    /// The current configuration, including settings reloaded since startup.
+    pub fn live_config(&self) -> MakerServerConfig {
+        self.live_config
+            .read()
+            .map(|config| config.clone())
+            .unwrap_or_else(|_| self.config.clone())
+    }
+
+    /// Reload `config.toml` from the data directory.
+    ///
+    /// Fees, liquidity and admission limits, dynamic pricing, required confirmations, supported
+    /// protocols and nostr relays apply right away, to swaps negotiated from now on. Ports,
+    /// Tor and fidelity bond settings keep their running values until makerd restarts.
+    pub fn reload_config(&self) -> Result<ConfigReload, MakerError> {
+        let path = self.data_dir.join("config.toml");
+        if !path.exists() {
+            return Err(MakerError::General(
+                "config.toml not found in data directory",
+            ));
+        }
+        let mut new = MakerServerConfig::new(Some(&path))?;
+        let fee_policy = FeePolicy::from(&new);
+        fee_policy.validate()?;
+
+        let mut live = self.live_config.write()?;
+        let ConfigReload {
+            applied,
+            restart_required,
+        } = merge_reloaded_config(&live, &mut new);
+
+        *live = new;
+        drop(live);
+        *self.fee_policy.write()? = fee_policy;
+
+        log::info!(
+            "[{}] Config reloaded. Applied: {:?}, restart required: {:?}",
+            self.config.network_port,
+            applied,
+            restart_required
+        );
+        Ok(ConfigReload {
+            applied,
+            restart_required,
+        })
+    }
+
+    /// The liquidity policy from the config.
+    pub fn liquidity_policy(&self) -> LiquidityPolicy {
+        LiquidityPolicy::from(&self.live_config())
+    }
+
+    /// Maximum swap size advertised in offers, after applying the [`LiquidityPolicy`] to the
+    /// wallet's fundable balance and the swaps in progress.
+    pub fn advertised_max_size(&self) -> Result<u64, MakerError> {
+        let in_flight = in_flight_amount(&*self.ongoing_swaps.lock()?).to_sat();
+        let offer_maxsize = self
+            .wallet
+            .read()
+            .map_err(|_| MakerError::General("Failed to lock wallet"))?
+            .store
+            .offer_maxsize;
+        Ok(self
+            .liquidity_policy()
+            .advertised_max_size(offer_maxsize, in_flight))
+    }
+
+    /// The fee policy currently served in new offers.
+    pub fn current_fee_policy(&self) -> FeePolicy {
+        self.fee_policy
+            .read()
+            .map(|p| p.clone())
+            .unwrap_or_else(|_| FeePolicy::from(&self.config))
+    }
+
+    /// The fee policy priced into new offers: the current policy, adjusted by
+    /// [`DynamicPricing`] when `dynamic_fees` is enabled.
+    pub fn served_fee_policy(&self) -> FeePolicy {
+        let fee_policy = self.current_fee_policy();
+        let config = self.live_config();
+        if !config.dynamic_fees {
+            return fee_policy;
+        }
+
+        let (in_flight, ongoing_swaps) = {
+            let swaps = self.ongoing_swaps.lock().unwrap();
+            (in_flight_amount(&swaps).to_sat(), swaps.len())
+        };
+        let offer_maxsize = self
+            .wallet
+            .read()
+            .map(|wallet| wallet.store.offer_maxsize)
+            .unwrap_or(0);
+        let capacity = in_flight + self.liquidity_policy().usable_liquidity(offer_maxsize);
+        let inputs = PricingInputs {
+            utilization: if capacity == 0 {
+                1.0
+            } else {
+                in_flight as f64 / capacity as f64
+            },
+            ongoing_swaps,
+            feerate: self.market_feerate.read().ok().and_then(|feerate| *feerate),
+        };
+        DynamicPricing::from(&config).apply(&fee_policy, &inputs)
+    }
+
+    /// Replace the live fee policy and persist it to `config.toml` in the data directory.
+    ///
+    /// Swaps already negotiated keep the policy of the offer they accepted.
+    pub fn update_fee_policy(&self, fee_policy: FeePolicy) -> Result<(), MakerError> {
+        fee_policy.validate()?;
+
+        let mut config = self.live_config.write()?;
+        config.base_fee = fee_policy.base_fee;
+        config.amount_relative_fee_pct = fee_policy.amount_relative_fee_pct;
+        config.time_relative_fee_pct = fee_policy.time_relative_fee_pct;
+        config.min_swap_amount = fee_policy.min_swap_amount;
+        config.write_to_file(&self.data_dir.join("config.toml"))?;
+        drop(config);
+
+        log::info!(
+            "[{}] Fee policy updated: {:?}",
+            self.config.network_port,
+            fee_policy
+        );
+        *self.fee_policy.write()? = fee_policy;
+        Ok(())
+    }
+
+    /// Snapshot in-flight swaps as `Active` tracker records.
+    ///
+    /// These swaps only live in memory until they complete or get dropped,
+    /// so they are not yet part of the persistent [`MakerSwapTracker`].
+    pub fn ongoing_swap_records(&self) -> Vec<MakerSwapRecord> {
+        let swaps = self.ongoing_swaps.lock().unwrap();
+        swaps
+            .iter()
+            .map(|(swap_id, state)| {
+                let mut record = MakerSwapRecord::from_swapcoins(
+                    swap_id.clone(),
+                    state.protocol,
+                    MakerSwapPhase::Active,
+                    state.swap_amount.to_sat(),
+                    &state.incoming_swapcoins,
+                    &state.outgoing_swapcoins,
+                    state.funding_broadcast,
+                );
+                record.created_at = record
+                    .created_at
+                    .saturating_sub(state.swap_start_time.elapsed().as_secs());
+                record.updated_at = record
+                    .updated_at
+                    .saturating_sub(state.last_activity.elapsed().as_secs());
+                record
+            })
+            .collect()
+    }
+
     /// Initialize a maker server. Backend `B` is picked at the call site via turbofish;
     /// the matching variant is pulled from `config.backend`.
     #[hotpath::measure]
@@ -716,6 +1772,8 @@ impl<B: BlockchainBackend> MakerServer<B> {
             );
             config.network = wallet_network;
         }
+        let faults = FaultInjector::from_env(FaultRole::Maker, config.network)
+            .map_err(|e| MakerError::General(e.to_string().leak()))?;
 
         let (zmq_addr, electrum_url, chain) = match &config.backend {
             BackendConfig::Bitcoind(rpc) => {
@@ -751,21 +1809,28 @@ impl<B: BlockchainBackend> MakerServer<B> {
             );
             swap_tracker.log_state();
         }
-        let nostr_relays = config.nostr_relays.clone();
         Ok(MakerServer {
             config: config.clone(),
+            live_config: RwLock::new(config.clone()),
             wallet: Arc::new(RwLock::new(wallet)),
             shutdown: AtomicBool::new(false),
             is_setup_complete: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+            graceful_shutdown: AtomicBool::new(false),
             highest_fidelity_proof: RwLock::new(None),
+            maker_address: RwLock::new(None),
+            selected_fidelity_index: RwLock::new(None),
+            fee_policy: RwLock::new(FeePolicy::from(&config)),
+            market_feerate: RwLock::new(None),
+            pow_challenge: Mutex::new(None),
             ongoing_swaps: Mutex::new(HashMap::new()),
             watch_service,
             thread_pool: Arc::new(ThreadPool::new(config.network_port)),
             data_dir,
             swap_tracker: Mutex::new(swap_tracker),
-            nostr_relays,
-            #[cfg(feature = "integration-test")]
-            behavior: MakerBehavior::default(),
+            events: MakerEventBus::default(),
+            active_connections: AtomicUsize::new(0),
+            faults,
         })
     }
 }
@@ -795,31 +1860,72 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
             .ok_or(MakerError::General("No fidelity proof available"))
     }
 
+    fn emit_event(&self, event: MakerEvent) {
+        self.events.emit(event);
+    }
+
+    fn is_accepting_swaps(&self) -> bool {
+        !self.paused.load(Ordering::Relaxed)
+    }
+
+    fn has_swap_capacity(&self) -> bool {
+        let max_swaps = self.live_config().max_concurrent_swaps;
+        max_swaps == 0 || self.ongoing_swap_count() < max_swaps
+    }
+
+    fn pow_challenge(&self) -> Option<PowChallenge> {
+        let difficulty = self.live_config().pow_difficulty;
+        if difficulty == 0 {
+            return None;
+        }
+        let mut current = self
+            .pow_challenge
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match &*current {
+            Some((issued_at, challenge))
+                if challenge.difficulty == difficulty
+                    && issued_at.elapsed() < POW_CHALLENGE_LIFETIME =>
+            {
+                Some(challenge.clone())
+            }
+            _ => {
+                let challenge = PowChallenge::new(difficulty);
+                *current = Some((Instant::now(), challenge.clone()));
+                Some(challenge)
+            }
+        }
+    }
+
     fn get_config(&self) -> MakerConfig {
+        let fee_policy = self.served_fee_policy();
+        let config = self.live_config();
         MakerConfig {
-            base_fee: self.config.base_fee,
-            amount_relative_fee_pct: self.config.amount_relative_fee_pct,
-            time_relative_fee_pct: self.config.time_relative_fee_pct,
-            min_swap_amount: self.config.min_swap_amount,
-            max_swap_amount: self
-                .wallet
-                .read()
-                .map(|w| w.store.offer_maxsize)
-                .unwrap_or(u64::MAX),
-            required_confirms: self.config.required_confirms,
-            supported_protocols: self.config.supported_protocols.clone(),
+            base_fee: fee_policy.base_fee,
+            amount_relative_fee_pct: fee_policy.amount_relative_fee_pct,
+            time_relative_fee_pct: fee_policy.time_relative_fee_pct,
+            min_swap_amount: fee_policy.min_swap_amount,
+            max_swap_amount: self.advertised_max_size().unwrap_or(0),
+            required_confirms: config.required_confirms,
+            supported_protocols: config.supported_protocols,
         }
     }
 
     #[hotpath::measure]
-    fn validate_swap_parameters(&self, details: &SwapDetails) -> Result<(), MakerError> {
+    fn validate_swap_parameters(
+        &self,
+        details: &SwapDetails,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<(), MakerError> {
         use super::handlers::MIN_CONTRACT_REACTION_TIME;
 
         let config = self.get_config();
+        let min_swap_amount =
+            fee_policy.map_or(config.min_swap_amount, |policy| policy.min_swap_amount);
 
         // Check amount is within bounds
         let amount_sat = details.amount.to_sat();
-        if amount_sat < config.min_swap_amount {
+        if amount_sat < min_swap_amount {
             return Err(MakerError::General("Swap amount below minimum"));
         }
         if amount_sat > config.max_swap_amount {
@@ -827,19 +1933,25 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
         }
 
         // Check protocol is supported
-        if !self
-            .config
+        if !config
             .supported_protocols
             .contains(&details.protocol_version)
         {
             return Err(MakerError::General("Protocol version not supported"));
         }
 
-        // Check maker has enough liquidity to fund the outgoing swap
+        // Check maker has enough liquidity to fund the outgoing swap, keeping the reserve
         if let Ok(wallet) = self.wallet.read() {
             if let Ok(balances) = wallet.get_balances() {
-                let swap_liquidity = balances.regular + balances.swap;
+                let swap_liquidity = Amount::from_sat(
+                    self.liquidity_policy()
+                        .usable_liquidity((balances.regular + balances.swap).to_sat()),
+                );
                 if swap_liquidity < details.amount {
+                    self.events.emit(MakerEvent::LowLiquidity {
+                        available_sat: swap_liquidity.to_sat(),
+                        required_sat: details.amount.to_sat(),
+                    });
                     return Err(MakerError::General(
                         "Not enough liquidity for this swap amount",
                     ));
@@ -867,11 +1979,7 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
     }
 
     fn calculate_swap_fee(&self, amount: Amount, timelock: u32) -> Amount {
-        let total_fee = self.config.base_fee as f64
-            + (amount.to_sat() as f64 * self.config.amount_relative_fee_pct) / 100.00
-            + (amount.to_sat() as f64 * timelock as f64 * self.config.time_relative_fee_pct)
-                / 100.00;
-        Amount::from_sat(total_fee.ceil() as u64)
+        self.served_fee_policy().swap_fee(amount, timelock)
     }
 
     fn network(&self) -> Network {
@@ -1042,6 +2150,9 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
                 self.config.network_port,
                 sweep_outcome.resolved.len(),
             );
+            self.swap_tracker
+                .lock()?
+                .record_sweeps(&sweep_outcome.resolved)?;
         }
 
         // Sync and save wallet state
@@ -1074,19 +2185,30 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
                 .read()
                 .map_err(|_| MakerError::General("Failed to lock wallet"))?;
             let balances = wallet.get_balances().map_err(MakerError::Wallet)?;
-            Some(balances.regular + balances.swap)
+            Some(Amount::from_sat(self.liquidity_policy().usable_liquidity(
+                (balances.regular + balances.swap).to_sat(),
+            )))
         } else {
             None
         };
 
         let mut swaps = self.ongoing_swaps.lock()?;
         if let Some(swap_liquidity) = swap_liquidity.filter(|_| !swaps.contains_key(swap_id)) {
-            let reserved_liquidity = swaps
-                .values()
-                .filter(|state| state.phase != SwapPhase::Completed)
-                .fold(Amount::ZERO, |total, state| total + state.swap_amount);
+            let reserved_liquidity = in_flight_amount(&swaps);
             let required_liquidity = reserved_liquidity + state.swap_amount;
 
+            let max_swaps = self.live_config().max_concurrent_swaps;
+            if max_swaps != 0 && swaps.len() >= max_swaps {
+                log::warn!(
+                    "[{}] Rejecting swap {}: {} swaps in progress, limit is {}",
+                    self.config.network_port,
+                    swap_id,
+                    swaps.len(),
+                    max_swaps,
+                );
+                return Err(MakerError::General("Too many concurrent swaps"));
+            }
+
             if swap_liquidity < required_liquidity {
                 log::warn!(
                     "[{}] Rejecting swap {}: available liquidity {}, active reservations {}, requested {}",
@@ -1096,6 +2218,10 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
                     reserved_liquidity,
                     state.swap_amount,
                 );
+                self.events.emit(MakerEvent::LowLiquidity {
+                    available_sat: swap_liquidity.to_sat(),
+                    required_sat: required_liquidity.to_sat(),
+                });
                 return Err(MakerError::InsufficientLiquidity {
                     available: swap_liquidity,
                     reserved: reserved_liquidity,
@@ -1115,6 +2241,7 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
         swap_state.funding_broadcast = state.funding_broadcast;
         swap_state.contract_feerate = state.contract_feerate;
         swap_state.reserve_utxo = state.reserve_utxo.clone();
+        swap_state.fee_policy = state.fee_policy.clone();
         swap_state.last_activity = Instant::now();
         swap_state.swap_start_time = state.swap_start_time;
         log::debug!(
@@ -1144,6 +2271,7 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
             state.funding_broadcast = s.funding_broadcast;
             state.contract_feerate = s.contract_feerate;
             state.reserve_utxo = s.reserve_utxo.clone();
+            state.fee_policy = s.fee_policy.clone();
             state.swap_start_time = s.swap_start_time;
             state
         })
@@ -1153,6 +2281,49 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
         self.remove_swap_state(swap_id);
     }
 
+    fn completed_swap_privkeys(
+        &self,
+        swap_id: &str,
+        privkeys: &[SwapPrivkey],
+    ) -> Result<Vec<SecretKey>, MakerError> {
+        let record = self
+            .swap_tracker
+            .lock()?
+            .get_record(swap_id)
+            .filter(|r| r.phase == MakerSwapPhase::Completed)
+            .cloned()
+            .ok_or(MakerError::General("No completed swap with this id"))?;
+
+        // Only the taker side of the swap knows the keys of our incoming contracts.
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let derived: Vec<PublicKey> = privkeys
+            .iter()
+            .map(|p| PublicKey {
+                compressed: true,
+                inner: bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &p.key),
+            })
+            .collect();
+        if record.handover_pubkeys.is_empty() || derived != record.handover_pubkeys {
+            return Err(MakerError::General(
+                "Handed over private keys don't match the completed swap",
+            ));
+        }
+
+        let wallet = self.wallet.read()?;
+        record
+            .outgoing_contract_txids
+            .iter()
+            .map(|txid| {
+                wallet
+                    .find_outgoing_swapcoin(&txid.to_string())
+                    .and_then(|swapcoin| swapcoin.my_privkey)
+                    .ok_or(MakerError::General(
+                        "Outgoing swapcoin of the completed swap not found",
+                    ))
+            })
+            .collect()
+    }
+
     fn data_dir(&self) -> &std::path::Path {
         &self.data_dir
     }
@@ -1509,9 +2680,8 @@ impl<B: BlockchainBackend> MakerTrait for MakerServer<B> {
         None
     }
 
-    #[cfg(feature = "integration-test")]
-    fn behavior(&self) -> MakerBehavior {
-        self.behavior
+    fn faults(&self) -> &FaultInjector {
+        &self.faults
     }
 }
 
@@ -1533,6 +2703,98 @@ impl<B: BlockchainBackend> MakerRpc for MakerServer<B> {
         &self.shutdown
     }
 
+    fn swap_records(&self) -> Result<Vec<MakerSwapRecord>, MakerError> {
+        let mut records = self
+            .swap_tracker
+            .lock()?
+            .list_records(None)
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        for ongoing in self.ongoing_swap_records() {
+            if !records.iter().any(|r| r.swap_id == ongoing.swap_id) {
+                records.push(ongoing);
+            }
+        }
+        records.sort_by_key(|r| r.created_at);
+        Ok(records)
+    }
+
+    fn paused(&self) -> &AtomicBool {
+        &self.paused
+    }
+
+    fn graceful_shutdown(&self) -> &AtomicBool {
+        &self.graceful_shutdown
+    }
+
+    fn ongoing_swap_count(&self) -> usize {
+        MakerServer::ongoing_swap_count(self)
+    }
+
+    fn events(&self) -> &MakerEventBus {
+        &self.events
+    }
+
+    fn fee_policy(&self) -> FeePolicy {
+        self.current_fee_policy()
+    }
+
+    fn set_fee_policy(&self, fee_policy: FeePolicy) -> Result<(), MakerError> {
+        self.update_fee_policy(fee_policy)
+    }
+
+    fn advertised_fidelity_index(&self) -> Result<Option<u32>, MakerError> {
+        MakerServer::advertised_fidelity_index(self)
+    }
+
+    fn reload_config(&self) -> Result<ConfigReload, MakerError> {
+        MakerServer::reload_config(self)
+    }
+
+    fn import_fidelity_bond(
+        &self,
+        outpoint: OutPoint,
+        pubkey: PublicKey,
+        locktime: u32,
+    ) -> Result<FidelityCertRequest, MakerError> {
+        MakerServer::import_fidelity_bond(self, outpoint, pubkey, locktime)
+    }
+
+    fn fidelity_cert_request(&self, index: u32) -> Result<FidelityCertRequest, MakerError> {
+        MakerServer::fidelity_cert_request(self, index)
+    }
+
+    fn certify_fidelity_bond(&self, index: u32, signature: &str) -> Result<(), MakerError> {
+        MakerServer::certify_fidelity_bond(self, index, signature)
+    }
+
+    fn export_fidelity_redeem(
+        &self,
+        index: u32,
+        address: &str,
+        feerate: f64,
+    ) -> Result<String, MakerError> {
+        MakerServer::export_fidelity_redeem(self, index, address, feerate)
+    }
+
+    fn create_fidelity_bond(
+        &self,
+        amount: Amount,
+        timelock: u32,
+        feerate: f64,
+    ) -> Result<(u32, Txid), MakerError> {
+        MakerServer::create_fidelity_bond(self, amount, timelock, feerate)
+    }
+
+    fn redeem_fidelity_bond(&self, index: u32, feerate: f64) -> Result<Txid, MakerError> {
+        MakerServer::redeem_fidelity_bond(self, index, feerate)
+    }
+
+    fn select_fidelity_bond(&self, index: Option<u32>) -> Result<u32, MakerError> {
+        MakerServer::select_fidelity_bond(self, index)
+    }
+
     fn get_tor_hostname(&self) -> Result<String, crate::utill::TorError> {
         let tor_key_bytes = self
             .wallet
@@ -1549,3 +2811,33 @@ impl<B: BlockchainBackend> MakerRpc for MakerServer<B> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_reloaded_config() {
+        let live = MakerServerConfig::default();
+        let mut new = MakerServerConfig {
+            base_fee: live.base_fee + 100,
+            network_port: live.network_port + 1,
+            tor_auth_password: "changed".to_string(),
+            password: Some("from the file".to_string()),
+            ..MakerServerConfig::default()
+        };
+
+        let reload = merge_reloaded_config(&live, &mut new);
+
+        assert_eq!(reload.applied, vec!["base_fee".to_string()]);
+        assert_eq!(
+            reload.restart_required,
+            vec!["network_port".to_string(), "tor_auth_password".to_string()]
+        );
+        // Restart-only and command line settings keep their running values.
+        assert_eq!(new.base_fee, live.base_fee + 100);
+        assert_eq!(new.network_port, live.network_port);
+        assert_eq!(new.tor_auth_password, live.tor_auth_password);
+        assert_eq!(new.password, live.password);
+    }
+}
@@ -9,9 +9,6 @@ use crate::{
     watch_tower::watcher_error::WatcherError,
 };
 
-#[cfg(feature = "integration-test")]
-use super::handlers::MakerBehavior;
-
 /// Enum to handle Maker-related errors.
 ///
 /// This enum encapsulates different types of errors that can occur while interacting
@@ -47,9 +44,6 @@ pub enum MakerError {
     Wallet(WalletError),
     /// Represents a network-related error.
     Net(NetError),
-    /// Represents an error triggered by special maker behavior.
-    #[cfg(feature = "integration-test")]
-    SpecialBehaviour(MakerBehavior),
     /// Represents a protocol-related error.
     Protocol(ProtocolError),
     /// Tor Error.
@@ -112,13 +106,6 @@ impl From<WalletError> for MakerError {
     }
 }
 
-#[cfg(feature = "integration-test")]
-impl From<MakerBehavior> for MakerError {
-    fn from(value: MakerBehavior) -> Self {
-        Self::SpecialBehaviour(value)
-    }
-}
-
 impl From<NetError> for MakerError {
     fn from(value: NetError) -> Self {
         Self::Net(value)
@@ -0,0 +1,262 @@
+//! Real-time maker events.
+//!
+//! The maker publishes a [`MakerEvent`] whenever something an operator may want to react to
+//! happens: a swap is negotiated, funded, completed or dropped, a recovery starts or ends, a
+//! fidelity bond is renewed, or liquidity runs low. Events are fanned out by the
+//! [`MakerEventBus`] to every RPC subscriber (`maker-cli subscribe`, or the `subscribe`
+//! JSON-RPC method). Nothing is buffered for absent subscribers, events are only delivered
+//! to connections open at the time they happen.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering::Relaxed},
+        mpsc::{sync_channel, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use bitcoin::{OutPoint, Txid};
+use serde::{Deserialize, Serialize};
+
+use super::{error::MakerError, swap_tracker::now_secs};
+use crate::protocol::common_messages::ProtocolVersion;
+
+/// Maximum number of concurrent event subscribers.
+pub const MAX_EVENT_SUBSCRIBERS: usize = 16;
+
+/// Events queued per subscriber. A subscriber falling further behind is disconnected.
+const SUBSCRIBER_QUEUE_SIZE: usize = 256;
+
+/// Something that happened in the maker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MakerEvent {
+    /// A taker's swap request was accepted.
+    SwapNegotiated {
+        /// The swap identifier.
+        swap_id: String,
+        /// Protocol used for the swap.
+        protocol: ProtocolVersion,
+        /// Swap amount in satoshis.
+        amount_sat: u64,
+        /// Contract timelock requested by the taker.
+        timelock: u32,
+    },
+    /// The maker broadcast its outgoing funding transactions.
+    FundingBroadcast {
+        /// The swap identifier.
+        swap_id: String,
+        /// Txids of the broadcast transactions.
+        txids: Vec<Txid>,
+    },
+    /// Private keys were handed over, the swap completed cooperatively.
+    SwapCompleted {
+        /// The swap identifier.
+        swap_id: String,
+        /// Amount received in the incoming contracts, in satoshis.
+        incoming_amount_sat: u64,
+        /// Amount sent in the outgoing contracts, in satoshis.
+        outgoing_amount_sat: u64,
+        /// Fee earned, in satoshis.
+        fee_earned_sat: u64,
+    },
+    /// The taker went idle mid-swap and the swap was handed over to recovery.
+    TakerDropped {
+        /// The swap identifier.
+        swap_id: String,
+        /// Whether the maker had already broadcast its funding.
+        funding_broadcast: bool,
+    },
+    /// Recovery of a dropped swap started.
+    RecoveryStarted {
+        /// The swap identifier.
+        swap_id: String,
+    },
+    /// Recovery of a dropped swap finished.
+    RecoveryFinished {
+        /// The swap identifier.
+        swap_id: String,
+        /// Recovery path used: `"hashlock"`, `"timelock"`, or `"none"` if nothing was on-chain.
+        recovery_type: String,
+        /// Txids of the recovery transactions.
+        txids: Vec<Txid>,
+    },
+    /// A new fidelity bond was confirmed and is now advertised.
+    BondRenewed {
+        /// Index of the bond in the wallet.
+        index: u32,
+        /// Outpoint locking the bond.
+        outpoint: OutPoint,
+        /// Bond amount in satoshis.
+        amount_sat: u64,
+        /// Block height at which the bond expires.
+        locktime: u32,
+    },
+    /// Spendable liquidity is too low to serve a swap.
+    LowLiquidity {
+        /// Spendable swap liquidity in satoshis.
+        available_sat: u64,
+        /// Liquidity that was needed, in satoshis.
+        required_sat: u64,
+    },
+}
+
+/// A [`MakerEvent`] as delivered to subscribers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MakerNotification {
+    /// Unix timestamp at which the event was emitted.
+    pub timestamp: u64,
+    /// The event.
+    pub event: MakerEvent,
+}
+
+/// Receiving end of a subscription, see [`MakerEventBus::subscribe`].
+///
+/// Dropping it unsubscribes.
+#[derive(Debug)]
+pub struct EventSubscription {
+    rx: Receiver<MakerNotification>,
+    // Shared with the bus, which prunes subscribers whose handle is gone.
+    _alive: Arc<()>,
+}
+
+impl EventSubscription {
+    /// Waits up to `timeout` for the next event.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<MakerNotification, RecvTimeoutError> {
+        self.rx.recv_timeout(timeout)
+    }
+}
+
+/// Fans out [`MakerEvent`]s to all current subscribers.
+#[derive(Debug, Default)]
+pub struct MakerEventBus {
+    subscribers: Mutex<Vec<(SyncSender<MakerNotification>, Arc<()>)>>,
+}
+
+impl MakerEventBus {
+    /// Registers a new subscriber. Fails once [`MAX_EVENT_SUBSCRIBERS`] are connected.
+    pub fn subscribe(&self) -> Result<EventSubscription, MakerError> {
+        let mut subscribers = self.subscribers.lock()?;
+        subscribers.retain(|(_, alive)| Arc::strong_count(alive) > 1);
+        if subscribers.len() >= MAX_EVENT_SUBSCRIBERS {
+            return Err(MakerError::General("Too many event subscribers"));
+        }
+        let (tx, rx) = sync_channel(SUBSCRIBER_QUEUE_SIZE);
+        let alive = Arc::new(());
+        subscribers.push((tx, Arc::clone(&alive)));
+        Ok(EventSubscription { rx, _alive: alive })
+    }
+
+    /// Publishes an event to every subscriber, dropping the ones that are gone or lagging.
+    pub fn emit(&self, event: MakerEvent) {
+        log::debug!("Maker event: {event:?}");
+        let notification = MakerNotification {
+            timestamp: now_secs(),
+            event,
+        };
+        let Ok(mut subscribers) = self.subscribers.lock() else {
+            return;
+        };
+        subscribers.retain(|(tx, _)| match tx.try_send(notification.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                log::warn!("Event subscriber is lagging behind, disconnecting it");
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+    }
+
+    /// Number of connected subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers
+            .lock()
+            .map(|s| {
+                s.iter()
+                    .filter(|(_, alive)| Arc::strong_count(alive) > 1)
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+}
+
+/// Forwards events of a subscription through `send` until the maker shuts down, the bus drops the
+/// subscriber, or `send` fails because the client went away.
+pub(crate) fn forward_events(
+    subscription: &EventSubscription,
+    shutdown: &AtomicBool,
+    mut send: impl FnMut(&MakerNotification) -> Result<(), MakerError>,
+) {
+    while !shutdown.load(Relaxed) {
+        match subscription.recv_timeout(Duration::from_secs(1)) {
+            Ok(notification) => {
+                if let Err(e) = send(&notification) {
+                    log::info!("Event subscriber disconnected: {e:?}");
+                    break;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn low_liquidity() -> MakerEvent {
+        MakerEvent::LowLiquidity {
+            available_sat: 1,
+            required_sat: 2,
+        }
+    }
+
+    #[test]
+    fn test_event_fan_out() {
+        let bus = MakerEventBus::default();
+        let first = bus.subscribe().unwrap();
+        let second = bus.subscribe().unwrap();
+
+        bus.emit(low_liquidity());
+        assert_eq!(first.rx.try_recv().unwrap().event, low_liquidity());
+        assert_eq!(second.rx.try_recv().unwrap().event, low_liquidity());
+
+        drop(second);
+        assert_eq!(bus.subscriber_count(), 1);
+    }
+
+    #[test]
+    fn test_lagging_subscriber_is_dropped() {
+        let bus = MakerEventBus::default();
+        let rx = bus.subscribe().unwrap();
+        for _ in 0..=SUBSCRIBER_QUEUE_SIZE {
+            bus.emit(low_liquidity());
+        }
+        assert_eq!(bus.subscriber_count(), 0);
+        assert_eq!(rx.rx.try_iter().count(), SUBSCRIBER_QUEUE_SIZE);
+    }
+
+    #[test]
+    fn test_event_json_shape() {
+        let notification = MakerNotification {
+            timestamp: 7,
+            event: MakerEvent::RecoveryStarted {
+                swap_id: "abc".to_string(),
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&notification).unwrap(),
+            serde_json::json!({
+                "timestamp": 7,
+                "event": { "type": "recovery_started", "swap_id": "abc" }
+            })
+        );
+        let cbor = serde_cbor::to_vec(&notification).unwrap();
+        assert_eq!(
+            serde_cbor::from_slice::<MakerNotification>(&cbor).unwrap(),
+            notification
+        );
+    }
+}
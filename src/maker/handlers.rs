@@ -2,14 +2,16 @@
 
 use std::{sync::Arc, time::Instant};
 
-use bitcoin::{bip32::ChainCode, Amount, PublicKey, Transaction};
+use bitcoin::{bip32::ChainCode, secp256k1::SecretKey, Amount, PublicKey, ScriptBuf, Transaction};
 
-use super::error::MakerError;
+use super::{api::FeePolicy, error::MakerError, events::MakerEvent};
 use crate::{
+    fault::FaultInjector,
     protocol::{
         common_messages::{
             AckSwapDetails, FidelityProof, GetOffer, MakerHello, MakerToTakerMessage, Offer,
-            ProtocolVersion, SwapDetails, TakerHello, TakerToMakerMessage,
+            PowChallenge, PowSolution, PrivateKeyHandover, ProtocolVersion, SwapDetails,
+            SwapPrivkey, TakerHello, TakerToMakerMessage,
         },
         legacy_messages::LegacyTakerMessage,
         taproot_messages::TaprootTakerMessage,
@@ -17,40 +19,6 @@ use crate::{
     wallet::swapcoin::{IncomingSwapCoin, OutgoingSwapCoin},
 };
 
-/// Test-only behavior overrides for the maker.
-#[cfg(feature = "integration-test")]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum MakerBehavior {
-    /// Normal operation (no test override).
-    #[default]
-    Normal,
-    /// Receive contract sigs and save swapcoins, but skip funding broadcast
-    /// and close the connection. Simulates last-maker misbehavior.
-    SkipFundingBroadcast,
-    /// Close connection when receiving ReqContractSigsForSender (abort2 scenarios).
-    CloseAtReqContractSigsForSender,
-    /// Close connection when receiving ProofOfFunding (abort2 scenario).
-    CloseAtProofOfFunding,
-    /// Close connection when receiving RespContractSigsForRecvrAndSender (abort3 scenario).
-    CloseAtContractSigsForRecvrAndSender,
-    /// Close connection when receiving ReqContractSigsForRecvr (abort3 scenario).
-    CloseAtContractSigsForRecvr,
-    /// Close connection when receiving PrivateKeyHandover / hash preimage (abort3 scenario).
-    CloseAtHashPreimage,
-    /// Broadcast contract transactions after setup, then close (malice scenario).
-    BroadcastContractAfterSetup,
-    /// Close connection after sending AckSwapDetails (taproot maker abort).
-    CloseAfterAckResponse,
-    /// Close connection at private key handover phase (taproot maker abort).
-    CloseAtPrivateKeyHandover,
-    /// Close connection at contract sigs exchange (taproot recovery test).
-    CloseAtContractSigsExchange,
-    /// Close connection after sweep (taproot hashlock recovery test).
-    CloseAfterSweep,
-    /// Use an invalid fidelity bond timelock (fidelity timelock violation test).
-    InvalidFidelityTimelock,
-}
-
 /// Minimum time required to react to contract broadcasts (in blocks).
 pub const MIN_CONTRACT_REACTION_TIME: u16 = 10;
 
@@ -81,6 +49,10 @@ pub struct ConnectionState {
     pub funding_broadcast: bool,
     /// Reserved UTXOs for this swap (prevents concurrent double-spending).
     pub reserve_utxo: Vec<bitcoin::OutPoint>,
+    /// Fee policy of the offer sent on this connection, pinned for the swap's lifetime.
+    pub fee_policy: Option<FeePolicy>,
+    /// Proof-of-work challenge issued on this connection, awaiting its solution.
+    pub pow_challenge: Option<PowChallenge>,
     /// Last activity timestamp.
     pub last_activity: Instant,
     /// Swap start time for duration tracking in reports.
@@ -121,6 +93,8 @@ impl Default for ConnectionState {
             contract_feerate: 0.0,
             funding_broadcast: false,
             reserve_utxo: Vec::new(),
+            fee_policy: None,
+            pow_challenge: None,
             last_activity: Instant::now(),
             swap_start_time: Instant::now(),
         }
@@ -161,6 +135,16 @@ impl ConnectionState {
         }
     }
 
+    /// Fee for this swap, using the fee policy pinned when the offer was sent.
+    ///
+    /// Falls back to the maker's live policy if no offer was served for this swap.
+    pub fn swap_fee<M: Maker + ?Sized>(&self, maker: &M, amount: Amount, timelock: u32) -> Amount {
+        match &self.fee_policy {
+            Some(fee_policy) => fee_policy.swap_fee(amount, timelock),
+            None => maker.calculate_swap_fee(amount, timelock),
+        }
+    }
+
     /// Verify that the incoming message's swap_id matches the state's swap_id.
     /// If the state has no swap_id yet (initial setup), this is a no-op.
     pub fn check_swap_id(&self, msg_swap_id: &str) -> Result<(), MakerError> {
@@ -198,8 +182,26 @@ pub trait Maker: Send + Sync {
     /// Get maker configuration values.
     fn get_config(&self) -> MakerConfig;
 
-    /// Validate swap parameters.
-    fn validate_swap_parameters(&self, details: &SwapDetails) -> Result<(), MakerError>;
+    /// Publish an event to RPC subscribers.
+    fn emit_event(&self, event: MakerEvent);
+
+    /// Whether new swaps are currently accepted (false while paused for maintenance).
+    fn is_accepting_swaps(&self) -> bool;
+
+    /// Whether another concurrent swap fits under the configured limit.
+    fn has_swap_capacity(&self) -> bool;
+
+    /// Current proof-of-work challenge takers must solve before getting the offer, if any.
+    fn pow_challenge(&self) -> Option<PowChallenge>;
+
+    /// Validate swap parameters against the fee policy pinned for the swap.
+    ///
+    /// Falls back to the live policy if no offer was served for this swap.
+    fn validate_swap_parameters(
+        &self,
+        details: &SwapDetails,
+        fee_policy: Option<&FeePolicy>,
+    ) -> Result<(), MakerError>;
 
     /// Calculate the swap fee.
     fn calculate_swap_fee(&self, amount: Amount, timelock: u32) -> Amount;
@@ -250,6 +252,14 @@ pub trait Maker: Send + Sync {
     /// Remove connection state for a completed swap.
     fn remove_connection_state(&self, swap_id: &str);
 
+    /// Outgoing private keys of a completed swap, served again to a taker resuming it.
+    /// `privkeys` must be the keys the taker handed over for the incoming contracts.
+    fn completed_swap_privkeys(
+        &self,
+        swap_id: &str,
+        privkeys: &[SwapPrivkey],
+    ) -> Result<Vec<SecretKey>, MakerError>;
+
     /// Get the data directory path for saving reports.
     fn data_dir(&self) -> &std::path::Path;
 
@@ -298,9 +308,8 @@ pub trait Maker: Send + Sync {
         multisig_redeemscript: &bitcoin::ScriptBuf,
     ) -> Option<OutgoingSwapCoin>;
 
-    /// Get the test behavior override.
-    #[cfg(feature = "integration-test")]
-    fn behavior(&self) -> MakerBehavior;
+    /// Get the injected faults.
+    fn faults(&self) -> &FaultInjector;
 }
 
 /// Maker configuration values.
@@ -341,6 +350,7 @@ pub fn handle_message<M: Maker>(
 
     match message {
         TakerToMakerMessage::TakerHello(hello) => handle_taker_hello(maker, state, hello),
+        TakerToMakerMessage::PowSolution(solution) => handle_pow_solution(maker, state, solution),
         TakerToMakerMessage::GetOffer(get_offer) => handle_get_offer(maker, state, get_offer),
         TakerToMakerMessage::SwapDetails(details) => handle_swap_details(maker, state, details),
 
@@ -401,6 +411,39 @@ fn handle_taker_hello<M: Maker>(
         Maker::network_port(maker.as_ref()),
     );
 
+    // With proof-of-work required, the offer is only served once the challenge is solved.
+    if let Some(challenge) = maker.pow_challenge() {
+        state.pow_challenge = Some(challenge.clone());
+        return Ok(Some(MakerToTakerMessage::MakerHello(MakerHello {
+            supported_protocols: maker.get_config().supported_protocols,
+            pow_challenge: Some(challenge),
+        })));
+    }
+
+    Ok(Some(accept_hello(maker, state)))
+}
+
+/// Handle PowSolution message.
+#[hotpath::measure]
+fn handle_pow_solution<M: Maker>(
+    maker: &Arc<M>,
+    state: &mut ConnectionState,
+    solution: PowSolution,
+) -> Result<Option<MakerToTakerMessage>, MakerError> {
+    state.expect_phase(&[SwapPhase::AwaitingHello])?;
+
+    let challenge = state.pow_challenge.take().ok_or(MakerError::General(
+        "Received a proof-of-work solution without a challenge",
+    ))?;
+    if !challenge.verify(solution.nonce) {
+        return Err(MakerError::General("Invalid proof-of-work solution"));
+    }
+
+    Ok(Some(accept_hello(maker, state)))
+}
+
+/// Complete the hello exchange, moving on to the offer request.
+fn accept_hello<M: Maker>(maker: &Arc<M>, state: &mut ConnectionState) -> MakerToTakerMessage {
     let config = maker.get_config();
     state.phase = SwapPhase::AwaitingOfferRequest;
 
@@ -410,9 +453,10 @@ fn handle_taker_hello<M: Maker>(
         config.supported_protocols
     );
 
-    Ok(Some(MakerToTakerMessage::MakerHello(MakerHello {
+    MakerToTakerMessage::MakerHello(MakerHello {
         supported_protocols: config.supported_protocols,
-    })))
+        pow_challenge: None,
+    })
 }
 
 /// Handle GetOffer message.
@@ -434,6 +478,12 @@ fn handle_get_offer<M: Maker>(
     let config = maker.get_config();
 
     state.phase = SwapPhase::AwaitingSwapDetails;
+    state.fee_policy = Some(FeePolicy {
+        base_fee: config.base_fee,
+        amount_relative_fee_pct: config.amount_relative_fee_pct,
+        time_relative_fee_pct: config.time_relative_fee_pct,
+        min_swap_amount: config.min_swap_amount,
+    });
 
     let offer = Offer {
         base_fee: config.base_fee,
@@ -446,13 +496,15 @@ fn handle_get_offer<M: Maker>(
         tweakable_point,
         fidelity,
         tweak_chain_code,
+        accepting_swaps: maker.is_accepting_swaps(),
     };
 
     log::info!(
-        "[{}] Sending offer: min={}, max={}",
+        "[{}] Sending offer: min={}, max={}, accepting_swaps={}",
         Maker::network_port(maker.as_ref()),
         offer.min_size,
-        offer.max_size
+        offer.max_size,
+        offer.accepting_swaps
     );
 
     Ok(Some(MakerToTakerMessage::Offer(Box::new(offer))))
@@ -475,7 +527,29 @@ fn handle_swap_details<M: Maker>(
         details.protocol_version
     );
 
-    maker.validate_swap_parameters(&details)?;
+    if !maker.is_accepting_swaps() {
+        log::info!(
+            "[{}] Paused, rejecting swap (id: {})",
+            Maker::network_port(maker.as_ref()),
+            details.id
+        );
+        return Ok(Some(MakerToTakerMessage::AckSwapDetails(
+            AckSwapDetails::reject(),
+        )));
+    }
+
+    if !maker.has_swap_capacity() {
+        log::info!(
+            "[{}] Too many concurrent swaps, rejecting swap (id: {})",
+            Maker::network_port(maker.as_ref()),
+            details.id
+        );
+        return Ok(Some(MakerToTakerMessage::AckSwapDetails(
+            AckSwapDetails::reject(),
+        )));
+    }
+
+    maker.validate_swap_parameters(&details, state.fee_policy.as_ref())?;
 
     state.swap_id = Some(details.id.clone());
     state.swap_amount = details.amount;
@@ -494,21 +568,47 @@ fn handle_swap_details<M: Maker>(
         Maker::network_port(maker.as_ref()),
         details.id
     );
-
-    #[cfg(feature = "integration-test")]
-    if maker.behavior() == MakerBehavior::CloseAfterAckResponse {
-        log::warn!(
-            "[{}] Test behavior: closing after AckSwapDetails",
-            maker.network_port()
-        );
-        return Err(MakerError::General("Test: closing after ack response"));
-    }
+    maker.emit_event(MakerEvent::SwapNegotiated {
+        swap_id: details.id.clone(),
+        protocol: details.protocol_version,
+        amount_sat: details.amount.to_sat(),
+        timelock: details.timelock,
+    });
 
     Ok(Some(MakerToTakerMessage::AckSwapDetails(
         AckSwapDetails::accept(tweakable_point),
     )))
 }
 
+/// Replay the private key handover of a swap that already completed.
+///
+/// A taker resuming an interrupted finalization hands its keys over again to every maker of
+/// the route, including those that completed before it went down and have since dropped the
+/// swap's connection state. They answer from the swap tracker and the wallet instead.
+pub(crate) fn replay_completed_handover<M: Maker>(
+    maker: &Arc<M>,
+    handover: PrivateKeyHandover,
+) -> Result<PrivateKeyHandover, MakerError> {
+    let privkeys = maker.completed_swap_privkeys(&handover.id, &handover.privkeys)?;
+
+    log::info!(
+        "[{}] Replaying private key handover for completed swap {}",
+        maker.network_port(),
+        handover.id
+    );
+
+    Ok(PrivateKeyHandover {
+        id: handover.id,
+        privkeys: privkeys
+            .into_iter()
+            .map(|key| SwapPrivkey {
+                identifier: ScriptBuf::new(),
+                key,
+            })
+            .collect(),
+    })
+}
+
 /// Restore connection state if this is a new/reconnected connection.
 #[hotpath::measure]
 fn restore_state_if_needed<M: Maker>(maker: &Arc<M>, state: &mut ConnectionState, swap_id: &str) {
@@ -533,6 +633,7 @@ fn restore_state_if_needed<M: Maker>(maker: &Arc<M>, state: &mut ConnectionState
             state.pending_funding_txes = stored.pending_funding_txes;
             state.funding_broadcast = stored.funding_broadcast;
             state.contract_feerate = stored.contract_feerate;
+            state.fee_policy = stored.fee_policy;
             state.swap_start_time = stored.swap_start_time;
         }
     }
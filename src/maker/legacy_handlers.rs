@@ -6,9 +6,11 @@ use bitcoin::{secp256k1::SecretKey, PublicKey, ScriptBuf};
 
 use super::{
     error::MakerError,
+    events::MakerEvent,
     handlers::{ConnectionState, Maker, SwapPhase},
 };
 use crate::{
+    fault::{FaultPoint, FaultStep},
     protocol::{
         common_messages::{MakerToTakerMessage, PrivateKeyHandover, SwapPrivkey},
         contract::{
@@ -73,20 +75,6 @@ fn process_req_contract_sigs_for_sender<M: Maker>(
     ])?;
     state.check_swap_id(&req.id)?;
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::CloseAtReqContractSigsForSender {
-            log::warn!(
-                "[{}] Test behavior: closing at ReqContractSigsForSender",
-                maker.network_port()
-            );
-            return Err(MakerError::General(
-                "Test: closing at ReqContractSigsForSender",
-            ));
-        }
-    }
-
     log::info!(
         "[{}] Processing ReqContractSigsForSender for swap {} with {} contracts",
         maker.network_port(),
@@ -131,18 +119,6 @@ fn process_proof_of_funding<M: Maker>(
     ])?;
     state.check_swap_id(&pof.id)?;
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::CloseAtProofOfFunding {
-            log::warn!(
-                "[{}] Test behavior: closing at ProofOfFunding",
-                maker.network_port()
-            );
-            return Err(MakerError::General("Test: closing at ProofOfFunding"));
-        }
-    }
-
     log::info!(
         "[{}] Processing ProofOfFunding for swap {} with {} funding txs",
         maker.network_port(),
@@ -238,7 +214,7 @@ fn process_proof_of_funding<M: Maker>(
         incoming_amount
     );
 
-    let swap_fee = maker.calculate_swap_fee(incoming_amount, pof.refund_locktime as u32);
+    let swap_fee = state.swap_fee(maker.as_ref(), incoming_amount, pof.refund_locktime as u32);
     let outgoing_amount = incoming_amount
         .checked_sub(swap_fee)
         .ok_or(MakerError::General("Swap fee exceeds incoming amount"))?;
@@ -375,20 +351,6 @@ fn process_resp_contract_sigs_for_recvr_and_sender<M: Maker>(
     state.expect_phase(&[SwapPhase::AwaitingSignaturesOrPreimage])?;
     state.check_swap_id(&resp.id)?;
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::CloseAtContractSigsForRecvrAndSender {
-            log::warn!(
-                "[{}] Test behavior: closing at RespContractSigsForRecvrAndSender",
-                maker.network_port()
-            );
-            return Err(MakerError::General(
-                "Test: closing at ContractSigsForRecvrAndSender",
-            ));
-        }
-    }
-
     log::info!(
         "[{}] Processing RespContractSigsForRecvrAndSender for swap {} ({} receiver sigs, {} sender sigs)",
         maker.network_port(),
@@ -438,24 +400,21 @@ fn process_resp_contract_sigs_for_recvr_and_sender<M: Maker>(
         );
     }
 
-    #[cfg(feature = "integration-test")]
+    if maker
+        .faults()
+        .fire(&FaultPoint::Step(FaultStep::FundingBroadcast))
+        .is_some()
     {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::SkipFundingBroadcast {
-            log::warn!(
-                "[{}] Test behavior: skipping funding broadcast",
-                maker.network_port()
-            );
-            state.phase = SwapPhase::AwaitingPrivateKeyHandover;
-            for incoming in &state.incoming_swapcoins {
-                maker.save_incoming_swapcoin(incoming)?;
-            }
-            for outgoing in &state.outgoing_swapcoins {
-                maker.save_outgoing_swapcoin(outgoing)?;
-            }
-            maker.store_connection_state(&resp.id, state)?;
-            return Err(MakerError::General("Test: skipped funding broadcast"));
+        log::warn!("[{}] Skipping funding broadcast", maker.network_port());
+        state.phase = SwapPhase::AwaitingPrivateKeyHandover;
+        for incoming in &state.incoming_swapcoins {
+            maker.save_incoming_swapcoin(incoming)?;
+        }
+        for outgoing in &state.outgoing_swapcoins {
+            maker.save_outgoing_swapcoin(outgoing)?;
         }
+        maker.store_connection_state(&resp.id, state)?;
+        return Err(MakerError::General("Fault: skipped funding broadcast"));
     }
 
     // Persist swapcoins (now carrying contract signatures) to the wallet
@@ -475,14 +434,22 @@ fn process_resp_contract_sigs_for_recvr_and_sender<M: Maker>(
         state.pending_funding_txes.len()
     );
 
+    let mut funding_txids = Vec::with_capacity(state.pending_funding_txes.len());
     for funding_tx in &state.pending_funding_txes {
         let txid = maker.broadcast_transaction(funding_tx)?;
         log::info!("[{}] Broadcast funding tx: {}", maker.network_port(), txid);
+        funding_txids.push(txid);
     }
 
     state.pending_funding_txes.clear();
     state.funding_broadcast = true;
     state.phase = SwapPhase::AwaitingPrivateKeyHandover;
+    if let Some(swap_id) = &state.swap_id {
+        maker.emit_event(MakerEvent::FundingBroadcast {
+            swap_id: swap_id.clone(),
+            txids: funding_txids,
+        });
+    }
 
     for outgoing in &state.outgoing_swapcoins {
         // Register outgoing contract output with watchtower EARLY so it can
@@ -501,23 +468,6 @@ fn process_resp_contract_sigs_for_recvr_and_sender<M: Maker>(
 
     maker.store_connection_state(&resp.id, state)?;
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::BroadcastContractAfterSetup {
-            log::warn!(
-                "[{}] Test behavior: broadcasting contract txs after setup, then closing",
-                maker.network_port()
-            );
-            for outgoing in &state.outgoing_swapcoins {
-                let _ = maker.broadcast_transaction(&outgoing.contract_tx);
-            }
-            // Remove stored state so taker can't reconnect and complete the swap
-            maker.remove_connection_state(&resp.id);
-            return Err(MakerError::General("Test: broadcast contract after setup"));
-        }
-    }
-
     log::info!(
         "[{}] Funding broadcast complete for swap {}",
         maker.network_port(),
@@ -537,18 +487,6 @@ fn process_req_contract_sigs_for_recvr<M: Maker>(
     state.expect_phase(&[SwapPhase::AwaitingPrivateKeyHandover])?;
     state.check_swap_id(&req.id)?;
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::CloseAtContractSigsForRecvr {
-            log::warn!(
-                "[{}] Test behavior: closing at ReqContractSigsForRecvr",
-                maker.network_port()
-            );
-            return Err(MakerError::General("Test: closing at ContractSigsForRecvr"));
-        }
-    }
-
     log::info!(
         "[{}] Processing ReqContractSigsForRecvr for swap {} with {} txs",
         maker.network_port(),
@@ -647,21 +585,17 @@ fn process_legacy_handover<M: Maker>(
     state: &mut ConnectionState,
     handover: PrivateKeyHandover,
 ) -> Result<Option<MakerToTakerMessage>, MakerError> {
+    // No swap in progress under this id: it may have completed before the taker went down.
+    if state.outgoing_swapcoins.is_empty() {
+        let response = super::handlers::replay_completed_handover(maker, handover)?;
+        return Ok(Some(MakerToTakerMessage::LegacyPrivateKeyHandover(
+            response,
+        )));
+    }
+
     state.expect_phase(&[SwapPhase::AwaitingPrivateKeyHandover])?;
     state.check_swap_id(&handover.id)?;
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::CloseAtHashPreimage {
-            log::warn!(
-                "[{}] Test behavior: closing at hash preimage / private key handover",
-                maker.network_port()
-            );
-            return Err(MakerError::General("Test: closing at hash preimage"));
-        }
-    }
-
     log::info!(
         "[{}] Processing Legacy private key handover for swap {} with {} key(s)",
         maker.network_port(),
@@ -669,10 +603,6 @@ fn process_legacy_handover<M: Maker>(
         handover.privkeys.len()
     );
 
-    if state.outgoing_swapcoins.is_empty() {
-        return Err(MakerError::General("No outgoing swapcoin found"));
-    }
-
     // Verify the received private keys before proceeding
     super::legacy_verification::verify_legacy_privkey_handover(
         &handover.privkeys,
@@ -786,4 +716,10 @@ fn emit_maker_success_report<M: Maker>(maker: &Arc<M>, state: &ConnectionState,
     if let Err(e) = report.save_for_wallet(maker.data_dir(), Some(maker.wallet_name())) {
         log::warn!("Failed to save maker success report: {:?}", e);
     }
+    maker.emit_event(MakerEvent::SwapCompleted {
+        swap_id: report.swap_id,
+        incoming_amount_sat: report.incoming_amount,
+        outgoing_amount_sat: report.outgoing_amount,
+        fee_earned_sat: report.fee_earned,
+    });
 }
@@ -0,0 +1,140 @@
+//! Liquidity policy of the maker.
+//!
+//! Without a policy the maker advertises its whole spendable balance as the maximum swap
+//! size, which leaks the exact balance and lets a single taker lock all of it. The
+//! [`LiquidityPolicy`] keeps a reserve that is never offered, caps the size of a single swap
+//! and the total amount locked in concurrent swaps, and rounds the advertised maximum down to
+//! a few significant digits.
+
+use serde::{Deserialize, Serialize};
+
+use super::api::MakerServerConfig;
+
+/// Limits on the liquidity offered to takers, set in `config.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiquidityPolicy {
+    /// Satoshis of spendable balance that are never offered.
+    pub reserve: u64,
+    /// Largest single swap in satoshis. `0` means no cap.
+    pub max_swap_size: u64,
+    /// Largest total amount in satoshis across concurrent swaps. `0` means no cap.
+    pub max_inflight: u64,
+    /// Significant digits kept in the advertised maximum swap size. `0` disables rounding.
+    pub offer_size_precision: u32,
+}
+
+impl LiquidityPolicy {
+    /// Liquidity available to swaps out of a `spendable` balance, in satoshis, before
+    /// subtracting what concurrent swaps already use.
+    pub fn usable_liquidity(&self, spendable: u64) -> u64 {
+        let usable = spendable.saturating_sub(self.reserve);
+        match self.max_inflight {
+            0 => usable,
+            cap => usable.min(cap),
+        }
+    }
+
+    /// Maximum swap size to advertise in offers.
+    ///
+    /// `offer_maxsize` is the largest amount the wallet can fund in one swap and `in_flight`
+    /// the total amount of the swaps in progress.
+    pub fn advertised_max_size(&self, offer_maxsize: u64, in_flight: u64) -> u64 {
+        let mut max_size = offer_maxsize.saturating_sub(self.reserve);
+        if self.max_swap_size != 0 {
+            max_size = max_size.min(self.max_swap_size);
+        }
+        if self.max_inflight != 0 {
+            max_size = max_size.min(self.max_inflight.saturating_sub(in_flight));
+        }
+        round_down_significant(max_size, self.offer_size_precision)
+    }
+}
+
+impl From<&MakerServerConfig> for LiquidityPolicy {
+    fn from(config: &MakerServerConfig) -> Self {
+        LiquidityPolicy {
+            reserve: config.liquidity_reserve,
+            max_swap_size: config.max_swap_size,
+            max_inflight: config.max_inflight_liquidity,
+            offer_size_precision: config.offer_size_precision,
+        }
+    }
+}
+
+/// Rounds `amount` down, keeping only its `digits` most significant decimal digits.
+fn round_down_significant(amount: u64, digits: u32) -> u64 {
+    if digits == 0 {
+        return amount;
+    }
+    let amount_digits = amount.checked_ilog10().map_or(0, |log| log + 1);
+    match amount_digits.checked_sub(digits) {
+        Some(dropped) if dropped > 0 => {
+            let unit = 10u64.pow(dropped);
+            amount / unit * unit
+        }
+        _ => amount,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> LiquidityPolicy {
+        LiquidityPolicy {
+            reserve: 0,
+            max_swap_size: 0,
+            max_inflight: 0,
+            offer_size_precision: 0,
+        }
+    }
+
+    #[test]
+    fn test_round_down_significant() {
+        assert_eq!(round_down_significant(123_456_789, 2), 120_000_000);
+        assert_eq!(round_down_significant(123_456_789, 4), 123_400_000);
+        assert_eq!(round_down_significant(99_999, 1), 90_000);
+        assert_eq!(round_down_significant(42, 2), 42);
+        assert_eq!(round_down_significant(7, 3), 7);
+        assert_eq!(round_down_significant(0, 2), 0);
+        assert_eq!(round_down_significant(123_456, 0), 123_456);
+    }
+
+    #[test]
+    fn test_advertised_max_size() {
+        // No policy advertises the full balance.
+        assert_eq!(policy().advertised_max_size(1_234_567, 0), 1_234_567);
+
+        let limited = LiquidityPolicy {
+            reserve: 200_000,
+            max_swap_size: 5_000_000,
+            max_inflight: 8_000_000,
+            offer_size_precision: 2,
+        };
+        // Reserve is kept back, then rounded to two digits.
+        assert_eq!(limited.advertised_max_size(1_234_567, 0), 1_000_000);
+        // Per-swap cap.
+        assert_eq!(limited.advertised_max_size(20_000_000, 0), 5_000_000);
+        // In-flight cap, shrinking as swaps are in progress.
+        assert_eq!(
+            limited.advertised_max_size(20_000_000, 4_500_000),
+            3_500_000
+        );
+        assert_eq!(limited.advertised_max_size(20_000_000, 9_000_000), 0);
+        // Balance below the reserve.
+        assert_eq!(limited.advertised_max_size(100_000, 0), 0);
+    }
+
+    #[test]
+    fn test_usable_liquidity() {
+        assert_eq!(policy().usable_liquidity(1_000), 1_000);
+        let limited = LiquidityPolicy {
+            reserve: 300,
+            max_inflight: 500,
+            ..policy()
+        };
+        assert_eq!(limited.usable_liquidity(1_000), 500);
+        assert_eq!(limited.usable_liquidity(600), 300);
+        assert_eq!(limited.usable_liquidity(200), 0);
+    }
+}
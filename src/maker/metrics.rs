@@ -0,0 +1,363 @@
+//! Prometheus metrics exporter for makerd.
+//!
+//! When `metrics_port` is set in the config, makerd serves its metrics in the Prometheus
+//! text exposition format at `http://127.0.0.1:<metrics_port>/metrics`. Every scrape reads
+//! the live [`MakerServer`] state (ongoing swaps, swap tracker, wallet store, watch service),
+//! so nothing is accounted for twice. The endpoint is bound to localhost and unauthenticated,
+//! it only exposes aggregate figures.
+
+use std::{
+    fmt::Write as _,
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{TcpListener, TcpStream},
+    sync::{atomic::Ordering::Relaxed, Arc},
+    thread::sleep,
+    time::Duration,
+};
+
+use super::{
+    accounting::fee_earned, api::MakerServer, error::MakerError, handlers::SwapPhase,
+    swap_tracker::MakerSwapPhase,
+};
+use crate::{
+    utill::HEART_BEAT_INTERVAL,
+    wallet::{BlockchainBackend, FidelityBondStatus, WalletError},
+    watch_tower::service::{QueryDelays, QUERY_DELAY_BUCKETS},
+};
+
+/// Every swap phase, so that phases without swaps are exported as zero.
+const SWAP_PHASES: [SwapPhase; 7] = [
+    SwapPhase::AwaitingHello,
+    SwapPhase::AwaitingOfferRequest,
+    SwapPhase::AwaitingSwapDetails,
+    SwapPhase::AwaitingContractData,
+    SwapPhase::AwaitingSignaturesOrPreimage,
+    SwapPhase::AwaitingPrivateKeyHandover,
+    SwapPhase::Completed,
+];
+
+/// Accumulates metric families in the Prometheus text exposition format.
+#[derive(Default)]
+struct MetricsWriter {
+    out: String,
+}
+
+impl MetricsWriter {
+    /// Writes a metric family. `name` is prefixed with `coinswap_maker_`.
+    fn family<'a>(
+        &mut self,
+        name: &str,
+        kind: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (&'a [(&'a str, String)], f64)>,
+    ) {
+        let _ = writeln!(self.out, "# HELP coinswap_maker_{name} {help}");
+        let _ = writeln!(self.out, "# TYPE coinswap_maker_{name} {kind}");
+        for (labels, value) in samples {
+            let _ = write!(self.out, "coinswap_maker_{name}");
+            if !labels.is_empty() {
+                let labels = labels
+                    .iter()
+                    .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                let _ = write!(self.out, "{{{labels}}}");
+            }
+            let _ = writeln!(self.out, " {value}");
+        }
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, "gauge", help, [(&[][..], value)]);
+    }
+
+    fn counter(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, "counter", help, [(&[][..], value)]);
+    }
+
+    fn histogram(&mut self, name: &str, help: &str, delays: &QueryDelays) {
+        let mut cumulative = 0;
+        let mut buckets = Vec::with_capacity(QUERY_DELAY_BUCKETS.len() + 1);
+        for (bound, count) in QUERY_DELAY_BUCKETS.iter().zip(delays.buckets) {
+            cumulative += count;
+            buckets.push((bound.to_string(), cumulative));
+        }
+        buckets.push(("+Inf".to_string(), delays.count));
+
+        let _ = writeln!(self.out, "# HELP coinswap_maker_{name} {help}");
+        let _ = writeln!(self.out, "# TYPE coinswap_maker_{name} histogram");
+        for (le, count) in buckets {
+            let _ = writeln!(
+                self.out,
+                "coinswap_maker_{name}_bucket{{le=\"{le}\"}} {count}"
+            );
+        }
+        let _ = writeln!(self.out, "coinswap_maker_{name}_sum {}", delays.sum_secs);
+        let _ = writeln!(self.out, "coinswap_maker_{name}_count {}", delays.count);
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Renders all maker metrics.
+fn render_metrics<B: BlockchainBackend>(maker: &MakerServer<B>) -> Result<String, MakerError> {
+    let mut metrics = MetricsWriter::default();
+
+    metrics.gauge(
+        "active_connections",
+        "Open taker connections.",
+        maker.active_connections.load(Relaxed) as f64,
+    );
+
+    let phases = maker.ongoing_swap_phases();
+    let phase_labels = SWAP_PHASES
+        .iter()
+        .map(|phase| [("phase", format!("{phase:?}"))])
+        .collect::<Vec<_>>();
+    metrics.family(
+        "ongoing_swaps",
+        "gauge",
+        "Swaps in progress, by phase.",
+        SWAP_PHASES
+            .iter()
+            .zip(&phase_labels)
+            .map(|(phase, labels)| {
+                let count = phases.iter().filter(|p| *p == phase).count();
+                (&labels[..], count as f64)
+            }),
+    );
+
+    let (mut completed, mut failed, mut recovered, mut fee_income) = (0, 0, 0, 0);
+    for record in maker.swap_tracker.lock()?.list_records(None) {
+        match record.phase {
+            MakerSwapPhase::Active => {}
+            MakerSwapPhase::Completed => completed += 1,
+            MakerSwapPhase::TakerDropped | MakerSwapPhase::Recovering => failed += 1,
+            MakerSwapPhase::Recovered => {
+                failed += 1;
+                recovered += 1;
+            }
+        }
+        fee_income += fee_earned(record);
+    }
+    metrics.counter(
+        "swaps_completed_total",
+        "Swaps completed cooperatively.",
+        completed as f64,
+    );
+    metrics.counter(
+        "swaps_failed_total",
+        "Swaps dropped by the taker before completion.",
+        failed as f64,
+    );
+    metrics.counter(
+        "swaps_recovered_total",
+        "Dropped swaps whose funds were recovered on-chain.",
+        recovered as f64,
+    );
+    metrics.counter(
+        "fee_income_sats_total",
+        "Swap fees earned, in satoshis.",
+        fee_income as f64,
+    );
+
+    metrics.gauge(
+        "offer_maxsize_sats",
+        "Maximum swap size advertised in offers, in satoshis.",
+        maker.advertised_max_size()? as f64,
+    );
+
+    let fee_policy = maker.served_fee_policy();
+    metrics.gauge(
+        "offer_base_fee_sats",
+        "Base fee served in offers, in satoshis.",
+        fee_policy.base_fee as f64,
+    );
+    metrics.gauge(
+        "offer_amount_relative_fee_pct",
+        "Amount-relative fee percentage served in offers.",
+        fee_policy.amount_relative_fee_pct,
+    );
+
+    let advertised = maker.advertised_fidelity_index()?;
+    let wallet = maker.wallet.read()?;
+
+    let current_height = wallet.rpc.get_block_count().map_err(WalletError::Rpc)? as u32;
+    let mut bonds = Vec::new();
+    for (index, bond) in wallet.get_fidelity_bonds().iter().enumerate() {
+        if bond.status(current_height) != FidelityBondStatus::Live {
+            continue;
+        }
+        let value = match wallet.calculate_bond_value(bond) {
+            Ok(value) => value.to_sat() as f64,
+            Err(e) => {
+                log::warn!("Failed to calculate value of fidelity bond {index}: {e:?}");
+                continue;
+            }
+        };
+        let labels = [
+            ("index", index.to_string()),
+            ("advertised", (advertised == Some(index as u32)).to_string()),
+        ];
+        bonds.push((labels, value, bond.blocks_to_expiry(current_height)));
+    }
+    metrics.family(
+        "fidelity_bond_value_sats",
+        "gauge",
+        "Value of live fidelity bonds, in satoshis.",
+        bonds.iter().map(|(labels, value, _)| (&labels[..], *value)),
+    );
+    metrics.family(
+        "fidelity_bond_blocks_to_expiry",
+        "gauge",
+        "Blocks until live fidelity bonds expire.",
+        bonds
+            .iter()
+            .map(|(labels, _, blocks)| (&labels[..], *blocks as f64)),
+    );
+
+    if let Some(duration) = wallet.last_sync_duration {
+        metrics.gauge(
+            "wallet_sync_duration_seconds",
+            "Duration of the last wallet sync.",
+            duration.as_secs_f64(),
+        );
+    }
+    drop(wallet);
+
+    metrics.histogram(
+        "watchtower_query_delay_seconds",
+        "Time the watchtower took to answer spend queries.",
+        &maker.watch_service.query_delays(),
+    );
+
+    Ok(metrics.out)
+}
+
+fn handle_scrape<B: BlockchainBackend>(
+    maker: &MakerServer<B>,
+    stream: &mut TcpStream,
+) -> Result<(), MakerError> {
+    let mut reader = BufReader::new(&*stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers, nothing in them matters here.
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", render_metrics(maker)?),
+        _ => ("404 Not Found", String::new()),
+    };
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Serves metrics on `127.0.0.1:<metrics_port>` until the maker shuts down.
+pub(crate) fn start_metrics_server<B: BlockchainBackend>(
+    maker: Arc<MakerServer<B>>,
+) -> Result<(), MakerError> {
+    let port = maker.config.metrics_port;
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    log::info!("[{port}] Metrics exporter listening at http://127.0.0.1:{port}/metrics");
+
+    listener.set_nonblocking(true)?;
+
+    while !maker.is_shutdown() {
+        match listener.accept() {
+            Ok((mut stream, _)) => {
+                stream.set_nonblocking(false)?;
+                stream.set_read_timeout(Some(Duration::from_secs(20)))?;
+                stream.set_write_timeout(Some(Duration::from_secs(20)))?;
+                if let Err(e) = handle_scrape(&maker, &mut stream) {
+                    log::error!("Error serving metrics: {e:?}");
+                }
+            }
+            Err(e) => {
+                if e.kind() != ErrorKind::WouldBlock {
+                    log::error!("Error accepting metrics connection: {e:?}");
+                }
+            }
+        }
+
+        sleep(HEART_BEAT_INTERVAL);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_text_format() {
+        let mut metrics = MetricsWriter::default();
+        metrics.gauge("active_connections", "Open taker connections.", 2.0);
+        let labels = [
+            [("phase", "AwaitingHello".to_string())],
+            [("phase", "say \"hi\"".to_string())],
+        ];
+        metrics.family(
+            "ongoing_swaps",
+            "gauge",
+            "Swaps in progress, by phase.",
+            labels.iter().map(|l| (&l[..], 1.5)),
+        );
+
+        assert_eq!(
+            metrics.out,
+            "# HELP coinswap_maker_active_connections Open taker connections.\n\
+             # TYPE coinswap_maker_active_connections gauge\n\
+             coinswap_maker_active_connections 2\n\
+             # HELP coinswap_maker_ongoing_swaps Swaps in progress, by phase.\n\
+             # TYPE coinswap_maker_ongoing_swaps gauge\n\
+             coinswap_maker_ongoing_swaps{phase=\"AwaitingHello\"} 1.5\n\
+             coinswap_maker_ongoing_swaps{phase=\"say \\\"hi\\\"\"} 1.5\n"
+        );
+    }
+
+    #[test]
+    fn test_metrics_histogram_format() {
+        let mut metrics = MetricsWriter::default();
+        let delays = QueryDelays {
+            buckets: [1, 0, 0, 1, 0, 0, 0, 0],
+            sum_secs: 120.5,
+            count: 3,
+        };
+        metrics.histogram("watchtower_query_delay_seconds", "Query delay.", &delays);
+
+        assert_eq!(
+            metrics.out,
+            "# HELP coinswap_maker_watchtower_query_delay_seconds Query delay.\n\
+             # TYPE coinswap_maker_watchtower_query_delay_seconds histogram\n\
+             coinswap_maker_watchtower_query_delay_seconds_bucket{le=\"0.01\"} 1\n\
+             coinswap_maker_watchtower_query_delay_seconds_bucket{le=\"0.05\"} 1\n\
+             coinswap_maker_watchtower_query_delay_seconds_bucket{le=\"0.1\"} 1\n\
+             coinswap_maker_watchtower_query_delay_seconds_bucket{le=\"0.5\"} 2\n\
+             coinswap_maker_watchtower_query_delay_seconds_bucket{le=\"1\"} 2\n\
+             coinswap_maker_watchtower_query_delay_seconds_bucket{le=\"5\"} 2\n\
+             coinswap_maker_watchtower_query_delay_seconds_bucket{le=\"15\"} 2\n\
+             coinswap_maker_watchtower_query_delay_seconds_bucket{le=\"60\"} 2\n\
+             coinswap_maker_watchtower_query_delay_seconds_bucket{le=\"+Inf\"} 3\n\
+             coinswap_maker_watchtower_query_delay_seconds_sum 120.5\n\
+             coinswap_maker_watchtower_query_delay_seconds_count 3\n"
+        );
+    }
+}
@@ -18,16 +18,21 @@ mod legacy_verification;
 pub mod taproot_handlers;
 mod taproot_verification;
 
+pub mod accounting;
 pub mod api;
+pub mod events;
 pub mod handlers;
+pub mod liquidity;
+mod metrics;
+pub mod pricing;
 pub mod server;
 pub mod swap_tracker;
 
+pub use crate::utill::{read_rpc_cookie, rpc_cookie_path, RPC_COOKIE_FILE};
 pub use error::MakerError;
-pub use rpc::{RpcMsgReq, RpcMsgResp};
+pub use events::{MakerEvent, MakerNotification};
+pub use rpc::{RpcMsgReq, RpcMsgResp, RpcRequest};
 
-#[cfg(feature = "integration-test")]
-pub use api::MakerBehavior;
 pub use api::{MakerServer, MakerServerConfig};
 pub use handlers::{
     handle_message, ConnectionState, Maker as MakerTrait, MakerConfig as HandlerConfig, SwapPhase,
@@ -0,0 +1,224 @@
+//! Dynamic fee pricing.
+//!
+//! The configured [`FeePolicy`] is static, so the maker undercharges when its liquidity is
+//! nearly exhausted and loses money when on-chain fees spike. When `dynamic_fees` is enabled
+//! in the config, offers are priced from the configured policy and the current conditions:
+//!
+//! - Relative fees grow with liquidity utilization and with the number of swaps in progress.
+//! - The base fee, which covers the maker's mining fees, additionally follows the backend's
+//!   feerate estimate relative to a reference feerate.
+//!
+//! Both adjustments are multipliers clamped between the operator's floor and ceiling. The
+//! minimum swap amount is never adjusted.
+
+use std::{sync::Arc, thread::sleep, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use super::api::{FeePolicy, MakerServer, MakerServerConfig};
+use crate::wallet::BlockchainBackend;
+
+/// How often the feerate estimate used for pricing is refreshed.
+const FEERATE_REFRESH_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Confirmation target of the feerate estimate, in blocks.
+const FEERATE_CONF_TARGET: u16 = 6;
+
+/// Parameters of the dynamic pricing engine, set in `config.toml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DynamicPricing {
+    /// Fee increase at full liquidity utilization, as a fraction of the configured fees.
+    pub utilization_factor: f64,
+    /// Fee increase per swap in progress, as a fraction of the configured fees.
+    pub ongoing_swap_factor: f64,
+    /// Feerate in sats/vByte at which the configured base fee is charged unchanged.
+    pub reference_feerate: f64,
+    /// Lowest multiplier applied to the configured fees.
+    pub min_multiplier: f64,
+    /// Highest multiplier applied to the configured fees.
+    pub max_multiplier: f64,
+}
+
+/// Current conditions the offer is priced from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PricingInputs {
+    /// Fraction of the usable liquidity locked in swaps in progress, within `[0, 1]`.
+    pub utilization: f64,
+    /// Number of swaps in progress.
+    pub ongoing_swaps: usize,
+    /// Feerate estimate in sats/vByte, if the backend provides one.
+    pub feerate: Option<f64>,
+}
+
+impl DynamicPricing {
+    /// Check the parameters are sane.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let factors = [
+            self.utilization_factor,
+            self.ongoing_swap_factor,
+            self.min_multiplier,
+            self.max_multiplier,
+        ];
+        if factors.iter().any(|f| !f.is_finite() || *f < 0.0) {
+            return Err("Dynamic pricing factors must be finite and non-negative");
+        }
+        if !self.reference_feerate.is_finite() || self.reference_feerate <= 0.0 {
+            return Err("Dynamic pricing reference feerate must be positive");
+        }
+        if self.min_multiplier > self.max_multiplier {
+            return Err("Dynamic pricing min multiplier is above max multiplier");
+        }
+        Ok(())
+    }
+
+    /// Multiplier of the relative fees.
+    fn relative_multiplier(&self, inputs: &PricingInputs) -> f64 {
+        1.0 + self.utilization_factor * inputs.utilization.clamp(0.0, 1.0)
+            + self.ongoing_swap_factor * inputs.ongoing_swaps as f64
+    }
+
+    /// Prices `policy` for the given conditions.
+    pub fn apply(&self, policy: &FeePolicy, inputs: &PricingInputs) -> FeePolicy {
+        let relative = self.relative_multiplier(inputs);
+        let feerate_ratio = inputs
+            .feerate
+            .filter(|feerate| feerate.is_finite() && *feerate > 0.0)
+            .map_or(1.0, |feerate| feerate / self.reference_feerate);
+        let clamp = |multiplier: f64| multiplier.clamp(self.min_multiplier, self.max_multiplier);
+
+        FeePolicy {
+            base_fee: (policy.base_fee as f64 * clamp(relative * feerate_ratio)).ceil() as u64,
+            amount_relative_fee_pct: policy.amount_relative_fee_pct * clamp(relative),
+            time_relative_fee_pct: policy.time_relative_fee_pct * clamp(relative),
+            min_swap_amount: policy.min_swap_amount,
+        }
+    }
+}
+
+impl From<&MakerServerConfig> for DynamicPricing {
+    fn from(config: &MakerServerConfig) -> Self {
+        DynamicPricing {
+            utilization_factor: config.fee_utilization_factor,
+            ongoing_swap_factor: config.fee_ongoing_swap_factor,
+            reference_feerate: config.fee_reference_feerate,
+            min_multiplier: config.min_fee_multiplier,
+            max_multiplier: config.max_fee_multiplier,
+        }
+    }
+}
+
+/// While dynamic pricing is enabled, keeps the maker's feerate estimate updated from the backend,
+/// until the maker shuts down.
+///
+/// The estimate is left unset when the backend has none, e.g. on regtest, in which case the
+/// base fee is not adjusted.
+pub(crate) fn feerate_refresh_loop<B: BlockchainBackend>(maker: Arc<MakerServer<B>>) {
+    let tick = Duration::from_secs(2);
+    let mut elapsed = FEERATE_REFRESH_INTERVAL;
+
+    while !maker.is_shutdown() {
+        if elapsed >= FEERATE_REFRESH_INTERVAL && maker.live_config().dynamic_fees {
+            elapsed = Duration::ZERO;
+            let estimate = maker.wallet.read().ok().and_then(|wallet| {
+                wallet
+                    .rpc
+                    .estimate_smart_fee(FEERATE_CONF_TARGET, None)
+                    .map_err(|e| log::debug!("Feerate estimation failed: {e:?}"))
+                    .ok()
+                    .and_then(|estimate| estimate.fee_rate)
+            });
+            // BTC/kvB to sats/vByte.
+            let estimate = estimate.map(|rate| rate.to_sat() as f64 / 1000.0);
+            log::debug!(
+                "[{}] Feerate estimate for pricing: {estimate:?} sats/vByte",
+                maker.config.network_port
+            );
+            if let Ok(mut feerate) = maker.market_feerate.write() {
+                *feerate = estimate;
+            }
+        }
+        sleep(tick);
+        elapsed += tick;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> FeePolicy {
+        FeePolicy {
+            base_fee: 1_000,
+            amount_relative_fee_pct: 0.1,
+            time_relative_fee_pct: 0.001,
+            min_swap_amount: 10_000,
+        }
+    }
+
+    fn pricing() -> DynamicPricing {
+        DynamicPricing {
+            utilization_factor: 1.0,
+            ongoing_swap_factor: 0.1,
+            reference_feerate: 10.0,
+            min_multiplier: 1.0,
+            max_multiplier: 3.0,
+        }
+    }
+
+    #[test]
+    fn test_idle_maker_charges_configured_fees() {
+        let idle = PricingInputs {
+            utilization: 0.0,
+            ongoing_swaps: 0,
+            feerate: None,
+        };
+        assert_eq!(pricing().apply(&policy(), &idle), policy());
+    }
+
+    #[test]
+    fn test_fees_follow_utilization_and_feerate() {
+        let busy = PricingInputs {
+            utilization: 0.5,
+            ongoing_swaps: 2,
+            feerate: Some(20.0),
+        };
+        let priced = pricing().apply(&policy(), &busy);
+        // Relative multiplier 1 + 0.5 + 0.2, base fee doubled again by the feerate.
+        assert!((priced.amount_relative_fee_pct - 0.17).abs() < 1e-9);
+        assert!((priced.time_relative_fee_pct - 0.0017).abs() < 1e-9);
+        assert_eq!(priced.base_fee, 3_000); // 1.7 * 2 clamped to the ceiling
+        assert_eq!(priced.min_swap_amount, 10_000);
+    }
+
+    #[test]
+    fn test_multiplier_floor() {
+        let cheap_fees = PricingInputs {
+            utilization: 0.0,
+            ongoing_swaps: 0,
+            feerate: Some(1.0),
+        };
+        assert_eq!(pricing().apply(&policy(), &cheap_fees).base_fee, 1_000);
+
+        let discount = DynamicPricing {
+            min_multiplier: 0.5,
+            ..pricing()
+        };
+        assert_eq!(discount.apply(&policy(), &cheap_fees).base_fee, 500);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(pricing().validate().is_ok());
+        let inverted = DynamicPricing {
+            min_multiplier: 2.0,
+            max_multiplier: 1.0,
+            ..pricing()
+        };
+        assert!(inverted.validate().is_err());
+        let no_reference = DynamicPricing {
+            reference_feerate: 0.0,
+            ..pricing()
+        };
+        assert!(no_reference.validate().is_err());
+    }
+}
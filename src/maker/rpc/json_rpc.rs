@@ -0,0 +1,759 @@
+//! JSON-RPC 2.0 over HTTP interface for makerd.
+//!
+//! This is an optional, localhost-only alternative to the CBOR framed protocol spoken by
+//! `maker-cli`. Every method maps onto an [`RpcMsgReq`] and is executed by the same
+//! [`process_request`] handler, so both interfaces always expose the same operations.
+//!
+//! Requests are `POST`ed to `http://127.0.0.1:<json_rpc_port>/` and must carry the RPC
+//! cookie as a bearer token (`Authorization: Bearer <cookie>`). Batch requests and
+//! notifications (requests without an `id`) are supported as per the specification.
+//!
+//! Method names are stable `snake_case` names mirroring the `maker-cli` subcommands:
+//!
+//! | Method                   | Params                                  |
+//! |--------------------------|-----------------------------------------|
+//! | `ping`                   |                                         |
+//! | `list_utxo`              |                                         |
+//! | `list_utxo_swap`         |                                         |
+//! | `list_utxo_contract`     |                                         |
+//! | `list_utxo_fidelity`     |                                         |
+//! | `get_balances`           |                                         |
+//! | `get_new_address`        |                                         |
+//! | `send_to_address`        | `address`, `amount`, `feerate` (opt)    |
+//! | `get_tor_address`        |                                         |
+//! | `get_data_dir`           |                                         |
+//! | `list_fidelity`          |                                         |
+//! | `create_fidelity`        | `amount`, `timelock`, `feerate` (opt)   |
+//! | `redeem_fidelity`        | `index`, `feerate` (opt)                |
+//! | `select_fidelity`        | `index` (opt)                           |
+//! | `import_fidelity`        | `outpoint`, `pubkey`, `locktime`        |
+//! | `fidelity_cert`          | `index`                                 |
+//! | `certify_fidelity`       | `index`, `signature`                    |
+//! | `export_fidelity_redeem` | `index`, `address`, `feerate` (opt)     |
+//! | `sync_wallet`            |                                         |
+//! | `list_swaps`             | `phase` (opt)                           |
+//! | `get_swap`               | `swap_id`                               |
+//! | `set_fee_policy`         | `base_fee`, `amount_relative_fee_pct`,  |
+//! |                          | `time_relative_fee_pct`,                |
+//! |                          | `min_swap_amount` (all opt)             |
+//! | `earnings`               | `from`, `to` (opt, `YYYY-MM-DD`)        |
+//! | `reload_config`          |                                         |
+//! | `pause`                  |                                         |
+//! | `resume`                 |                                         |
+//! | `stop`                   | `graceful` (opt)                        |
+//! | `subscribe`              |                                         |
+//!
+//! Params are passed by name, as a JSON object.
+//!
+//! `subscribe` must be sent on its own, not in a batch. The response is kept open and
+//! streamed as newline delimited JSON: first the regular response, then one `event`
+//! notification (`{"jsonrpc":"2.0","method":"event","params":{..}}`) per maker event.
+
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{atomic::Ordering::Relaxed, Arc},
+    thread::{self, sleep},
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{
+    messages::{RpcMsgReq, RpcMsgResp},
+    server::{process_request, MakerRpc},
+};
+use crate::{
+    maker::{error::MakerError, events::forward_events, swap_tracker::MakerSwapPhase},
+    utill::{token_matches, HEART_BEAT_INTERVAL, MIN_FEE_RATE},
+};
+use bitcoin::{OutPoint, PublicKey};
+
+/// Invalid JSON was received.
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON sent is not a valid request object.
+pub const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Invalid method parameters.
+pub const INVALID_PARAMS: i64 = -32602;
+/// Generic server side failure reported by a handler.
+pub const SERVER_ERROR: i64 = -32000;
+/// Missing or wrong RPC auth token.
+pub const UNAUTHORIZED: i64 = -32001;
+/// [`MakerError::IO`].
+pub const IO_ERROR: i64 = -32010;
+/// [`MakerError::Wallet`].
+pub const WALLET_ERROR: i64 = -32011;
+/// [`MakerError::Net`].
+pub const NET_ERROR: i64 = -32012;
+/// [`MakerError::Protocol`].
+pub const PROTOCOL_ERROR: i64 = -32013;
+/// [`MakerError::TorError`].
+pub const TOR_ERROR: i64 = -32014;
+/// [`MakerError::Watcher`].
+pub const WATCHER_ERROR: i64 = -32015;
+/// [`MakerError::InsufficientLiquidity`].
+pub const INSUFFICIENT_LIQUIDITY: i64 = -32016;
+/// Any other [`MakerError`] variant.
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// Method opening an event stream, see the module docs.
+const SUBSCRIBE_METHOD: &str = "subscribe";
+
+/// Largest accepted HTTP request body.
+const MAX_BODY_SIZE: usize = 1 << 20;
+
+/// A JSON-RPC 2.0 error object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    /// Error code, see the constants in this module.
+    pub code: i64,
+    /// Short human readable description.
+    pub message: String,
+    /// Structured details. For maker errors this holds the error `kind`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl JsonRpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+impl From<&MakerError> for JsonRpcError {
+    fn from(e: &MakerError) -> Self {
+        let (code, kind) = match e {
+            MakerError::IO(_) => (IO_ERROR, "io"),
+            MakerError::Wallet(_) => (WALLET_ERROR, "wallet"),
+            MakerError::Net(_) => (NET_ERROR, "net"),
+            MakerError::Protocol(_) => (PROTOCOL_ERROR, "protocol"),
+            MakerError::TorError(_) => (TOR_ERROR, "tor"),
+            MakerError::Watcher(_) => (WATCHER_ERROR, "watcher"),
+            MakerError::InsufficientLiquidity { .. } => {
+                (INSUFFICIENT_LIQUIDITY, "insufficient_liquidity")
+            }
+            MakerError::General(_) => (SERVER_ERROR, "general"),
+            MakerError::UnexpectedMessage { .. } => (INTERNAL_ERROR, "unexpected_message"),
+            MakerError::MutexPossion => (INTERNAL_ERROR, "mutex_poison"),
+            MakerError::Secp(_) => (INTERNAL_ERROR, "secp"),
+        };
+        Self {
+            code,
+            message: format!("{e:?}"),
+            data: Some(json!({ "kind": kind })),
+        }
+    }
+}
+
+/// A single JSON-RPC 2.0 request object.
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+    #[serde(default)]
+    id: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct SendToAddressParams {
+    address: String,
+    amount: u64,
+    feerate: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct ListSwapsParams {
+    phase: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GetSwapParams {
+    swap_id: String,
+}
+
+#[derive(Deserialize)]
+struct StopParams {
+    #[serde(default)]
+    graceful: bool,
+}
+
+#[derive(Deserialize)]
+struct SetFeePolicyParams {
+    base_fee: Option<u64>,
+    amount_relative_fee_pct: Option<f64>,
+    time_relative_fee_pct: Option<f64>,
+    min_swap_amount: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct EarningsParams {
+    from: Option<String>,
+    to: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreateFidelityParams {
+    amount: u64,
+    timelock: u32,
+    feerate: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct RedeemFidelityParams {
+    index: u32,
+    feerate: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct ImportFidelityParams {
+    outpoint: OutPoint,
+    pubkey: PublicKey,
+    locktime: u32,
+}
+
+#[derive(Deserialize)]
+struct FidelityIndexParams {
+    index: u32,
+}
+
+#[derive(Deserialize)]
+struct CertifyFidelityParams {
+    index: u32,
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct ExportFidelityRedeemParams {
+    index: u32,
+    address: String,
+    feerate: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct SelectFidelityParams {
+    index: Option<u32>,
+}
+
+fn parse_params<T: DeserializeOwned>(params: Option<Value>) -> Result<T, JsonRpcError> {
+    serde_json::from_value(params.unwrap_or_else(|| json!({})))
+        .map_err(|e| JsonRpcError::new(INVALID_PARAMS, format!("Invalid params: {e}")))
+}
+
+/// Maps a JSON-RPC method and its params onto an [`RpcMsgReq`].
+fn to_rpc_request(method: &str, params: Option<Value>) -> Result<RpcMsgReq, JsonRpcError> {
+    let req = match method {
+        "ping" => RpcMsgReq::Ping,
+        "list_utxo" => RpcMsgReq::Utxo,
+        "list_utxo_swap" => RpcMsgReq::SwapUtxo,
+        "list_utxo_contract" => RpcMsgReq::ContractUtxo,
+        "list_utxo_fidelity" => RpcMsgReq::FidelityUtxo,
+        "get_balances" => RpcMsgReq::Balances,
+        "get_new_address" => RpcMsgReq::NewAddress,
+        "send_to_address" => {
+            let p: SendToAddressParams = parse_params(params)?;
+            RpcMsgReq::SendToAddress {
+                address: p.address,
+                amount: p.amount,
+                feerate: p.feerate.unwrap_or(MIN_FEE_RATE),
+            }
+        }
+        "get_tor_address" => RpcMsgReq::GetTorAddress,
+        "get_data_dir" => RpcMsgReq::GetDataDir,
+        "list_fidelity" => RpcMsgReq::ListFidelity,
+        "create_fidelity" => {
+            let p: CreateFidelityParams = parse_params(params)?;
+            RpcMsgReq::CreateFidelity {
+                amount: p.amount,
+                timelock: p.timelock,
+                feerate: p.feerate.unwrap_or(MIN_FEE_RATE),
+            }
+        }
+        "redeem_fidelity" => {
+            let p: RedeemFidelityParams = parse_params(params)?;
+            RpcMsgReq::RedeemFidelity {
+                index: p.index,
+                feerate: p.feerate.unwrap_or(MIN_FEE_RATE),
+            }
+        }
+        "select_fidelity" => {
+            let p: SelectFidelityParams = parse_params(params)?;
+            RpcMsgReq::SelectFidelity { index: p.index }
+        }
+        "import_fidelity" => {
+            let p: ImportFidelityParams = parse_params(params)?;
+            RpcMsgReq::ImportFidelity {
+                outpoint: p.outpoint,
+                pubkey: p.pubkey,
+                locktime: p.locktime,
+            }
+        }
+        "fidelity_cert" => {
+            let p: FidelityIndexParams = parse_params(params)?;
+            RpcMsgReq::FidelityCert { index: p.index }
+        }
+        "certify_fidelity" => {
+            let p: CertifyFidelityParams = parse_params(params)?;
+            RpcMsgReq::CertifyFidelity {
+                index: p.index,
+                signature: p.signature,
+            }
+        }
+        "export_fidelity_redeem" => {
+            let p: ExportFidelityRedeemParams = parse_params(params)?;
+            RpcMsgReq::ExportFidelityRedeem {
+                index: p.index,
+                address: p.address,
+                feerate: p.feerate.unwrap_or(MIN_FEE_RATE),
+            }
+        }
+        "sync_wallet" => RpcMsgReq::SyncWallet,
+        "list_swaps" => {
+            let p: ListSwapsParams = parse_params(params)?;
+            let phase = p
+                .phase
+                .map(|s| s.parse::<MakerSwapPhase>())
+                .transpose()
+                .map_err(|e| JsonRpcError::new(INVALID_PARAMS, e))?;
+            RpcMsgReq::ListSwaps { phase }
+        }
+        "get_swap" => {
+            let p: GetSwapParams = parse_params(params)?;
+            RpcMsgReq::GetSwap { swap_id: p.swap_id }
+        }
+        "set_fee_policy" => {
+            let p: SetFeePolicyParams = parse_params(params)?;
+            RpcMsgReq::SetFeePolicy {
+                base_fee: p.base_fee,
+                amount_relative_fee_pct: p.amount_relative_fee_pct,
+                time_relative_fee_pct: p.time_relative_fee_pct,
+                min_swap_amount: p.min_swap_amount,
+            }
+        }
+        "earnings" => {
+            let p: EarningsParams = parse_params(params)?;
+            RpcMsgReq::Earnings {
+                from: p.from,
+                to: p.to,
+            }
+        }
+        "reload_config" => RpcMsgReq::ReloadConfig,
+        "stop" => {
+            let p: StopParams = parse_params(params)?;
+            RpcMsgReq::Stop {
+                graceful: p.graceful,
+            }
+        }
+        SUBSCRIBE_METHOD => RpcMsgReq::Subscribe,
+        "pause" => RpcMsgReq::Pause,
+        "resume" => RpcMsgReq::Resume,
+        _ => {
+            return Err(JsonRpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {method}"),
+            ))
+        }
+    };
+    Ok(req)
+}
+
+/// Converts an [`RpcMsgResp`] into a JSON-RPC result value.
+fn to_json_result(resp: RpcMsgResp) -> Result<Value, JsonRpcError> {
+    let to_value = |v: Result<Value, serde_json::Error>| {
+        v.map_err(|e| JsonRpcError::new(INTERNAL_ERROR, format!("Serialization failed: {e}")))
+    };
+    let value = match resp {
+        RpcMsgResp::Pong | RpcMsgResp::Shutdown => Value::Null,
+        RpcMsgResp::UtxoResp { utxos }
+        | RpcMsgResp::SwapUtxoResp { utxos }
+        | RpcMsgResp::FidelityUtxoResp { utxos }
+        | RpcMsgResp::ContractUtxoResp { utxos } => to_value(serde_json::to_value(utxos))?,
+        RpcMsgResp::TotalBalanceResp(balances) => json!({
+            "regular": balances.regular.to_sat(),
+            "swap": balances.swap.to_sat(),
+            "contract": balances.contract.to_sat(),
+            "fidelity": balances.fidelity.to_sat(),
+            "spendable": balances.spendable.to_sat(),
+        }),
+        RpcMsgResp::NewAddressResp(s)
+        | RpcMsgResp::SendToAddressResp(s)
+        | RpcMsgResp::GetTorAddressResp(s) => Value::String(s),
+        RpcMsgResp::GetDataDirResp(path) => Value::String(path.display().to_string()),
+        RpcMsgResp::FidelitySpend(txid) => Value::String(txid.to_string()),
+        RpcMsgResp::FidelityCreated { index, txid } => {
+            json!({ "index": index, "txid": txid.to_string() })
+        }
+        RpcMsgResp::AdvertisedFidelity(index) | RpcMsgResp::FidelityCertified(index) => {
+            json!({ "index": index })
+        }
+        RpcMsgResp::FidelityCertResp(request) => to_value(serde_json::to_value(request))?,
+        RpcMsgResp::FidelityRedeemPsbt(psbt) => Value::String(psbt),
+        RpcMsgResp::Subscribed => Value::String("subscribed".to_string()),
+        RpcMsgResp::Event(notification) => to_value(serde_json::to_value(notification))?,
+        RpcMsgResp::ListBonds(bonds) => to_value(serde_json::from_str(&bonds))?,
+        RpcMsgResp::ListSwapsResp(swaps) => to_value(serde_json::to_value(swaps))?,
+        RpcMsgResp::GetSwapResp(swap) => to_value(serde_json::to_value(swap))?,
+        RpcMsgResp::FeePolicyResp(fee_policy) => to_value(serde_json::to_value(fee_policy))?,
+        RpcMsgResp::EarningsResp(report) => to_value(serde_json::to_value(report))?,
+        RpcMsgResp::ConfigReloaded(reload) => to_value(serde_json::to_value(reload))?,
+        RpcMsgResp::GracefulShutdown { ongoing_swaps } => json!({ "ongoing_swaps": ongoing_swaps }),
+        RpcMsgResp::SwapAcceptance {
+            accepting,
+            ongoing_swaps,
+        } => json!({ "accepting": accepting, "ongoing_swaps": ongoing_swaps }),
+        RpcMsgResp::ServerError(e) => return Err(JsonRpcError::new(SERVER_ERROR, e)),
+    };
+    Ok(value)
+}
+
+fn response(id: Value, result: Result<Value, JsonRpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+        Err(error) => json!({ "jsonrpc": "2.0", "error": error, "id": id }),
+    }
+}
+
+/// Handles a single request object. Returns `None` for notifications.
+fn handle_call<M: MakerRpc>(maker: &Arc<M>, call: Value) -> Option<Value> {
+    let call: JsonRpcRequest = match serde_json::from_value(call) {
+        Ok(call) => call,
+        Err(e) => {
+            return Some(response(
+                Value::Null,
+                Err(JsonRpcError::new(INVALID_REQUEST, e.to_string())),
+            ))
+        }
+    };
+    let id = call.id;
+
+    let result = if call.jsonrpc != "2.0" {
+        Err(JsonRpcError::new(
+            INVALID_REQUEST,
+            "Only JSON-RPC 2.0 is supported",
+        ))
+    } else {
+        to_rpc_request(&call.method, call.params).and_then(|req| {
+            log::info!("JSON-RPC request received: {req:?}");
+            process_request(maker, req)
+                .map_err(|e| {
+                    log::error!("Error processing JSON-RPC request: {e:?}");
+                    JsonRpcError::from(&e)
+                })
+                .and_then(to_json_result)
+        })
+    };
+
+    id.map(|id| response(id, result))
+}
+
+/// Processes a JSON-RPC body (single or batch). Returns `None` if nothing needs to be sent back.
+fn handle_body<M: MakerRpc>(maker: &Arc<M>, body: &[u8]) -> Option<Value> {
+    let value: Value = match serde_json::from_slice(body) {
+        Ok(v) => v,
+        Err(e) => {
+            return Some(response(
+                Value::Null,
+                Err(JsonRpcError::new(PARSE_ERROR, e.to_string())),
+            ))
+        }
+    };
+
+    match value {
+        Value::Array(calls) if calls.is_empty() => Some(response(
+            Value::Null,
+            Err(JsonRpcError::new(INVALID_REQUEST, "Empty batch")),
+        )),
+        Value::Array(calls) => {
+            let responses: Vec<Value> = calls
+                .into_iter()
+                .filter_map(|call| handle_call(maker, call))
+                .collect();
+            (!responses.is_empty()).then(|| Value::Array(responses))
+        }
+        call => handle_call(maker, call),
+    }
+}
+
+/// A parsed HTTP request: method, bearer token and body.
+struct HttpRequest {
+    method: String,
+    token: Option<String>,
+    body: Vec<u8>,
+}
+
+fn read_http_request(stream: &mut TcpStream) -> Result<HttpRequest, MakerError> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let method = request_line
+        .split_whitespace()
+        .next()
+        .ok_or(MakerError::General("Malformed HTTP request line"))?
+        .to_string();
+
+    let mut content_length = 0usize;
+    let mut token = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse()
+                .map_err(|_| MakerError::General("Invalid Content-Length header"))?;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            token = value.strip_prefix("Bearer ").map(|t| t.trim().to_string());
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Err(MakerError::General("HTTP request body too large"));
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(HttpRequest {
+        method,
+        token,
+        body,
+    })
+}
+
+fn write_http_response(
+    stream: &mut TcpStream,
+    status: &str,
+    body: Option<&Value>,
+) -> Result<(), MakerError> {
+    let body = body.map(|b| b.to_string()).unwrap_or_default();
+    let mut head = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    if !body.is_empty() {
+        head.push_str("Content-Type: application/json\r\n");
+    }
+    if status.starts_with("401") {
+        head.push_str("WWW-Authenticate: Bearer realm=\"makerd\"\r\n");
+    }
+    head.push_str("\r\n");
+
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Answers a `subscribe` call and streams event notifications on the connection from a
+/// background thread, so the server keeps serving other requests meanwhile.
+fn stream_events<M: MakerRpc>(
+    maker: &Arc<M>,
+    stream: &TcpStream,
+    id: Option<Value>,
+) -> Result<(), MakerError> {
+    let subscription = maker.events().subscribe()?;
+    let mut stream = stream.try_clone()?;
+
+    let write_line = |stream: &mut TcpStream, value: &Value| -> Result<(), MakerError> {
+        stream.write_all(format!("{value}\n").as_bytes())?;
+        stream.flush()?;
+        Ok(())
+    };
+
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\nConnection: close\r\n\r\n",
+    )?;
+    write_line(
+        &mut stream,
+        &response(id.unwrap_or(Value::Null), Ok(json!("subscribed"))),
+    )?;
+
+    let maker = Arc::clone(maker);
+    thread::Builder::new()
+        .name("json-rpc-subscriber".to_string())
+        .spawn(move || {
+            forward_events(&subscription, maker.shutdown(), |notification| {
+                let event = json!({ "jsonrpc": "2.0", "method": "event", "params": notification });
+                write_line(&mut stream, &event)
+            });
+        })?;
+    Ok(())
+}
+
+fn handle_http_connection<M: MakerRpc>(
+    maker: &Arc<M>,
+    stream: &mut TcpStream,
+    rpc_token: &str,
+) -> Result<(), MakerError> {
+    let request = read_http_request(stream)?;
+
+    if request.method != "POST" {
+        return write_http_response(stream, "405 Method Not Allowed", None);
+    }
+
+    if !request
+        .token
+        .as_deref()
+        .is_some_and(|t| token_matches(rpc_token, t))
+    {
+        log::warn!("Rejected unauthenticated JSON-RPC request");
+        let error = response(
+            Value::Null,
+            Err(JsonRpcError::new(
+                UNAUTHORIZED,
+                "Unauthorized: invalid RPC auth token",
+            )),
+        );
+        return write_http_response(stream, "401 Unauthorized", Some(&error));
+    }
+
+    if let Ok(call) = serde_json::from_slice::<JsonRpcRequest>(&request.body) {
+        if call.method == SUBSCRIBE_METHOD {
+            return stream_events(maker, stream, call.id);
+        }
+    }
+
+    match handle_body(maker, &request.body) {
+        Some(resp) => write_http_response(stream, "200 OK", Some(&resp)),
+        None => write_http_response(stream, "204 No Content", None),
+    }
+}
+
+/// Serves the JSON-RPC interface on `127.0.0.1:<json_rpc_port>` until the maker shuts down.
+#[hotpath::measure]
+pub(crate) fn start_json_rpc_server<M: MakerRpc>(
+    maker: Arc<M>,
+    rpc_token: &str,
+) -> Result<(), MakerError> {
+    let port = maker.config().json_rpc_port;
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    log::info!("[{port}] JSON-RPC server listening at http://127.0.0.1:{port}");
+
+    listener.set_nonblocking(true)?;
+
+    while !maker.shutdown().load(Relaxed) {
+        match listener.accept() {
+            Ok((mut stream, addr)) => {
+                log::debug!("Got JSON-RPC connection from: {addr}");
+                stream.set_nonblocking(false)?;
+                stream.set_read_timeout(Some(Duration::from_secs(20)))?;
+                stream.set_write_timeout(Some(Duration::from_secs(20)))?;
+                if let Err(e) = handle_http_connection(&maker, &mut stream, rpc_token) {
+                    log::error!("Error processing JSON-RPC request: {e:?}");
+                    let error = response(Value::Null, Err(JsonRpcError::from(&e)));
+                    if let Err(e) =
+                        write_http_response(&mut stream, "400 Bad Request", Some(&error))
+                    {
+                        log::error!("Error sending JSON-RPC response {e:?}");
+                    }
+                }
+            }
+            Err(e) => {
+                if e.kind() != ErrorKind::WouldBlock {
+                    log::error!("Error accepting JSON-RPC connection: {e:?}");
+                }
+            }
+        }
+
+        sleep(HEART_BEAT_INTERVAL);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_method_mapping() {
+        assert!(matches!(to_rpc_request("ping", None), Ok(RpcMsgReq::Ping)));
+        assert!(matches!(
+            to_rpc_request("list_swaps", Some(json!({ "phase": "taker-dropped" }))),
+            Ok(RpcMsgReq::ListSwaps {
+                phase: Some(MakerSwapPhase::TakerDropped)
+            })
+        ));
+        assert!(matches!(
+            to_rpc_request("send_to_address", Some(json!({ "address": "a", "amount": 5 }))),
+            Ok(RpcMsgReq::SendToAddress { amount: 5, feerate, .. }) if feerate == MIN_FEE_RATE
+        ));
+
+        assert!(matches!(
+            to_rpc_request(SUBSCRIBE_METHOD, None),
+            Ok(RpcMsgReq::Subscribe)
+        ));
+        assert!(matches!(
+            to_rpc_request("select_fidelity", None),
+            Ok(RpcMsgReq::SelectFidelity { index: None })
+        ));
+        assert!(matches!(
+            to_rpc_request("redeem_fidelity", Some(json!({ "index": 2 }))),
+            Ok(RpcMsgReq::RedeemFidelity { index: 2, feerate }) if feerate == MIN_FEE_RATE
+        ));
+        assert!(matches!(
+            to_rpc_request("earnings", Some(json!({ "from": "2024-01-01" }))),
+            Ok(RpcMsgReq::Earnings { from: Some(from), to: None }) if from == "2024-01-01"
+        ));
+        assert!(matches!(
+            to_rpc_request("reload_config", None),
+            Ok(RpcMsgReq::ReloadConfig)
+        ));
+
+        assert!(matches!(
+            to_rpc_request(
+                "import_fidelity",
+                Some(json!({
+                    "outpoint": "0202020202020202020202020202020202020202020202020202020202020202:1",
+                    "pubkey": "031499764842691088897cff51efd85347dd3215912cbb8fb9b121b1da3b15bec8",
+                    "locktime": 900_000,
+                }))
+            ),
+            Ok(RpcMsgReq::ImportFidelity { outpoint, locktime: 900_000, .. }) if outpoint.vout == 1
+        ));
+        assert!(matches!(
+            to_rpc_request("export_fidelity_redeem", Some(json!({ "index": 1, "address": "a" }))),
+            Ok(RpcMsgReq::ExportFidelityRedeem { index: 1, feerate, .. }) if feerate == MIN_FEE_RATE
+        ));
+
+        let err = to_rpc_request("create_fidelity", Some(json!({ "amount": 1 }))).unwrap_err();
+        assert_eq!(err.code, INVALID_PARAMS);
+        let err = to_rpc_request("get_swap", Some(json!({}))).unwrap_err();
+        assert_eq!(err.code, INVALID_PARAMS);
+        let err = to_rpc_request("list_swaps", Some(json!({ "phase": "bogus" }))).unwrap_err();
+        assert_eq!(err.code, INVALID_PARAMS);
+        let err = to_rpc_request("Ping", None).unwrap_err();
+        assert_eq!(err.code, METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_error_object_from_maker_error() {
+        let err = JsonRpcError::from(&MakerError::General("boom"));
+        assert_eq!(err.code, SERVER_ERROR);
+        assert_eq!(err.data, Some(json!({ "kind": "general" })));
+
+        let io = MakerError::IO(std::io::Error::other("disk"));
+        assert_eq!(JsonRpcError::from(&io).code, IO_ERROR);
+
+        let resp = response(json!(1), Err(JsonRpcError::new(METHOD_NOT_FOUND, "nope")));
+        assert_eq!(resp["error"]["code"], json!(METHOD_NOT_FOUND));
+        assert!(resp.get("result").is_none());
+    }
+}
@@ -1,12 +1,20 @@
 use std::fmt::Display;
 
 use crate::utill::UTXO;
-use bitcoin::Txid;
+use bitcoin::{OutPoint, PublicKey, Txid};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, to_string_pretty};
 use std::path::PathBuf;
 
-use crate::wallet::Balances;
+use crate::{
+    maker::{
+        accounting::EarningsReport,
+        api::{ConfigReload, FeePolicy},
+        events::MakerNotification,
+        swap_tracker::{MakerSwapPhase, MakerSwapRecord},
+    },
+    wallet::{Balances, FidelityCertRequest},
+};
 
 /// Enum representing RPC message requests.
 ///
@@ -42,11 +50,115 @@ pub enum RpcMsgReq {
     /// Request to retrieve the data directory path.
     GetDataDir,
     /// Request to stop the Maker server.
-    Stop,
+    Stop {
+        /// Stop accepting new swaps and only shut down once all in-flight swaps are done.
+        graceful: bool,
+    },
+    /// Request to stop accepting new swaps, letting in-flight ones finish.
+    Pause,
+    /// Request to accept new swaps again after a pause.
+    Resume,
     /// Request to list all active and past fidelity bonds.
     ListFidelity,
+    /// Request to create a new fidelity bond.
+    CreateFidelity {
+        /// The bond amount in satoshis.
+        amount: u64,
+        /// Relative timelock in blocks, counted from the current height.
+        timelock: u32,
+        /// Feerate of the funding transaction, in sats/vByte.
+        feerate: f64,
+    },
+    /// Request to redeem an expired fidelity bond.
+    RedeemFidelity {
+        /// Index of the bond, as shown by [`RpcMsgReq::ListFidelity`].
+        index: u32,
+        /// Feerate of the redeem transaction, in sats/vByte.
+        feerate: f64,
+    },
+    /// Request to stream maker events. The connection stays open and receives an
+    /// [`RpcMsgResp::Event`] for every event until either side closes it.
+    Subscribe,
+    /// Request to choose which fidelity bond is advertised in offers.
+    SelectFidelity {
+        /// Index of a live bond, or `None` to advertise the highest-value bond.
+        index: Option<u32>,
+    },
+    /// Request to register a fidelity bond locked to an offline key.
+    ImportFidelity {
+        /// Outpoint of the bond.
+        outpoint: OutPoint,
+        /// Public key of the offline key.
+        pubkey: PublicKey,
+        /// Block height the bond is timelocked until.
+        locktime: u32,
+    },
+    /// Request the certificate an offline fidelity bond key must sign.
+    FidelityCert {
+        /// Index of the bond, as shown by [`RpcMsgReq::ListFidelity`].
+        index: u32,
+    },
+    /// Request to import the certificate signature of an offline fidelity bond.
+    CertifyFidelity {
+        /// Index of the bond, as shown by [`RpcMsgReq::ListFidelity`].
+        index: u32,
+        /// Signature from `signmessage` (base64) or DER-encoded hex signature.
+        signature: String,
+    },
+    /// Request an unsigned PSBT redeeming an offline fidelity bond.
+    ExportFidelityRedeem {
+        /// Index of the bond, as shown by [`RpcMsgReq::ListFidelity`].
+        index: u32,
+        /// Address receiving the bond amount.
+        address: String,
+        /// Feerate of the redeem transaction, in sats/vByte.
+        feerate: f64,
+    },
     /// Request to sync the internal wallet with blockchain.
     SyncWallet,
+    /// Request to list tracked swaps, optionally only those in a given phase.
+    ListSwaps {
+        /// Only return swaps in this phase.
+        phase: Option<MakerSwapPhase>,
+    },
+    /// Request the full record of a single swap.
+    GetSwap {
+        /// The swap identifier.
+        swap_id: String,
+    },
+    /// Request to update the fee policy served in offers. Unset fields are left unchanged.
+    /// Returns the resulting policy; an empty request just returns the current one.
+    SetFeePolicy {
+        /// Base fee in satoshis per swap.
+        base_fee: Option<u64>,
+        /// Amount-relative fee percentage.
+        amount_relative_fee_pct: Option<f64>,
+        /// Time-relative fee percentage.
+        time_relative_fee_pct: Option<f64>,
+        /// Minimum swap amount in satoshis.
+        min_swap_amount: Option<u64>,
+    },
+    /// Request an earnings report over the swaps started in a date range.
+    Earnings {
+        /// First UTC day included, as `YYYY-MM-DD`. Unbounded if unset.
+        from: Option<String>,
+        /// Last UTC day included, as `YYYY-MM-DD`. Unbounded if unset.
+        to: Option<String>,
+    },
+    /// Request to reload `config.toml`, applying the settings that can change at runtime.
+    ReloadConfig,
+}
+
+/// An authenticated RPC request, as sent over the wire.
+///
+/// Every [`RpcMsgReq`] is wrapped together with the RPC token, which is the content of
+/// the `.cookie` file makerd writes in its data directory at startup.
+#[derive(Serialize, Deserialize)]
+pub struct RpcRequest {
+    /// The RPC authentication token.
+    pub auth: String,
+    /// The actual request.
+    pub msg: RpcMsgReq,
 }
 
 /// Enum representing RPC message responses.
@@ -89,12 +201,53 @@ pub enum RpcMsgResp {
     GetDataDirResp(PathBuf),
     /// Response indicating the server has been shut down.
     Shutdown,
+    /// Response to a graceful stop request.
+    GracefulShutdown {
+        /// Swaps still in flight that will be completed before shutting down.
+        ongoing_swaps: usize,
+    },
+    /// Response to a pause or resume request.
+    SwapAcceptance {
+        /// Whether new swaps are accepted now.
+        accepting: bool,
+        /// Swaps currently in flight.
+        ongoing_swaps: usize,
+    },
     /// Response with the fidelity spending txid.
     FidelitySpend(Txid),
+    /// Response to a fidelity bond creation request.
+    FidelityCreated {
+        /// Index of the new bond.
+        index: u32,
+        /// Txid of the funding transaction.
+        txid: Txid,
+    },
+    /// Response with the index of the fidelity bond now advertised.
+    AdvertisedFidelity(u32),
+    /// Response with the certificate an offline fidelity bond key must sign.
+    FidelityCertResp(FidelityCertRequest),
+    /// Response to a fidelity certificate import, with the bond index.
+    FidelityCertified(u32),
+    /// Response with an unsigned PSBT redeeming an offline fidelity bond, in base64.
+    FidelityRedeemPsbt(String),
+    /// Response confirming an event subscription; events follow on the same connection.
+    Subscribed,
+    /// An event streamed to a subscriber.
+    Event(MakerNotification),
     /// Response with the internal server error.
     ServerError(String),
     /// Response listing all current and past fidelity bonds.
     ListBonds(String),
+    /// Response listing tracked swap records.
+    ListSwapsResp(Vec<MakerSwapRecord>),
+    /// Response containing a single swap record.
+    GetSwapResp(Box<MakerSwapRecord>),
+    /// Response containing the fee policy now in effect for new swaps.
+    FeePolicyResp(FeePolicy),
+    /// Response containing an earnings report.
+    EarningsResp(Box<EarningsReport>),
+    /// Response listing the settings changed by a config reload.
+    ConfigReloaded(ConfigReload),
 }
 
 impl Display for RpcMsgResp {
@@ -130,9 +283,86 @@ impl Display for RpcMsgResp {
             Self::GetTorAddressResp(addr) => write!(f, "{addr}"),
             Self::GetDataDirResp(path) => write!(f, "{}", path.display()),
             Self::Shutdown => write!(f, "Shutdown Initiated"),
+            Self::GracefulShutdown { ongoing_swaps } => write!(
+                f,
+                "Graceful shutdown initiated, waiting for {ongoing_swaps} in-flight swap(s) to finish"
+            ),
+            Self::SwapAcceptance {
+                accepting,
+                ongoing_swaps,
+            } => {
+                if *accepting {
+                    write!(f, "Accepting new swaps ({ongoing_swaps} in flight)")
+                } else {
+                    write!(
+                        f,
+                        "Paused, not accepting new swaps ({ongoing_swaps} in flight)"
+                    )
+                }
+            }
             Self::FidelitySpend(txid) => write!(f, "{txid}"),
+            Self::FidelityCreated { index, txid } => write!(
+                f,
+                "Fidelity bond {index} broadcast: {txid}. It will be advertised once confirmed, if it is the highest-value bond."
+            ),
+            Self::AdvertisedFidelity(index) => write!(f, "Advertising fidelity bond {index}"),
+            Self::FidelityCertResp(request) => write!(
+                f,
+                "{}",
+                serde_json::to_string_pretty(request)
+                    .expect("Fidelity certificate JSON serialization failed")
+            ),
+            Self::FidelityCertified(index) => write!(
+                f,
+                "Fidelity bond {index} certified. It will be advertised once confirmed, if it is the highest-value bond."
+            ),
+            Self::FidelityRedeemPsbt(psbt) => write!(f, "{psbt}"),
+            Self::Subscribed => write!(f, "Subscribed to maker events"),
+            Self::Event(notification) => write!(
+                f,
+                "{}",
+                serde_json::to_string(notification).expect("Event JSON serialization failed")
+            ),
             Self::ServerError(e) => write!(f, "{e}"),
             Self::ListBonds(v) => write!(f, "{v}"),
+            Self::ListSwapsResp(swaps) => write!(
+                f,
+                "{}",
+                serde_json::to_string_pretty(swaps).expect("Swap JSON serialization failed")
+            ),
+            Self::GetSwapResp(swap) => write!(
+                f,
+                "{}",
+                serde_json::to_string_pretty(swap).expect("Swap JSON serialization failed")
+            ),
+            Self::FeePolicyResp(fee_policy) => write!(
+                f,
+                "{}",
+                serde_json::to_string_pretty(fee_policy)
+                    .expect("Fee policy JSON serialization failed")
+            ),
+            Self::EarningsResp(report) => write!(
+                f,
+                "{}",
+                serde_json::to_string_pretty(report)
+                    .expect("Earnings JSON serialization failed")
+            ),
+            Self::ConfigReloaded(reload) => {
+                if reload.applied.is_empty() && reload.restart_required.is_empty() {
+                    return write!(f, "No changes");
+                }
+                if !reload.applied.is_empty() {
+                    writeln!(f, "Applied: {}", reload.applied.join(", "))?;
+                }
+                if !reload.restart_required.is_empty() {
+                    writeln!(
+                        f,
+                        "Restart required: {}",
+                        reload.restart_required.join(", ")
+                    )?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -1,4 +1,5 @@
+pub mod json_rpc;
 mod messages;
 pub mod server;
 
-pub use messages::{RpcMsgReq, RpcMsgResp};
+pub use messages::{RpcMsgReq, RpcMsgResp, RpcRequest};
@@ -5,38 +5,144 @@ use std::{
         atomic::{AtomicBool, Ordering::Relaxed},
         Arc,
     },
-    thread::sleep,
+    thread::{self, sleep},
     time::Duration,
 };
 
-use bitcoin::Amount;
+use bitcoin::{Amount, OutPoint, PublicKey, Txid};
 
-use super::messages::RpcMsgReq;
+use super::messages::{RpcMsgReq, RpcRequest};
 use crate::{
-    maker::{api::MakerServerConfig, error::MakerError, rpc::messages::RpcMsgResp},
+    maker::{
+        accounting::earnings_report,
+        api::{ConfigReload, FeePolicy, MakerServerConfig},
+        error::MakerError,
+        events::{forward_events, MakerEventBus},
+        rpc::messages::RpcMsgResp,
+        swap_tracker::MakerSwapRecord,
+    },
     utill::{
-        parse_checked_address, read_message, send_message, TorError, HEART_BEAT_INTERVAL, UTXO,
+        parse_checked_address, read_message, send_message, token_matches, TorError,
+        HEART_BEAT_INTERVAL, UTXO,
     },
-    wallet::{AddressType, BlockchainBackend, Destination, Wallet},
+    wallet::{AddressType, BlockchainBackend, Destination, FidelityCertRequest, Wallet},
 };
 use std::{path::Path, sync::RwLock};
 
-pub trait MakerRpc {
+pub trait MakerRpc: Send + Sync + 'static {
     /// The blockchain backend driving the maker's wallet.
     type Backend: BlockchainBackend;
     fn wallet(&self) -> &RwLock<Wallet<Self::Backend>>;
     fn data_dir(&self) -> &Path;
     fn config(&self) -> &MakerServerConfig;
     fn shutdown(&self) -> &AtomicBool;
+    /// Flag rejecting new swaps while set.
+    fn paused(&self) -> &AtomicBool;
+    /// Flag requesting shutdown once no swaps are in flight.
+    fn graceful_shutdown(&self) -> &AtomicBool;
+    /// Number of swaps currently in flight.
+    fn ongoing_swap_count(&self) -> usize;
+    /// Event bus feeding subscriptions.
+    fn events(&self) -> &MakerEventBus;
     fn get_tor_hostname(&self) -> Result<String, TorError>;
+    /// All tracked swaps (persisted and in-flight), oldest first.
+    fn swap_records(&self) -> Result<Vec<MakerSwapRecord>, MakerError>;
+    /// The fee policy currently served in offers.
+    fn fee_policy(&self) -> FeePolicy;
+    /// Replace the fee policy for swaps negotiated from now on, and persist it.
+    fn set_fee_policy(&self, fee_policy: FeePolicy) -> Result<(), MakerError>;
+    /// Index of the fidelity bond currently advertised, if any.
+    fn advertised_fidelity_index(&self) -> Result<Option<u32>, MakerError>;
+    /// Create and broadcast a fidelity bond, returning its index and funding txid.
+    fn create_fidelity_bond(
+        &self,
+        amount: Amount,
+        timelock: u32,
+        feerate: f64,
+    ) -> Result<(u32, Txid), MakerError>;
+    /// Redeem an expired fidelity bond, returning the redeem txid.
+    fn redeem_fidelity_bond(&self, index: u32, feerate: f64) -> Result<Txid, MakerError>;
+    /// Choose the advertised fidelity bond (`None` for the highest-value one).
+    fn select_fidelity_bond(&self, index: Option<u32>) -> Result<u32, MakerError>;
+    /// Register a fidelity bond locked to an offline key, returning the certificate to sign.
+    fn import_fidelity_bond(
+        &self,
+        outpoint: OutPoint,
+        pubkey: PublicKey,
+        locktime: u32,
+    ) -> Result<FidelityCertRequest, MakerError>;
+    /// The certificate the offline key of a bond must sign.
+    fn fidelity_cert_request(&self, index: u32) -> Result<FidelityCertRequest, MakerError>;
+    /// Import the certificate signature of an offline bond.
+    fn certify_fidelity_bond(&self, index: u32, signature: &str) -> Result<(), MakerError>;
+    /// Unsigned base64 PSBT redeeming an offline bond to `address`.
+    fn export_fidelity_redeem(
+        &self,
+        index: u32,
+        address: &str,
+        feerate: f64,
+    ) -> Result<String, MakerError>;
+    /// Reload `config.toml`, applying the settings that can change at runtime.
+    fn reload_config(&self) -> Result<ConfigReload, MakerError>;
 }
 
 #[hotpath::measure]
-fn handle_request<M: MakerRpc>(maker: &Arc<M>, socket: &mut TcpStream) -> Result<(), MakerError> {
+fn handle_request<M: MakerRpc>(
+    maker: &Arc<M>,
+    socket: &mut TcpStream,
+    rpc_token: &str,
+) -> Result<(), MakerError> {
     let msg_bytes = read_message(socket)?;
-    let rpc_request: RpcMsgReq = serde_cbor::from_slice(&msg_bytes)?;
-    log::info!("RPC request received: {rpc_request:?}");
+    let RpcRequest { auth, msg } = serde_cbor::from_slice(&msg_bytes)?;
+
+    if !token_matches(rpc_token, &auth) {
+        log::warn!("Rejected unauthenticated RPC request");
+        send_message(
+            socket,
+            &RpcMsgResp::ServerError("Unauthorized: invalid RPC auth token".to_string()),
+        )?;
+        return Ok(());
+    }
+
+    log::info!("RPC request received: {msg:?}");
+    if let RpcMsgReq::Subscribe = msg {
+        return subscribe(maker, socket);
+    }
+    let resp = process_request(maker, msg)?;
+
+    if let Err(e) = send_message(socket, &resp) {
+        log::error!("Error sending RPC response {e:?}");
+    }
+
+    Ok(())
+}
+
+/// Confirms the subscription and streams events over the connection from a background
+/// thread, so the RPC server keeps serving other requests meanwhile.
+fn subscribe<M: MakerRpc>(maker: &Arc<M>, socket: &TcpStream) -> Result<(), MakerError> {
+    let subscription = maker.events().subscribe()?;
+    let mut stream = socket.try_clone()?;
+    send_message(&mut stream, &RpcMsgResp::Subscribed)?;
+
+    let maker = Arc::clone(maker);
+    thread::Builder::new()
+        .name("rpc-subscriber".to_string())
+        .spawn(move || {
+            forward_events(&subscription, maker.shutdown(), |notification| {
+                send_message(&mut stream, &RpcMsgResp::Event(notification.clone()))?;
+                Ok(())
+            });
+        })?;
+    Ok(())
+}
 
+/// Executes a single RPC request against the maker.
+///
+/// This is shared by the CBOR protocol used by `maker-cli` and the JSON-RPC interface.
+pub(crate) fn process_request<M: MakerRpc>(
+    maker: &Arc<M>,
+    rpc_request: RpcMsgReq,
+) -> Result<RpcMsgResp, MakerError> {
     let resp = match rpc_request {
         RpcMsgReq::Ping => RpcMsgResp::Pong,
         RpcMsgReq::ContractUtxo => {
@@ -133,15 +239,138 @@ fn handle_request<M: MakerRpc>(maker: &Arc<M>, socket: &mut TcpStream) -> Result
                 RpcMsgResp::GetTorAddressResp(hostname)
             }
         }
-        RpcMsgReq::Stop => {
+        RpcMsgReq::Stop { graceful: false } => {
             maker.shutdown().store(true, Relaxed);
             RpcMsgResp::Shutdown
         }
+        RpcMsgReq::Stop { graceful: true } => {
+            maker.paused().store(true, Relaxed);
+            maker.graceful_shutdown().store(true, Relaxed);
+            let ongoing_swaps = maker.ongoing_swap_count();
+            log::info!("Graceful shutdown requested, waiting for {ongoing_swaps} in-flight swaps");
+            RpcMsgResp::GracefulShutdown { ongoing_swaps }
+        }
+        RpcMsgReq::Pause => {
+            maker.paused().store(true, Relaxed);
+            log::info!("Maker paused, new swaps will be rejected");
+            RpcMsgResp::SwapAcceptance {
+                accepting: false,
+                ongoing_swaps: maker.ongoing_swap_count(),
+            }
+        }
+        RpcMsgReq::Resume => {
+            if maker.graceful_shutdown().load(Relaxed) {
+                RpcMsgResp::ServerError("Graceful shutdown in progress, cannot resume".to_string())
+            } else {
+                maker.paused().store(false, Relaxed);
+                log::info!("Maker resumed, accepting new swaps");
+                RpcMsgResp::SwapAcceptance {
+                    accepting: true,
+                    ongoing_swaps: maker.ongoing_swap_count(),
+                }
+            }
+        }
 
         RpcMsgReq::ListFidelity => {
-            let list = maker.wallet().read()?.display_fidelity_bonds()?;
+            let mut bonds = maker.wallet().read()?.fidelity_bonds_info()?;
+            let advertised = maker.advertised_fidelity_index()?;
+            for (index, bond) in bonds.iter_mut().enumerate() {
+                bond["advertised"] = serde_json::json!(advertised == Some(index as u32));
+            }
+            let list = serde_json::to_string_pretty(&bonds)
+                .map_err(|e| MakerError::General(e.to_string().leak()))?;
             RpcMsgResp::ListBonds(list)
         }
+        RpcMsgReq::CreateFidelity {
+            amount,
+            timelock,
+            feerate,
+        } => {
+            let (index, txid) =
+                maker.create_fidelity_bond(Amount::from_sat(amount), timelock, feerate)?;
+            RpcMsgResp::FidelityCreated { index, txid }
+        }
+        RpcMsgReq::RedeemFidelity { index, feerate } => {
+            RpcMsgResp::FidelitySpend(maker.redeem_fidelity_bond(index, feerate)?)
+        }
+        RpcMsgReq::Subscribe => {
+            return Err(MakerError::General(
+                "Event subscriptions are served by the RPC transport",
+            ))
+        }
+        RpcMsgReq::SelectFidelity { index } => {
+            RpcMsgResp::AdvertisedFidelity(maker.select_fidelity_bond(index)?)
+        }
+        RpcMsgReq::ImportFidelity {
+            outpoint,
+            pubkey,
+            locktime,
+        } => RpcMsgResp::FidelityCertResp(maker.import_fidelity_bond(outpoint, pubkey, locktime)?),
+        RpcMsgReq::FidelityCert { index } => {
+            RpcMsgResp::FidelityCertResp(maker.fidelity_cert_request(index)?)
+        }
+        RpcMsgReq::CertifyFidelity { index, signature } => {
+            maker.certify_fidelity_bond(index, &signature)?;
+            RpcMsgResp::FidelityCertified(index)
+        }
+        RpcMsgReq::ExportFidelityRedeem {
+            index,
+            address,
+            feerate,
+        } => {
+            RpcMsgResp::FidelityRedeemPsbt(maker.export_fidelity_redeem(index, &address, feerate)?)
+        }
+        RpcMsgReq::ListSwaps { phase } => {
+            let swaps = maker
+                .swap_records()?
+                .into_iter()
+                .filter(|r| phase.is_none_or(|p| r.phase == p))
+                .collect();
+            RpcMsgResp::ListSwapsResp(swaps)
+        }
+        RpcMsgReq::GetSwap { swap_id } => {
+            match maker
+                .swap_records()?
+                .into_iter()
+                .find(|r| r.swap_id == swap_id)
+            {
+                Some(record) => RpcMsgResp::GetSwapResp(Box::new(record)),
+                None => RpcMsgResp::ServerError(format!("No swap found with id {swap_id}")),
+            }
+        }
+        RpcMsgReq::SetFeePolicy {
+            base_fee,
+            amount_relative_fee_pct,
+            time_relative_fee_pct,
+            min_swap_amount,
+        } => {
+            let mut fee_policy = maker.fee_policy();
+            if let Some(base_fee) = base_fee {
+                fee_policy.base_fee = base_fee;
+            }
+            if let Some(pct) = amount_relative_fee_pct {
+                fee_policy.amount_relative_fee_pct = pct;
+            }
+            if let Some(pct) = time_relative_fee_pct {
+                fee_policy.time_relative_fee_pct = pct;
+            }
+            if let Some(min_swap_amount) = min_swap_amount {
+                fee_policy.min_swap_amount = min_swap_amount;
+            }
+            maker.set_fee_policy(fee_policy.clone())?;
+            RpcMsgResp::FeePolicyResp(fee_policy)
+        }
+        RpcMsgReq::Earnings { from, to } => {
+            let records = maker.swap_records()?;
+            let report = earnings_report(
+                &*maker.wallet().read()?,
+                &records,
+                from.as_deref(),
+                to.as_deref(),
+            )?;
+            RpcMsgResp::EarningsResp(Box::new(report))
+        }
+        RpcMsgReq::ReloadConfig => RpcMsgResp::ConfigReloaded(maker.reload_config()?),
         RpcMsgReq::SyncWallet => {
             log::info!("Initializing wallet sync");
             let mut wallet = maker.wallet().write()?;
@@ -154,15 +383,14 @@ fn handle_request<M: MakerRpc>(maker: &Arc<M>, socket: &mut TcpStream) -> Result
         }
     };
 
-    if let Err(e) = send_message(socket, &resp) {
-        log::error!("Error sending RPC response {e:?}");
-    }
-
-    Ok(())
+    Ok(resp)
 }
 
 #[hotpath::measure]
-pub(crate) fn start_rpc_server<M: MakerRpc>(maker: Arc<M>) -> Result<(), MakerError> {
+pub(crate) fn start_rpc_server<M: MakerRpc>(
+    maker: Arc<M>,
+    rpc_token: &str,
+) -> Result<(), MakerError> {
     let rpc_port = maker.config().rpc_port;
     let listener = TcpListener::bind(("127.0.0.1", rpc_port))?;
     let rpc_socket = format!("127.0.0.1:{rpc_port}");
@@ -182,7 +410,7 @@ pub(crate) fn start_rpc_server<M: MakerRpc>(maker: Arc<M>) -> Result<(), MakerEr
                 stream.set_read_timeout(Some(Duration::from_secs(20)))?;
                 stream.set_write_timeout(Some(Duration::from_secs(20)))?;
                 // Do not cause hard error if a rpc request fails
-                if let Err(e) = handle_request(&maker, &mut stream) {
+                if let Err(e) = handle_request(&maker, &mut stream, rpc_token) {
                     log::error!("Error processing RPC Request: {e:?}");
                     // Send the error back to client.
                     if let Err(e) =
@@ -8,23 +8,32 @@ use std::{
         Arc,
     },
     thread::{self, sleep},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use crate::{
+    fault::{message_name, FaultAction, FaultPoint},
     maker::rpc::server::MakerRpc,
     nostr_coinswap::broadcast_bond_on_nostr,
     protocol::common_messages::{FidelityProof, MakerToTakerMessage, TakerToMakerMessage},
-    utill::HEART_BEAT_INTERVAL,
+    utill::{remove_rpc_cookie, rpc_cookie_path, write_rpc_cookie, HEART_BEAT_INTERVAL},
     wallet::{BlockchainBackend, RecoveryReport},
 };
 
 use super::{
     api::MakerServer,
     error::MakerError,
-    handlers::{handle_message, ConnectionState, Maker},
+    events::MakerEvent,
+    handlers::{handle_message, ConnectionState, Maker, SwapPhase},
 };
 
+/// Phases before the swap details are accepted, bounded by `handshake_timeout`.
+const HANDSHAKE_PHASES: [SwapPhase; 3] = [
+    SwapPhase::AwaitingHello,
+    SwapPhase::AwaitingOfferRequest,
+    SwapPhase::AwaitingSwapDetails,
+];
+
 /// Idle connection timeout (production).
 #[cfg(not(feature = "integration-test"))]
 pub const IDLE_CONNECTION_TIMEOUT: Duration = Duration::from_secs(900);
@@ -65,11 +74,40 @@ pub fn start_server<B: BlockchainBackend>(maker: Arc<MakerServer<B>>) -> Result<
         maker.get_tor_hostname()?
     };
 
+    // Write the RPC auth cookie shared by both RPC interfaces
+    let rpc_token = write_rpc_cookie(&maker.config.data_dir)?;
+    log::info!(
+        "[{}] RPC auth cookie written to {}",
+        maker.config.network_port,
+        rpc_cookie_path(&maker.config.data_dir).display()
+    );
+
+    // Spawn RPC server thread for maker-cli operations. It starts before the fidelity bond
+    // setup, so that an offline bond can be imported while the setup waits for one.
+    let maker_rpc = Arc::clone(&maker);
+    let token = rpc_token.clone();
+    let rpc_handle = thread::Builder::new()
+        .name("rpc-server".to_string())
+        .spawn(move || {
+            if let Err(e) = crate::maker::rpc::server::start_rpc_server(maker_rpc, &token) {
+                log::error!("RPC server error: {:?}", e);
+            }
+        })
+        .map_err(MakerError::IO)?;
+    maker.thread_pool.add_thread(rpc_handle);
+
     log::info!(
         "[{}] Setting up fidelity bond...",
         maker.config.network_port
     );
-    let fidelity_proof = maker.setup_fidelity_bond(&maker_address)?;
+    let fidelity_proof = match maker.setup_fidelity_bond(&maker_address) {
+        Ok(proof) => proof,
+        Err(e) => {
+            // Stop the RPC server thread.
+            maker.shutdown.store(true, Relaxed);
+            return Err(e);
+        }
+    };
 
     spawn_nostr_broadcast_thread(&maker, fidelity_proof)?;
 
@@ -129,17 +167,43 @@ pub fn start_server<B: BlockchainBackend>(maker: Arc<MakerServer<B>>) -> Result<
         maker.config.network_port
     );
 
-    // Spawn RPC server thread for maker-cli operations
-    let maker_rpc = Arc::clone(&maker);
-    let rpc_handle = thread::Builder::new()
-        .name("rpc-server".to_string())
-        .spawn(move || {
-            if let Err(e) = crate::maker::rpc::server::start_rpc_server(maker_rpc) {
-                log::error!("RPC server error: {:?}", e);
-            }
-        })
+    // Spawn the optional JSON-RPC server thread
+    if maker.config.json_rpc_port != 0 {
+        let maker_json_rpc = Arc::clone(&maker);
+        let json_rpc_handle = thread::Builder::new()
+            .name("json-rpc-server".to_string())
+            .spawn(move || {
+                if let Err(e) =
+                    crate::maker::rpc::json_rpc::start_json_rpc_server(maker_json_rpc, &rpc_token)
+                {
+                    log::error!("JSON-RPC server error: {:?}", e);
+                }
+            })
+            .map_err(MakerError::IO)?;
+        maker.thread_pool.add_thread(json_rpc_handle);
+    }
+
+    // Spawn the optional metrics exporter thread
+    if maker.config.metrics_port != 0 {
+        let maker_metrics = Arc::clone(&maker);
+        let metrics_handle = thread::Builder::new()
+            .name("metrics-server".to_string())
+            .spawn(move || {
+                if let Err(e) = super::metrics::start_metrics_server(maker_metrics) {
+                    log::error!("Metrics server error: {:?}", e);
+                }
+            })
+            .map_err(MakerError::IO)?;
+        maker.thread_pool.add_thread(metrics_handle);
+    }
+
+    // Spawn the feerate refresh thread for dynamic pricing, which can be enabled by a reload
+    let maker_pricing = Arc::clone(&maker);
+    let pricing_handle = thread::Builder::new()
+        .name("pricing-feerate".to_string())
+        .spawn(move || super::pricing::feerate_refresh_loop(maker_pricing))
         .map_err(MakerError::IO)?;
-    maker.thread_pool.add_thread(rpc_handle);
+    maker.thread_pool.add_thread(pricing_handle);
 
     // Spawn idle state checker thread for recovery
     let maker_clone = Arc::clone(&maker);
@@ -167,8 +231,32 @@ pub fn start_server<B: BlockchainBackend>(maker: Arc<MakerServer<B>>) -> Result<
     maker.thread_pool.add_thread(fidelity_handle);
 
     while !maker.is_shutdown() {
+        // A graceful stop completes once every in-flight swap has finished or been
+        // handed over to recovery, which resumes from the swap tracker after restart.
+        if maker.graceful_shutdown.load(Relaxed) && !maker.has_ongoing_swaps() {
+            log::info!(
+                "[{}] No swaps in flight, completing graceful shutdown",
+                maker.config.network_port
+            );
+            maker.shutdown.store(true, Relaxed);
+            break;
+        }
+
         match listener.accept() {
             Ok((stream, addr)) => {
+                let max_connections = maker.live_config().max_connections;
+                if max_connections != 0 && maker.active_connections.load(Relaxed) >= max_connections
+                {
+                    log::warn!(
+                        "[{}] Refusing connection from {}: {} connections open",
+                        maker.config.network_port,
+                        addr,
+                        max_connections
+                    );
+                    drop(stream);
+                    continue;
+                }
+
                 log::info!(
                     "[{}] New connection from {}",
                     maker.config.network_port,
@@ -176,12 +264,14 @@ pub fn start_server<B: BlockchainBackend>(maker: Arc<MakerServer<B>>) -> Result<
                 );
 
                 let maker_clone = Arc::clone(&maker);
+                maker.active_connections.fetch_add(1, Relaxed);
                 thread::Builder::new()
                     .name(format!("connection-{}", addr))
                     .spawn(move || {
-                        if let Err(e) = handle_connection(maker_clone, stream) {
+                        if let Err(e) = handle_connection(Arc::clone(&maker_clone), stream) {
                             log::error!("Connection error: {:?}", e);
                         }
+                        maker_clone.active_connections.fetch_sub(1, Relaxed);
                     })
                     .map_err(MakerError::IO)?;
             }
@@ -199,6 +289,7 @@ pub fn start_server<B: BlockchainBackend>(maker: Arc<MakerServer<B>>) -> Result<
 
     maker.watch_service.shutdown();
     maker.thread_pool.join_all_threads()?;
+    remove_rpc_cookie(&maker.config.data_dir);
 
     log::info!(
         "[{}] Sync at:----Shutdown wallet----",
@@ -228,11 +319,12 @@ fn spawn_nostr_broadcast_thread<B: BlockchainBackend>(
     );
 
     let maker_clone = Arc::clone(maker);
-    let relays = maker.nostr_relays.clone();
     let handle = thread::Builder::new()
         .name("nostr-thread".to_string())
         .spawn(move || {
+            let mut fidelity = fidelity;
             // Initial broadcast
+            let relays = maker_clone.live_config().nostr_relays;
             if let Err(e) = broadcast_bond_on_nostr(fidelity.clone(), &relays, &maker_clone.config)
             {
                 log::warn!("Initial nostr broadcast failed: {:?}", e);
@@ -254,6 +346,17 @@ fn spawn_nostr_broadcast_thread<B: BlockchainBackend>(
 
                 log::debug!("Re-pinging nostr relays with bond announcement");
 
+                // The advertised bond may have been renewed or re-selected since.
+                if let Ok(Some(current)) = maker_clone
+                    .highest_fidelity_proof
+                    .read()
+                    .map(|proof| proof.clone())
+                {
+                    fidelity = current;
+                }
+
+                // Relays may have been changed by a config reload.
+                let relays = maker_clone.live_config().nostr_relays;
                 if let Err(e) =
                     broadcast_bond_on_nostr(fidelity.clone(), &relays, &maker_clone.config)
                 {
@@ -277,11 +380,11 @@ fn handle_connection<B: BlockchainBackend>(
     stream: TcpStream,
 ) -> Result<(), MakerError> {
     stream.set_nonblocking(false).map_err(MakerError::IO)?;
-    stream
-        .set_read_timeout(Some(IDLE_CONNECTION_TIMEOUT))
-        .map_err(MakerError::IO)?;
 
+    let handshake_timeout = Duration::from_secs(maker.live_config().handshake_timeout);
     let mut state = ConnectionState::default();
+    let mut phase = state.phase;
+    let mut phase_started = Instant::now();
 
     log::debug!(
         "[{}] Starting connection handler",
@@ -303,7 +406,31 @@ fn handle_connection<B: BlockchainBackend>(
             break;
         }
 
-        let message = match read_message(&stream) {
+        // Handshake phases are timed from when they were entered, keepalives don't extend them.
+        if state.phase != phase {
+            phase = state.phase;
+            phase_started = Instant::now();
+        }
+        let read_timeout = if !handshake_timeout.is_zero() && HANDSHAKE_PHASES.contains(&phase) {
+            match handshake_timeout.checked_sub(phase_started.elapsed()) {
+                Some(remaining) if !remaining.is_zero() => remaining,
+                _ => {
+                    log::info!(
+                        "[{}] Handshake timed out in phase {:?}",
+                        maker.config.network_port,
+                        phase
+                    );
+                    break;
+                }
+            }
+        } else {
+            IDLE_CONNECTION_TIMEOUT
+        };
+        stream
+            .set_read_timeout(Some(read_timeout))
+            .map_err(MakerError::IO)?;
+
+        let mut message = match read_message(&stream) {
             Ok(msg) => msg,
             Err(e) => {
                 log::debug!(
@@ -321,6 +448,22 @@ fn handle_connection<B: BlockchainBackend>(
             message
         );
 
+        // Keepalives for a known swap hold its reservation and the handshake open, e.g. while
+        // the taker waits for its funding to confirm.
+        if let TakerToMakerMessage::WaitingFundingConfirmation(ref swap_id) = message {
+            if maker.touch_swap(swap_id) {
+                phase_started = Instant::now();
+            }
+        }
+
+        if let Some(action) = maker.faults.fire_message(FaultPoint::Recv, &mut message) {
+            inject_fault(&maker, &state, action);
+            break;
+        }
+        let name = (!maker.faults.is_empty())
+            .then(|| message_name(&message))
+            .flatten();
+
         let response = match handle_message(&maker, &mut state, message) {
             Ok(resp) => resp,
             Err(e) => {
@@ -330,7 +473,25 @@ fn handle_connection<B: BlockchainBackend>(
             }
         };
 
-        if let Some(response) = response {
+        match name.and_then(|name| maker.faults.fire(&FaultPoint::Handled(name))) {
+            Some(FaultAction::Corrupt(field)) => log::warn!(
+                "[{}] Fault: cannot corrupt `{}` of a handled message",
+                maker.config.network_port,
+                field
+            ),
+            Some(action) => {
+                inject_fault(&maker, &state, action);
+                break;
+            }
+            None => {}
+        }
+
+        if let Some(mut response) = response {
+            if let Some(action) = maker.faults.fire_message(FaultPoint::Send, &mut response) {
+                inject_fault(&maker, &state, action);
+                break;
+            }
+
             log::debug!(
                 "[{}] Sending response: {:?}",
                 maker.config.network_port,
@@ -348,6 +509,11 @@ fn handle_connection<B: BlockchainBackend>(
         }
 
         if state.phase == super::handlers::SwapPhase::Completed {
+            // Record the swap before sweeping, so the sweep gets attached to it.
+            if let Some(ref swap_id) = state.swap_id {
+                record_completed_swap(&maker, swap_id, &state);
+            }
+
             log::info!(
                 "[{}] Swap completed, sweeping incoming swapcoins",
                 maker.config.network_port
@@ -427,18 +593,54 @@ fn handle_connection<B: BlockchainBackend>(
     Ok(())
 }
 
+/// Carries out a fault injected into a connection, which is closed afterwards.
+fn inject_fault<B: BlockchainBackend>(
+    maker: &MakerServer<B>,
+    state: &ConnectionState,
+    action: FaultAction,
+) {
+    if action == FaultAction::BroadcastContracts {
+        for outgoing in &state.outgoing_swapcoins {
+            if let Err(e) = maker.broadcast_transaction(&outgoing.contract_tx) {
+                log::warn!(
+                    "[{}] Fault: failed to broadcast contract tx: {:?}",
+                    maker.config.network_port,
+                    e
+                );
+            }
+        }
+        // Forget the swap so the taker can't reconnect and complete it.
+        if let Some(ref swap_id) = state.swap_id {
+            maker.remove_connection_state(swap_id);
+        }
+    }
+    log::warn!("[{}] Fault: closing connection", maker.config.network_port);
+}
+
 /// Background thread that checks for idle swap states and spawns recovery.
 #[hotpath::measure]
 fn check_for_idle_states<B: BlockchainBackend>(
     maker: Arc<MakerServer<B>>,
 ) -> Result<(), MakerError> {
-    use super::swap_tracker::{now_secs, MakerRecoveryState, MakerSwapPhase, MakerSwapRecord};
+    use super::swap_tracker::{MakerSwapPhase, MakerSwapRecord};
 
     loop {
         if maker.is_shutdown() {
             break;
         }
 
+        let stale_reservations = match maker.live_config().reservation_timeout {
+            0 => Vec::new(),
+            timeout => maker.drain_stale_reservations(Duration::from_secs(timeout)),
+        };
+        for swap_id in stale_reservations {
+            log::warn!(
+                "[{}] Swap {} was not funded in time, releasing its liquidity reservation",
+                maker.config.network_port,
+                swap_id
+            );
+        }
+
         let idle_swaps = maker.drain_idle_swaps(IDLE_CONNECTION_TIMEOUT);
 
         for idle in idle_swaps {
@@ -449,23 +651,23 @@ fn check_for_idle_states<B: BlockchainBackend>(
             );
 
             // Create a tracker record for this dropped swap.
-            let now = now_secs();
-            let record = MakerSwapRecord {
-                swap_id: idle.swap_id.clone(),
-                protocol: idle.protocol,
-                phase: MakerSwapPhase::TakerDropped,
-                swap_amount_sat: idle.swap_amount_sat,
-                incoming_count: idle.incoming_swapcoins.len(),
-                outgoing_count: idle.outgoing_swapcoins.len(),
-                funding_broadcast: idle.funding_broadcast,
-                recovery: MakerRecoveryState::default(),
-                created_at: now,
-                updated_at: now,
-            };
+            let record = MakerSwapRecord::from_swapcoins(
+                idle.swap_id.clone(),
+                idle.protocol,
+                MakerSwapPhase::TakerDropped,
+                idle.swap_amount_sat,
+                &idle.incoming_swapcoins,
+                &idle.outgoing_swapcoins,
+                idle.funding_broadcast,
+            );
 
             if let Err(e) = maker.swap_tracker.lock().unwrap().save_record(&record) {
                 log::error!("Failed to save swap tracker record: {:?}", e);
             }
+            maker.events.emit(MakerEvent::TakerDropped {
+                swap_id: idle.swap_id.clone(),
+                funding_broadcast: idle.funding_broadcast,
+            });
 
             let swap_id = idle.swap_id.clone();
             let maker_clone = Arc::clone(&maker);
@@ -577,12 +779,10 @@ fn check_for_preimage_via_watchtower<B: BlockchainBackend>(
                 txid: contract_txid,
                 vout: vout as u32,
             };
-            maker.watch_service.watch_request(outpoint);
-
             if let Some(crate::watch_tower::watcher::WatcherEvent::UtxoSpent {
                 spending_tx: Some(spending_tx),
                 ..
-            }) = maker.watch_service.wait_for_event()
+            }) = maker.watch_service.query_spend(outpoint)
             {
                 // Extract preimages from the spending transaction's witnesses.
                 for input in &spending_tx.input {
@@ -663,6 +863,32 @@ fn check_for_preimage_via_watchtower<B: BlockchainBackend>(
     Ok(())
 }
 
+/// Persist a cooperatively completed swap in the Maker swap tracker.
+fn record_completed_swap<B: BlockchainBackend>(
+    maker: &MakerServer<B>,
+    swap_id: &str,
+    state: &ConnectionState,
+) {
+    use super::swap_tracker::{MakerSwapPhase, MakerSwapRecord};
+
+    let mut record = MakerSwapRecord::from_swapcoins(
+        swap_id.to_string(),
+        state.protocol,
+        MakerSwapPhase::Completed,
+        state.swap_amount.to_sat(),
+        &state.incoming_swapcoins,
+        &state.outgoing_swapcoins,
+        state.funding_broadcast,
+    );
+    record.created_at = record
+        .created_at
+        .saturating_sub(state.swap_start_time.elapsed().as_secs());
+
+    if let Err(e) = maker.swap_tracker.lock().unwrap().save_record(&record) {
+        log::error!("Failed to save swap tracker record: {:?}", e);
+    }
+}
+
 /// Update the Maker swap tracker with the given closure.
 ///
 /// Locks the tracker, applies `f` to the record matching `swap_id`, then flushes.
@@ -725,6 +951,9 @@ fn recover_from_swap<B: BlockchainBackend>(
         incoming_swapcoins.len(),
         outgoing_swapcoins.len()
     );
+    maker.events.emit(MakerEvent::RecoveryStarted {
+        swap_id: swap_id.clone(),
+    });
 
     // Check if funding was ever broadcast. If not, there is nothing on-chain
     // to recover — discard the swapcoins and exit immediately.
@@ -764,6 +993,11 @@ fn recover_from_swap<B: BlockchainBackend>(
                 r.phase = MakerSwapPhase::Recovered;
                 r.recovery.phase = MakerRecoveryPhase::CleanedUp;
             });
+            maker.events.emit(MakerEvent::RecoveryFinished {
+                swap_id: swap_id.clone(),
+                recovery_type: "none".to_string(),
+                txids: Vec::new(),
+            });
 
             #[cfg(feature = "integration-test")]
             maker.shutdown.store(true, Relaxed);
@@ -876,6 +1110,11 @@ fn recover_from_swap<B: BlockchainBackend>(
                     "hashlock".to_string(),
                     recovery_txids,
                 );
+                maker.events.emit(MakerEvent::RecoveryFinished {
+                    swap_id: swap_id.clone(),
+                    recovery_type: "hashlock".to_string(),
+                    txids: swept.resolved.iter().map(|(_, txid)| *txid).collect(),
+                });
 
                 #[cfg(feature = "integration-test")]
                 maker.shutdown.store(true, Relaxed);
@@ -957,6 +1196,11 @@ fn recover_from_swap<B: BlockchainBackend>(
                     "timelock".to_string(),
                     recovery_txids,
                 );
+                maker.events.emit(MakerEvent::RecoveryFinished {
+                    swap_id: swap_id.clone(),
+                    recovery_type: "timelock".to_string(),
+                    txids: recovered.resolved.iter().map(|(_, txid)| *txid).collect(),
+                });
 
                 #[cfg(feature = "integration-test")]
                 maker.shutdown.store(true, Relaxed);
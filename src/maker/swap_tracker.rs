@@ -9,13 +9,17 @@ use std::{
     collections::HashMap,
     fmt,
     path::{Path, PathBuf},
+    str::FromStr,
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use bitcoin::Txid;
+use bitcoin::{PublicKey, Txid};
 use serde::{Deserialize, Serialize};
 
-use crate::protocol::common_messages::ProtocolVersion;
+use crate::{
+    protocol::common_messages::ProtocolVersion,
+    wallet::swapcoin::{IncomingSwapCoin, OutgoingSwapCoin},
+};
 
 use super::error::MakerError;
 
@@ -42,6 +46,23 @@ impl fmt::Display for MakerSwapPhase {
     }
 }
 
+impl FromStr for MakerSwapPhase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "active" => Ok(MakerSwapPhase::Active),
+            "takerdropped" => Ok(MakerSwapPhase::TakerDropped),
+            "recovering" => Ok(MakerSwapPhase::Recovering),
+            "recovered" => Ok(MakerSwapPhase::Recovered),
+            "completed" => Ok(MakerSwapPhase::Completed),
+            _ => Err(format!(
+                "Unknown swap phase '{s}'. Use one of: active, taker-dropped, recovering, recovered, completed."
+            )),
+        }
+    }
+}
+
 /// Maker recovery progress within a swap.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
 pub enum MakerRecoveryPhase {
@@ -124,11 +145,103 @@ pub struct MakerSwapRecord {
     /// If `false`, there is nothing on-chain to recover for this swap.
     #[serde(default)]
     pub funding_broadcast: bool,
+    /// Total value of the incoming contracts (what the taker side pays us).
+    #[serde(default)]
+    pub incoming_amount_sat: u64,
+    /// Total value of the outgoing contracts (what we pay the next hop).
+    #[serde(default)]
+    pub outgoing_amount_sat: u64,
+    /// Txids of the incoming contract transactions.
+    #[serde(default)]
+    pub incoming_contract_txids: Vec<Txid>,
+    /// Txids of the outgoing contract transactions.
+    #[serde(default)]
+    pub outgoing_contract_txids: Vec<Txid>,
+    /// Txids of the funding transactions we created for the outgoing contracts.
+    #[serde(default)]
+    pub outgoing_funding_txids: Vec<Txid>,
+    /// Txids of the transactions sweeping the incoming coins after a cooperative swap.
+    #[serde(default)]
+    pub sweep_txids: Vec<Txid>,
+    /// Public keys of the private keys the taker side hands over for the incoming contracts.
+    /// Lets the handover of a completed swap be replayed to a taker that resumes it.
+    #[serde(default)]
+    pub handover_pubkeys: Vec<PublicKey>,
     pub recovery: MakerRecoveryState,
     pub created_at: u64,
     pub updated_at: u64,
 }
 
+impl MakerSwapRecord {
+    /// Build a fresh record from the swapcoins held for a swap.
+    pub fn from_swapcoins(
+        swap_id: String,
+        protocol: ProtocolVersion,
+        phase: MakerSwapPhase,
+        swap_amount_sat: u64,
+        incoming_swapcoins: &[IncomingSwapCoin],
+        outgoing_swapcoins: &[OutgoingSwapCoin],
+        funding_broadcast: bool,
+    ) -> Self {
+        let now = now_secs();
+        MakerSwapRecord {
+            swap_id,
+            protocol,
+            phase,
+            swap_amount_sat,
+            incoming_count: incoming_swapcoins.len(),
+            outgoing_count: outgoing_swapcoins.len(),
+            funding_broadcast,
+            incoming_amount_sat: incoming_swapcoins
+                .iter()
+                .map(|s| s.funding_amount.to_sat())
+                .sum(),
+            outgoing_amount_sat: outgoing_swapcoins
+                .iter()
+                .map(|s| s.funding_amount.to_sat())
+                .sum(),
+            incoming_contract_txids: incoming_swapcoins
+                .iter()
+                .map(|s| s.contract_tx.compute_txid())
+                .collect(),
+            outgoing_contract_txids: outgoing_swapcoins
+                .iter()
+                .map(|s| s.contract_tx.compute_txid())
+                .collect(),
+            outgoing_funding_txids: outgoing_funding_txids(outgoing_swapcoins),
+            sweep_txids: Vec::new(),
+            handover_pubkeys: incoming_swapcoins
+                .iter()
+                .filter_map(|s| s.other_pubkey)
+                .collect(),
+            recovery: MakerRecoveryState::default(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Txids of the funding transactions behind the outgoing swapcoins.
+///
+/// Legacy contracts spend a separate 2-of-2 funding output, while a Taproot contract
+/// transaction is the funding transaction itself.
+fn outgoing_funding_txids(outgoing_swapcoins: &[OutgoingSwapCoin]) -> Vec<Txid> {
+    let mut txids = Vec::new();
+    for swapcoin in outgoing_swapcoins {
+        let txid = match swapcoin.protocol {
+            ProtocolVersion::Legacy => match swapcoin.contract_tx.input.first() {
+                Some(input) => input.previous_output.txid,
+                None => continue,
+            },
+            ProtocolVersion::Taproot => swapcoin.contract_tx.compute_txid(),
+        };
+        if !txids.contains(&txid) {
+            txids.push(txid);
+        }
+    }
+    txids
+}
+
 impl fmt::Display for MakerSwapRecord {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -142,6 +255,20 @@ impl fmt::Display for MakerSwapRecord {
             self.outgoing_count,
             self.funding_broadcast,
         )?;
+        if !self.incoming_contract_txids.is_empty() {
+            write!(
+                f,
+                "\n  in_contracts={:?}",
+                short_txids(&self.incoming_contract_txids)
+            )?;
+        }
+        if !self.outgoing_contract_txids.is_empty() {
+            write!(
+                f,
+                "\n  out_contracts={:?}",
+                short_txids(&self.outgoing_contract_txids)
+            )?;
+        }
         write!(f, "\n  recovery: {}", self.recovery)?;
         Ok(())
     }
@@ -220,23 +347,60 @@ impl MakerSwapTracker {
         self.data.swaps.get_mut(swap_id)
     }
 
+    /// Attach sweep transactions to the swaps owning the swept incoming contracts.
+    ///
+    /// `resolved` holds `(contract_txid, sweep_txid)` pairs as returned by the wallet.
+    pub fn record_sweeps(&mut self, resolved: &[(Txid, Txid)]) -> Result<(), MakerError> {
+        let mut changed = false;
+        for (contract_txid, sweep_txid) in resolved {
+            if let Some(record) = self
+                .data
+                .swaps
+                .values_mut()
+                .find(|r| r.incoming_contract_txids.contains(contract_txid))
+            {
+                if !record.sweep_txids.contains(sweep_txid) {
+                    record.sweep_txids.push(*sweep_txid);
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
     /// Returns all swap records not yet fully resolved.
     ///
-    /// Includes records where phase is not `Recovered`/`Completed` or
-    /// recovery phase has not reached `CleanedUp`.
+    /// Includes records where phase is not `Recovered`/`Completed`, or
+    /// recovered records whose recovery phase has not reached `CleanedUp`.
+    /// Cooperatively `Completed` swaps never went through recovery and are
+    /// always considered resolved.
     pub fn incomplete_swaps(&self) -> Vec<&MakerSwapRecord> {
         self.data
             .swaps
             .values()
-            .filter(|r| {
-                !matches!(
-                    r.phase,
-                    MakerSwapPhase::Recovered | MakerSwapPhase::Completed
-                ) || r.recovery.phase < MakerRecoveryPhase::CleanedUp
+            .filter(|r| match r.phase {
+                MakerSwapPhase::Completed => false,
+                MakerSwapPhase::Recovered => r.recovery.phase < MakerRecoveryPhase::CleanedUp,
+                _ => true,
             })
             .collect()
     }
 
+    /// Returns all swap records, oldest first, optionally filtered by phase.
+    pub fn list_records(&self, phase: Option<MakerSwapPhase>) -> Vec<&MakerSwapRecord> {
+        let mut records: Vec<_> = self
+            .data
+            .swaps
+            .values()
+            .filter(|r| phase.is_none_or(|p| r.phase == p))
+            .collect();
+        records.sort_by_key(|r| r.created_at);
+        records
+    }
+
     /// Log all swap records at INFO level.
     pub fn log_state(&self) {
         if self.data.swaps.is_empty() {
@@ -288,6 +452,13 @@ mod tests {
             incoming_count: 2,
             outgoing_count: 2,
             funding_broadcast: true,
+            incoming_amount_sat: 100_000,
+            outgoing_amount_sat: 99_000,
+            incoming_contract_txids: Vec::new(),
+            outgoing_contract_txids: Vec::new(),
+            outgoing_funding_txids: Vec::new(),
+            sweep_txids: Vec::new(),
+            handover_pubkeys: Vec::new(),
             recovery: MakerRecoveryState::default(),
             created_at: now_secs(),
             updated_at: now_secs(),
@@ -340,6 +511,90 @@ mod tests {
         assert_eq!(tracker.incomplete_swaps().len(), 1);
     }
 
+    #[test]
+    fn test_completed_not_in_incomplete() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker = MakerSwapTracker::load_or_create(dir.path()).unwrap();
+
+        let record = make_test_record("swap1", MakerSwapPhase::Completed);
+        tracker.save_record(&record).unwrap();
+        assert!(tracker.incomplete_swaps().is_empty());
+    }
+
+    #[test]
+    fn test_list_records_filters_by_phase() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker = MakerSwapTracker::load_or_create(dir.path()).unwrap();
+
+        let mut first = make_test_record("swap1", MakerSwapPhase::Completed);
+        first.created_at = 1;
+        let mut second = make_test_record("swap2", MakerSwapPhase::Recovering);
+        second.created_at = 2;
+        let mut third = make_test_record("swap3", MakerSwapPhase::Completed);
+        third.created_at = 3;
+        tracker.save_record(&third).unwrap();
+        tracker.save_record(&second).unwrap();
+        tracker.save_record(&first).unwrap();
+
+        let all: Vec<_> = tracker
+            .list_records(None)
+            .iter()
+            .map(|r| r.swap_id.as_str())
+            .collect();
+        assert_eq!(all, vec!["swap1", "swap2", "swap3"]);
+
+        let completed = tracker.list_records(Some(MakerSwapPhase::Completed));
+        assert_eq!(completed.len(), 2);
+        assert!(completed
+            .iter()
+            .all(|r| r.phase == MakerSwapPhase::Completed));
+    }
+
+    #[test]
+    fn test_record_sweeps() {
+        let dir = TempDir::new().unwrap();
+        let contract_txid =
+            Txid::from_str("0000000000000000000000000000000000000000000000000000000000000001")
+                .unwrap();
+        let sweep_txid =
+            Txid::from_str("0000000000000000000000000000000000000000000000000000000000000002")
+                .unwrap();
+
+        {
+            let mut tracker = MakerSwapTracker::load_or_create(dir.path()).unwrap();
+            let mut record = make_test_record("swap1", MakerSwapPhase::Completed);
+            record.incoming_contract_txids = vec![contract_txid];
+            tracker.save_record(&record).unwrap();
+            tracker
+                .save_record(&make_test_record("swap2", MakerSwapPhase::Completed))
+                .unwrap();
+
+            let resolved = [(contract_txid, sweep_txid)];
+            tracker.record_sweeps(&resolved).unwrap();
+            tracker.record_sweeps(&resolved).unwrap();
+        }
+
+        let tracker = MakerSwapTracker::load_or_create(dir.path()).unwrap();
+        assert_eq!(
+            tracker.get_record("swap1").unwrap().sweep_txids,
+            vec![sweep_txid]
+        );
+        assert!(tracker.get_record("swap2").unwrap().sweep_txids.is_empty());
+    }
+
+    #[test]
+    fn test_phase_from_str() {
+        assert_eq!(
+            "taker-dropped".parse::<MakerSwapPhase>().unwrap(),
+            MakerSwapPhase::TakerDropped
+        );
+        assert_eq!(
+            "Recovering".parse::<MakerSwapPhase>().unwrap(),
+            MakerSwapPhase::Recovering
+        );
+        assert!("unknown".parse::<MakerSwapPhase>().is_err());
+    }
+
     #[test]
     fn test_phase_ordering() {
         assert!(MakerSwapPhase::Active < MakerSwapPhase::TakerDropped);
@@ -6,9 +6,11 @@ use bitcoin::{OutPoint, PublicKey};
 
 use super::{
     error::MakerError,
+    events::MakerEvent,
     handlers::{ConnectionState, Maker, SwapPhase},
 };
 use crate::{
+    fault::{FaultPoint, FaultStep},
     protocol::{
         common_messages::{MakerToTakerMessage, PrivateKeyHandover, SwapPrivkey},
         contract::calculate_pubkey_from_nonce,
@@ -56,20 +58,6 @@ fn process_taproot_contract<M: Maker>(
     state.expect_phase(&[SwapPhase::AwaitingContractData])?;
     state.check_swap_id(&data.id)?;
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::CloseAtContractSigsExchange {
-            log::warn!(
-                "[{}] Test behavior: closing at taproot contract sigs exchange",
-                maker.network_port()
-            );
-            return Err(MakerError::General(
-                "Test: closing at contract sigs exchange",
-            ));
-        }
-    }
-
     log::info!(
         "[{}] Processing Taproot contract data for swap {}",
         maker.network_port(),
@@ -131,8 +119,11 @@ fn process_taproot_contract<M: Maker>(
     incoming_swapcoin.tap_tweak = Some(data.tap_tweak_scalar()?);
 
     // Use the relative offset sent by the taker for deterministic fee calculation
-    let fee =
-        maker.calculate_swap_fee(incoming_funding_amount, state.refund_locktime_offset as u32);
+    let fee = state.swap_fee(
+        maker.as_ref(),
+        incoming_funding_amount,
+        state.refund_locktime_offset as u32,
+    );
     let outgoing_amount = incoming_funding_amount
         .checked_sub(fee)
         .ok_or(MakerError::General("Fee exceeds incoming amount"))?;
@@ -253,23 +244,23 @@ fn process_taproot_contract<M: Maker>(
     state.incoming_swapcoins.push(incoming_swapcoin.clone());
     state.outgoing_swapcoins.push(outgoing_swapcoin.clone());
 
-    #[cfg(feature = "integration-test")]
+    if maker
+        .faults()
+        .fire(&FaultPoint::Step(FaultStep::FundingBroadcast))
+        .is_some()
     {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::SkipFundingBroadcast {
-            log::warn!(
-                "[{}] Test behavior: skipping Taproot funding broadcast",
-                maker.network_port()
-            );
-            // Swapcoins are already in state.
-            // Save them to wallet for recovery detection.
-            state.funding_broadcast = true;
-            state.phase = SwapPhase::AwaitingPrivateKeyHandover;
-            maker.save_incoming_swapcoin(&incoming_swapcoin)?;
-            maker.save_outgoing_swapcoin(&outgoing_swapcoin)?;
-            maker.store_connection_state(&data.id, state)?;
-            return Err(MakerError::General("Test: skipped funding broadcast"));
-        }
+        log::warn!(
+            "[{}] Skipping Taproot funding broadcast",
+            maker.network_port()
+        );
+        // Swapcoins are already in state.
+        // Save them to wallet for recovery detection.
+        state.funding_broadcast = true;
+        state.phase = SwapPhase::AwaitingPrivateKeyHandover;
+        maker.save_incoming_swapcoin(&incoming_swapcoin)?;
+        maker.save_outgoing_swapcoin(&outgoing_swapcoin)?;
+        maker.store_connection_state(&data.id, state)?;
+        return Err(MakerError::General("Fault: skipped funding broadcast"));
     }
 
     match maker.broadcast_transaction(&contract_tx) {
@@ -280,6 +271,10 @@ fn process_taproot_contract<M: Maker>(
                 txid,
                 data.id
             );
+            maker.emit_event(MakerEvent::FundingBroadcast {
+                swap_id: data.id.clone(),
+                txids: vec![txid],
+            });
 
             maker.register_watch_outpoint(contract_outpoint, contract_spk.clone());
         }
@@ -302,19 +297,6 @@ fn process_taproot_contract<M: Maker>(
 
     maker.store_connection_state(&data.id, state)?;
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::BroadcastContractAfterSetup {
-            log::warn!(
-                "[{}] Test behavior: broadcasting contract tx after taproot setup, then closing",
-                maker.network_port()
-            );
-            let _ = maker.broadcast_transaction(&outgoing_swapcoin.contract_tx);
-            return Err(MakerError::General("Test: broadcast contract after setup"));
-        }
-    }
-
     log::info!(
         "[{}] Created Taproot swapcoins for swap {}. Outgoing amount: {}",
         maker.network_port(),
@@ -352,21 +334,17 @@ fn process_taproot_handover<M: Maker>(
     state: &mut ConnectionState,
     handover: PrivateKeyHandover,
 ) -> Result<Option<MakerToTakerMessage>, MakerError> {
+    // No swap in progress under this id: it may have completed before the taker went down.
+    if state.outgoing_swapcoins.is_empty() {
+        let response = super::handlers::replay_completed_handover(maker, handover)?;
+        return Ok(Some(MakerToTakerMessage::TaprootPrivateKeyHandover(
+            response,
+        )));
+    }
+
     state.expect_phase(&[SwapPhase::AwaitingPrivateKeyHandover])?;
     state.check_swap_id(&handover.id)?;
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::CloseAtPrivateKeyHandover {
-            log::warn!(
-                "[{}] Test behavior: closing at taproot private key handover",
-                maker.network_port()
-            );
-            return Err(MakerError::General("Test: closing at private key handover"));
-        }
-    }
-
     log::info!(
         "[{}] Processing Taproot private key handover for swap {}",
         maker.network_port(),
@@ -407,18 +385,6 @@ fn process_taproot_handover<M: Maker>(
     // Generate and save maker success report
     emit_maker_success_report(maker, state, &handover.id);
 
-    #[cfg(feature = "integration-test")]
-    {
-        use super::handlers::MakerBehavior;
-        if maker.behavior() == MakerBehavior::CloseAfterSweep {
-            log::warn!(
-                "[{}] Test behavior: closing after sweep / completing handover",
-                maker.network_port()
-            );
-            return Err(MakerError::General("Test: closing after sweep"));
-        }
-    }
-
     log::info!(
         "[{}] Taproot swap {} completed successfully, returning private key",
         maker.network_port(),
@@ -482,4 +448,10 @@ fn emit_maker_success_report<M: Maker>(maker: &Arc<M>, state: &ConnectionState,
     if let Err(e) = report.save_for_wallet(maker.data_dir(), Some(maker.wallet_name())) {
         log::warn!("Failed to save maker success report: {:?}", e);
     }
+    maker.emit_event(MakerEvent::SwapCompleted {
+        swap_id: report.swap_id,
+        incoming_amount_sat: report.incoming_amount,
+        outgoing_amount_sat: report.outgoing_amount,
+        fee_earned_sat: report.fee_earned,
+    });
 }
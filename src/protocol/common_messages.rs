@@ -1,6 +1,10 @@
 //! Common Coinswap Protocol Messages and Top-Level Message Enums.
 
-use bitcoin::{bip32::ChainCode, hashes::sha256d::Hash, Amount, PublicKey};
+use bitcoin::{
+    bip32::ChainCode,
+    hashes::{sha256, sha256d::Hash, Hash as _},
+    Amount, PublicKey,
+};
 use serde::{Deserialize, Serialize};
 
 use super::{
@@ -40,15 +44,101 @@ pub enum ProtocolVersion {
     Taproot,
 }
 
+impl std::fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtocolVersion::Legacy => write!(f, "legacy"),
+            ProtocolVersion::Taproot => write!(f, "taproot"),
+        }
+    }
+}
+
+impl std::str::FromStr for ProtocolVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "legacy" => Ok(ProtocolVersion::Legacy),
+            "taproot" => Ok(ProtocolVersion::Taproot),
+            _ => Err(format!(
+                "Unknown protocol '{}'. Use 'legacy' or 'taproot'.",
+                s
+            )),
+        }
+    }
+}
+
 /// Initial handshake from Taker to Maker.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct TakerHello;
 
+/// Solution to the [`PowChallenge`] a maker sent in its [`MakerHello`].
+///
+/// A separate message rather than a field of [`TakerHello`], so that hellos keep their wire
+/// format and makers and takers predating proof-of-work still talk to each other.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PowSolution {
+    /// Nonce solving the challenge.
+    pub nonce: u64,
+}
+
 /// Handshake response from Maker to Taker.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MakerHello {
     /// Protocol versions this maker supports.
     pub supported_protocols: Vec<ProtocolVersion>,
+    /// Proof-of-work the taker must solve before the maker serves its offer.
+    #[serde(default)]
+    pub pow_challenge: Option<PowChallenge>,
+}
+
+/// Highest [`PowChallenge`] difficulty takers solve, about 16 million hashes.
+pub const MAX_POW_DIFFICULTY: u8 = 24;
+
+/// Hashcash-style proof-of-work challenge, issued by makers to make opening swaps costly.
+///
+/// A nonce solves the challenge if `SHA256(challenge || nonce)`, with the nonce in
+/// little-endian, starts with at least `difficulty` zero bits. Makers rotate their challenge
+/// periodically, so takers can reuse a solution until then.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PowChallenge {
+    /// Random challenge, replaced by the maker when it rotates.
+    pub challenge: [u8; 32],
+    /// Required number of leading zero bits.
+    pub difficulty: u8,
+}
+
+impl PowChallenge {
+    /// Create a fresh random challenge.
+    pub fn new(difficulty: u8) -> Self {
+        PowChallenge {
+            challenge: bitcoin::secp256k1::rand::random(),
+            difficulty,
+        }
+    }
+
+    /// Check whether `nonce` solves the challenge.
+    pub fn verify(&self, nonce: u64) -> bool {
+        let mut preimage = self.challenge.to_vec();
+        preimage.extend_from_slice(&nonce.to_le_bytes());
+        let hash = sha256::Hash::hash(&preimage).to_byte_array();
+
+        let mut zero_bits = 0u32;
+        for byte in hash {
+            zero_bits += byte.leading_zeros();
+            if byte != 0 {
+                break;
+            }
+        }
+        zero_bits >= self.difficulty as u32
+    }
+
+    /// Find a nonce solving the challenge. Takes about `2^difficulty` hashes.
+    pub fn solve(&self) -> u64 {
+        (0..=u64::MAX)
+            .find(|nonce| self.verify(*nonce))
+            .expect("a solution exists for any difficulty up to 256 bits")
+    }
 }
 
 /// Request for offer from Taker to Maker.
@@ -79,6 +169,14 @@ pub struct Offer {
     pub fidelity: FidelityProof,
     /// Chain code for deterministic derivation of swap addresses from the tweakable point.
     pub tweak_chain_code: ChainCode,
+    /// Whether the maker is currently accepting new swaps.
+    /// False while the maker is paused for maintenance; swap details will be rejected.
+    #[serde(default = "default_accepting_swaps")]
+    pub accepting_swaps: bool,
+}
+
+fn default_accepting_swaps() -> bool {
+    true
 }
 
 /// Swap details from Taker to Maker.
@@ -118,6 +216,13 @@ impl AckSwapDetails {
             tweakable_point: Some(tweakable_point),
         }
     }
+
+    /// Create a rejection response.
+    pub fn reject() -> Self {
+        AckSwapDetails {
+            tweakable_point: None,
+        }
+    }
 }
 
 /// A private key exchanged during swap completion.
@@ -163,6 +268,8 @@ pub enum TakerToMakerMessage {
     TaprootPrivateKeyHandover(PrivateKeyHandover),
     /// Taker keepalive while waiting for funding confirmation.
     WaitingFundingConfirmation(String),
+    /// Solution to the maker's proof-of-work challenge, answered with a new hello.
+    PowSolution(PowSolution),
 }
 
 /// All messages sent from Maker to Taker.
@@ -187,3 +294,73 @@ pub enum MakerToTakerMessage {
     /// Taproot private key handover.
     TaprootPrivateKeyHandover(PrivateKeyHandover),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pow_challenge() {
+        let challenge = PowChallenge {
+            challenge: [1; 32],
+            difficulty: 12,
+        };
+        let nonce = challenge.solve();
+        assert!(challenge.verify(nonce));
+        // A solution is bound to its challenge.
+        let other = PowChallenge {
+            challenge: [2; 32],
+            difficulty: 12,
+        };
+        assert!(!other.verify(nonce));
+        // Any nonce solves a zero-difficulty challenge.
+        assert!(PowChallenge::new(0).verify(nonce));
+    }
+
+    #[test]
+    fn test_hello_without_pow_decodes() {
+        // Hellos from peers unaware of proof-of-work still decode.
+        #[derive(Serialize)]
+        struct LegacyMakerHello {
+            supported_protocols: Vec<ProtocolVersion>,
+        }
+        let bytes = serde_cbor::to_vec(&LegacyMakerHello {
+            supported_protocols: vec![ProtocolVersion::Taproot],
+        })
+        .unwrap();
+        let hello: MakerHello = serde_cbor::from_slice(&bytes).unwrap();
+        assert_eq!(hello.pow_challenge, None);
+    }
+
+    #[test]
+    fn test_taker_hello_wire_format() {
+        // Takers and makers predating proof-of-work sent the unit hello; it must keep
+        // decoding both ways.
+        #[derive(Serialize, Deserialize)]
+        struct OldTakerHello;
+        #[derive(Serialize, Deserialize)]
+        enum OldTakerToMakerMessage {
+            TakerHello(OldTakerHello),
+        }
+
+        let old_bytes =
+            serde_cbor::to_vec(&OldTakerToMakerMessage::TakerHello(OldTakerHello)).unwrap();
+        let msg: TakerToMakerMessage = serde_cbor::from_slice(&old_bytes).unwrap();
+        assert!(matches!(msg, TakerToMakerMessage::TakerHello(TakerHello)));
+
+        let new_bytes = serde_cbor::to_vec(&TakerToMakerMessage::TakerHello(TakerHello)).unwrap();
+        assert_eq!(new_bytes, old_bytes);
+        let msg: OldTakerToMakerMessage = serde_cbor::from_slice(&new_bytes).unwrap();
+        assert!(matches!(
+            msg,
+            OldTakerToMakerMessage::TakerHello(OldTakerHello)
+        ));
+
+        let solution = TakerToMakerMessage::PowSolution(PowSolution { nonce: 42 });
+        let bytes = serde_cbor::to_vec(&solution).unwrap();
+        match serde_cbor::from_slice(&bytes).unwrap() {
+            TakerToMakerMessage::PowSolution(PowSolution { nonce }) => assert_eq!(nonce, 42),
+            msg => panic!("unexpected message {:?}", msg),
+        }
+    }
+}
@@ -1,11 +1,13 @@
 //! Taker API for both Legacy (ECDSA) and Taproot (MuSig2) protocols.
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     convert::TryFrom,
     net::TcpStream,
-    path::PathBuf,
-    sync::{atomic::AtomicBool, mpsc, Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::AtomicBool, mpsc, Arc, Mutex, OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
     thread,
     time::{Duration, Instant},
 };
@@ -27,22 +29,29 @@ use bitcoin::{
     Amount, OutPoint, PublicKey,
 };
 use bitcoind::bitcoincore_rpc::json::ListUnspentResultEntry;
+use serde::{Deserialize, Serialize};
 use socks::Socks5Stream;
 
 use crate::{
+    fault::{FaultAction, FaultInjector, FaultPoint, FaultRole, FaultStep},
     nostr_coinswap::NOSTR_RELAYS,
     protocol::{
         common_messages::{
-            GetOffer, MakerToTakerMessage, Offer, PrivateKeyHandover, ProtocolVersion, SwapDetails,
-            SwapPrivkey, TakerHello, TakerToMakerMessage,
+            GetOffer, MakerHello, MakerToTakerMessage, Offer, PowChallenge, PowSolution,
+            PrivateKeyHandover, ProtocolVersion, SwapDetails, SwapPrivkey, TakerHello,
+            TakerToMakerMessage, MAX_POW_DIFFICULTY,
         },
-        contract::calculate_pubkey_from_nonce,
+        contract::{calculate_coinswap_fee, calculate_pubkey_from_nonce},
+    },
+    utill::{
+        check_tor_status, generate_maker_keys, get_taker_dir, read_message, send_message,
+        MIN_FEE_RATE,
     },
-    utill::{check_tor_status, generate_maker_keys, get_taker_dir, read_message, send_message},
     wallet::{
         swapcoin::{IncomingSwapCoin, OutgoingSwapCoin, WatchOnlySwapCoin},
-        BackendConfig, BitcoindBackend, BlockchainBackend, MakerFeeInfo as ReportMakerFeeInfo,
-        RPCConfig, RecoveryOutcome, SwapStatus, TakerReport, Wallet,
+        BackendConfig, BitcoindBackend, BlockchainBackend, ContractRecoveryPlan,
+        MakerFeeInfo as ReportMakerFeeInfo, RPCConfig, RecoveryOutcome, SwapStatus,
+        SweepDestination, TakerReport, Wallet,
     },
     watch_tower::{
         registry_storage::FileRegistry,
@@ -54,13 +63,15 @@ use crate::{
 };
 
 use super::{
-    background_services::{BreachDetector, RecoveryLoop},
+    background_services::{BreachDetector, RecoveryLoop, RECOVERY_FEE_RATE},
     config::TakerConfig,
     error::TakerError,
+    multi_route::PreparedRoutes,
     offers::{
         MakerAddress, MakerOfferCandidate, MakerProtocol, OfferAndAddress, OfferBook,
         OfferBookHandle, OfferSyncClient, OfferSyncHandle, OfferSyncService,
     },
+    selection::{weighted_sample, MakerSelection},
 };
 
 /// Connection type for the taker.
@@ -117,6 +128,9 @@ pub(crate) const CONFIRMATION_HEIGHT_TOLERANCE: u32 = 50;
 /// actual deduction may be up to 50% more than the advertised maker fee.
 pub(crate) const FEE_VERIFICATION_MARGIN: f64 = 1.5;
 
+/// Fee rate of the taker's funding transactions, in sats/vB.
+pub(crate) const FUNDING_FEE_RATE: f64 = MIN_FEE_RATE;
+
 /// Taker configuration.
 #[derive(Debug, Clone)]
 pub struct TakerInitConfig {
@@ -138,6 +152,11 @@ pub struct TakerInitConfig {
     pub connection_type: ConnectionType,
     /// Nostr relay URLs for maker discovery.
     pub nostr_relays: Vec<String>,
+    /// Sweep, recover and keep recovering unresolved swap contracts on startup.
+    /// Disable to inspect or recover contracts selectively.
+    pub auto_recover: bool,
+    /// Seed for the random maker selection, making it reproducible. Random when `None`.
+    pub maker_selection_seed: Option<u64>,
 }
 
 impl Default for TakerInitConfig {
@@ -151,6 +170,8 @@ impl Default for TakerInitConfig {
             password: None,
             connection_type: ConnectionType::Tor,
             nostr_relays: NOSTR_RELAYS.iter().map(|s| s.to_string()).collect(),
+            auto_recover: true,
+            maker_selection_seed: None,
         }
     }
 }
@@ -173,6 +194,18 @@ impl TakerInitConfig {
         self.nostr_relays = relays;
         self
     }
+
+    /// Enable or disable startup recovery of unresolved swap contracts.
+    pub fn with_auto_recover(mut self, auto_recover: bool) -> Self {
+        self.auto_recover = auto_recover;
+        self
+    }
+
+    /// Seed the random maker selection, so the same makers are picked on every run.
+    pub fn with_maker_selection_seed(mut self, seed: u64) -> Self {
+        self.maker_selection_seed = Some(seed);
+        self
+    }
 }
 
 /// Swap parameters.
@@ -193,6 +226,16 @@ pub struct SwapParams {
     /// Manually specified maker addresses (optional). When set, these makers
     /// are used instead of auto-discovery from the offerbook.
     pub preferred_makers: Option<Vec<String>>,
+    /// Where the received coins are swept once the swap completes. Defaults to this wallet.
+    pub destination: SweepDestination,
+    /// Cap on the total maker fees, in sats. Defaults to `max_total_fee` from the taker config.
+    pub max_total_fee: Option<Amount>,
+    /// Cap on the total maker fees, as a percentage of `send_amount`. Defaults to
+    /// `max_fee_pct` from the taker config.
+    pub max_fee_pct: Option<f64>,
+    /// Number of independent routes `send_amount` is split across. Each route has its own
+    /// makers, funding UTXOs and preimage. Values below 2 run a single route.
+    pub route_count: usize,
 }
 
 impl SwapParams {
@@ -206,6 +249,10 @@ impl SwapParams {
             required_confirms: 1,
             manually_selected_outpoints: None,
             preferred_makers: None,
+            destination: SweepDestination::Wallet,
+            max_total_fee: None,
+            max_fee_pct: None,
+            route_count: 1,
         }
     }
 
@@ -233,6 +280,49 @@ impl SwapParams {
         self.preferred_makers = Some(makers);
         self
     }
+
+    /// Sweep the received coins to an external destination, such as a payment address or
+    /// a cold wallet, instead of this wallet.
+    ///
+    /// The destination is only used when the swap completes. Coins recovered from a failed
+    /// swap always return to this wallet.
+    pub fn with_destination(mut self, destination: SweepDestination) -> Self {
+        self.destination = destination;
+        self
+    }
+
+    /// Cap the total maker fees of the swap, in sats.
+    pub fn with_max_total_fee(mut self, max_total_fee: Amount) -> Self {
+        self.max_total_fee = Some(max_total_fee);
+        self
+    }
+
+    /// Cap the total maker fees of the swap, as a percentage of the swap amount.
+    pub fn with_max_fee_pct(mut self, max_fee_pct: f64) -> Self {
+        self.max_fee_pct = Some(max_fee_pct);
+        self
+    }
+
+    /// Split the swap into `route_count` independent routes with randomized amounts, run
+    /// concurrently through disjoint sets of makers.
+    ///
+    /// `max_total_fee` is shared out between the routes in proportion to their amounts.
+    pub fn with_routes(mut self, route_count: usize) -> Self {
+        self.route_count = route_count;
+        self
+    }
+
+    /// The most the swap may cost in maker fees: the lower of `max_total_fee` and
+    /// `max_fee_pct`. `None` when neither is set.
+    pub fn fee_budget(&self) -> Option<Amount> {
+        let pct_budget = self
+            .max_fee_pct
+            .map(|pct| Amount::from_sat((self.send_amount.to_sat() as f64 * pct / 100.0) as u64));
+        match (self.max_total_fee, pct_budget) {
+            (Some(total), Some(pct)) => Some(total.min(pct)),
+            (total, pct) => total.or(pct),
+        }
+    }
 }
 
 /// Per-maker fee breakdown returned in SwapSummary.
@@ -269,6 +359,139 @@ pub struct SwapSummary {
     pub total_estimated_fee: Amount,
     /// Estimated amount the taker will receive after all fees.
     pub estimated_receive_amount: Amount,
+    /// Fee cap the swap is held to, if any. See [`SwapParams::fee_budget`].
+    pub fee_budget: Option<Amount>,
+    /// Summaries of the individual routes of a multi-route swap, in route order. Empty
+    /// for a single-route swap.
+    pub routes: Vec<SwapSummary>,
+}
+
+/// One past or in-flight swap, merged from the swap tracker and the wallet's swap reports.
+///
+/// Never carries secrets (preimage, nonces), so it is safe to print or serialize.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapHistoryEntry {
+    /// Unique swap ID.
+    pub swap_id: String,
+    /// Final outcome, if a swap report was written for this swap.
+    pub status: Option<SwapStatus>,
+    /// Last tracked lifecycle phase, if the tracker still holds this swap.
+    pub phase: Option<SwapPhase>,
+    /// Protocol version, if known from the tracker.
+    pub protocol: Option<ProtocolVersion>,
+    /// Amount sent in satoshis.
+    pub send_amount_sat: u64,
+    /// Amount received back in satoshis, if reported.
+    pub received_amount_sat: Option<u64>,
+    /// Total fee paid in satoshis, if reported.
+    pub fee_paid_sat: Option<u64>,
+    /// Maker addresses in route order.
+    pub makers: Vec<String>,
+    /// Unix timestamp when the swap started.
+    pub started_at: u64,
+    /// Swap duration in seconds. For swaps still in progress, the time since they started.
+    pub duration_seconds: f64,
+    /// Failure reason, if the swap failed.
+    pub error: Option<String>,
+}
+
+/// When a tracked swap ended: its last update once completed or failed, now otherwise.
+fn record_end(record: &SwapRecord) -> u64 {
+    match record.phase {
+        SwapPhase::Completed | SwapPhase::Failed => record.updated_at,
+        _ => now_secs(),
+    }
+}
+
+impl SwapHistoryEntry {
+    fn from_record(record: &SwapRecord) -> Self {
+        SwapHistoryEntry {
+            swap_id: record.swap_id.clone(),
+            status: None,
+            phase: Some(record.phase),
+            protocol: Some(record.protocol),
+            send_amount_sat: record.send_amount_sat,
+            received_amount_sat: None,
+            fee_paid_sat: None,
+            makers: record.makers.iter().map(|m| m.address.clone()).collect(),
+            started_at: record.created_at,
+            duration_seconds: record_end(record).saturating_sub(record.created_at) as f64,
+            error: record.failure_reason.clone(),
+        }
+    }
+
+    fn from_report(report: &TakerReport) -> Self {
+        let mut entry = SwapHistoryEntry {
+            swap_id: report.swap_id.clone(),
+            status: None,
+            phase: None,
+            protocol: None,
+            send_amount_sat: report.outgoing_amount,
+            received_amount_sat: None,
+            fee_paid_sat: None,
+            makers: Vec::new(),
+            started_at: report.start_timestamp,
+            duration_seconds: 0.0,
+            error: None,
+        };
+        entry.apply_report(report);
+        entry
+    }
+
+    /// Overlay the outcome recorded in a swap report.
+    fn apply_report(&mut self, report: &TakerReport) {
+        self.status = Some(report.status.clone());
+        self.received_amount_sat = Some(report.incoming_amount);
+        self.fee_paid_sat = Some(report.fee_paid);
+        self.duration_seconds = report.swap_duration_seconds;
+        if !report.maker_addresses.is_empty() {
+            self.makers = report.maker_addresses.clone();
+        }
+        if report.error_message.is_some() {
+            self.error = report.error_message.clone();
+        }
+    }
+}
+
+/// Merge the tracker's records with the reports saved for `wallet_file_name` in `data_dir`.
+///
+/// Shared by [`Taker::swap_history`] and the `takerd` RPC, which answers history queries
+/// without waiting on the taker while a swap is running.
+pub(crate) fn load_swap_history(
+    swap_tracker: &Mutex<SwapTracker>,
+    data_dir: &Path,
+    wallet_file_name: &str,
+) -> Result<Vec<SwapHistoryEntry>, TakerError> {
+    let reports = TakerReport::load_for_wallet(data_dir, Some(wallet_file_name))?;
+
+    let mut entries: Vec<SwapHistoryEntry> = swap_tracker
+        .lock()
+        .unwrap()
+        .records()
+        .into_iter()
+        .map(SwapHistoryEntry::from_record)
+        .collect();
+
+    // Later reports for the same swap (e.g. a failure followed by a recovery) win.
+    for report in &reports {
+        match entries.iter_mut().find(|e| e.swap_id == report.swap_id) {
+            Some(entry) => entry.apply_report(report),
+            None => entries.push(SwapHistoryEntry::from_report(report)),
+        }
+    }
+
+    entries.sort_by_key(|e| e.started_at);
+    Ok(entries)
+}
+
+/// Wallet coins a swap spent and created, relative to a snapshot taken when it started.
+pub(crate) struct UtxoChanges {
+    /// Amounts of the snapshot's UTXOs that have been spent since.
+    pub(crate) input_utxos: Vec<u64>,
+    /// New regular UTXOs, as `(amount, address)`.
+    pub(crate) output_change_utxos: Vec<(u64, String)>,
+    /// Swept swap UTXOs and coins paid to an external destination, as `(amount, address)`.
+    pub(crate) output_swap_utxos: Vec<(u64, String)>,
 }
 
 /// State for an ongoing swap.
@@ -300,6 +523,8 @@ pub(crate) struct OngoingSwapState {
     /// Reference block height captured during negotiation for consistent Taproot CLTV timelocks.
     /// Taproot uses absolute heights, so all timelock calculations must use the same base height.
     pub(crate) reference_height: Option<u32>,
+    /// Sweep outputs paid to the external destination once the swap completed.
+    pub(crate) paid_out: Vec<(bitcoin::Address, Amount)>,
 }
 
 /// Connection state for a maker in the swap route.
@@ -348,6 +573,51 @@ impl MakerConnection {
     }
 }
 
+/// Estimate each hop's fee from the makers' offers, in route order.
+///
+/// Every hop pays its fee on what is left after the previous hops, at the locktime its
+/// position in the route gets. Hops without an offer are counted as free.
+pub(crate) fn estimate_route_fees(
+    send_amount: Amount,
+    hops: &[(String, ProtocolVersion, Option<&Offer>)],
+) -> Vec<MakerFeeInfo> {
+    let maker_count = hops.len();
+    let mut maker_fees = Vec::with_capacity(maker_count);
+    let mut amount_sats = send_amount.to_sat() as f64;
+
+    for (i, (address, protocol, offer)) in hops.iter().enumerate() {
+        let locktime = REFUND_LOCKTIME_BASE + REFUND_LOCKTIME_STEP * (maker_count - i - 1) as u16;
+
+        let (base_fee, amt_pct, time_pct) = match offer {
+            Some(offer) => (
+                offer.base_fee,
+                offer.amount_relative_fee_pct,
+                offer.time_relative_fee_pct,
+            ),
+            None => (0, 0.0, 0.0),
+        };
+
+        let fee = base_fee as f64
+            + (amount_sats * amt_pct) / 100.0
+            + (amount_sats * locktime as f64 * time_pct) / 100.0;
+        let fee_sats = fee.ceil() as u64;
+
+        maker_fees.push(MakerFeeInfo {
+            address: address.clone(),
+            protocol: *protocol,
+            base_fee,
+            amount_relative_fee_pct: amt_pct,
+            time_relative_fee_pct: time_pct,
+            locktime,
+            estimated_fee_sats: fee_sats,
+        });
+
+        amount_sats = (amount_sats - fee).max(0.0);
+    }
+
+    maker_fees
+}
+
 impl<B: BlockchainBackend> Taker<B> {
     /// Compute the minimum expected output amount for a specific maker hop.
     ///
@@ -358,6 +628,8 @@ impl<B: BlockchainBackend> Taker<B> {
     /// Returns `None` if any maker along the route (up to and including `maker_idx`)
     /// has no stored offer.
     ///
+    /// If the swap has a fee budget, the amount never goes below `send_amount - budget`.
+    ///
     /// Fee formula: `total_fee = base_fee + (amount * amt_pct)/100 + (amount * locktime * time_pct)/100`
     /// TODO: Use fee estimation here
     #[hotpath::measure]
@@ -379,7 +651,12 @@ impl<B: BlockchainBackend> Taker<B> {
             amount_sats = (amount_sats - fee_with_margin).max(0.0);
         }
 
-        Some(Amount::from_sat(amount_sats as u64))
+        // Whatever the margin allows, the deductions up to this hop must stay within the budget.
+        let min_expected = Amount::from_sat(amount_sats as u64);
+        match swap.params.fee_budget() {
+            Some(budget) => Some(min_expected.max(send_amount.checked_sub(budget)?)),
+            None => Some(min_expected),
+        }
     }
 }
 
@@ -399,24 +676,40 @@ pub struct Taker<B: BlockchainBackend = BitcoindBackend> {
     pub(crate) offerbook: OfferBookHandle,
     /// Watch service for transaction monitoring.
     pub(crate) watch_service: WatchService,
-    /// Handle for offer sync background service.
-    offer_sync_handle: OfferSyncHandle,
+    /// Handle for offer sync background service. `None` for route workers, which share
+    /// the offerbook of the taker that spawned them.
+    offer_sync_handle: Option<OfferSyncHandle>,
     /// Ongoing swap state (`None` when no swap is active).
     pub(crate) ongoing_swap: Option<OngoingSwapState>,
     /// Persistent swap tracker for crash-resilient recovery.
     pub(crate) swap_tracker: Arc<Mutex<SwapTracker>>,
     /// Background recovery loop (active when incomplete swap recovery is in progress).
-    recovery_loop: Option<RecoveryLoop>,
+    pub(crate) recovery_loop: Option<RecoveryLoop>,
+    /// Recovery loops of failed routes of multi-route swaps, kept running after their
+    /// route workers are gone.
+    pub(crate) route_recovery_loops: Vec<RecoveryLoop>,
+    /// Routes of a prepared multi-route swap, waiting for `start_coinswap`.
+    pub(crate) prepared_routes: Option<PreparedRoutes<B>>,
+    /// UTXOs set aside for the other routes of a multi-route swap. Never used to fund
+    /// this taker's swap.
+    pub(crate) reserved_outpoints: Vec<OutPoint>,
     /// Breach detector for legacy swaps (monitors funding outpoints for adversarial contract broadcasts).
     pub(crate) breach_detector: Option<BreachDetector>,
-    /// Test behavior.
-    #[cfg(feature = "integration-test")]
-    pub behavior: TakerBehavior,
+    /// Faults injected into the swap flow, for adversarial testing.
+    pub faults: FaultInjector,
+    /// How makers are picked from the offerbook.
+    pub(crate) maker_selection: MakerSelection,
+    /// Fee caps from the taker config, applied to swaps that don't set their own.
+    default_max_total_fee: Option<Amount>,
+    default_max_fee_pct: Option<f64>,
 }
 
 impl<B: BlockchainBackend> Drop for Taker<B> {
     fn drop(&mut self) {
-        log::info!("Shutting down taker.");
+        if !self.is_route_worker() {
+            log::info!("Shutting down taker.");
+        }
+        self.abort_prepared_routes();
         // Flush any pending swap state before shutdown
         if let Some(swap) = &self.ongoing_swap {
             if let Ok(record) = self.persist_build_record(swap) {
@@ -430,16 +723,26 @@ impl<B: BlockchainBackend> Drop for Taker<B> {
             log::info!("Shutting down recovery loop");
             drop(recovery);
         }
+        if !self.route_recovery_loops.is_empty() {
+            log::info!("Shutting down route recovery loops");
+            self.route_recovery_loops.clear();
+        }
         // Shut down breach detector (if running)
         if let Some(detector) = self.breach_detector.take() {
             log::info!("Shutting down breach detector");
             detector.stop();
         }
+        // A route worker shares its services with the taker that spawned it.
+        if self.is_route_worker() {
+            return;
+        }
         if let Err(e) = self.offerbook.persist() {
             log::error!("Failed to persist offerbook: {:?}", e);
         }
         log::info!("Shutting down offer sync background job");
-        self.offer_sync_handle.shutdown();
+        if let Some(handle) = &mut self.offer_sync_handle {
+            handle.shutdown();
+        }
         log::info!("Shutting down watch service background job");
         self.watch_service.shutdown();
         log::info!("Offerbook data saved to disk.");
@@ -452,6 +755,78 @@ impl<B: BlockchainBackend> Drop for Taker<B> {
     }
 }
 
+/// Most proof-of-work solutions kept for reuse; the cache is emptied when it fills up.
+const MAX_CACHED_POW_SOLUTIONS: usize = 256;
+
+/// Solutions to maker proof-of-work challenges, by challenge.
+///
+/// Makers serve the same challenge for a while, so offerbook syncs and swaps reuse a
+/// solution instead of solving it again on every connection.
+static POW_SOLUTIONS: OnceLock<Mutex<HashMap<PowChallenge, u64>>> = OnceLock::new();
+
+/// Solve `challenge`, or return the cached solution if it was solved before.
+fn solve_pow_challenge(challenge: &PowChallenge) -> u64 {
+    let cache = POW_SOLUTIONS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(nonce) = cache.lock().ok().and_then(|c| c.get(challenge).copied()) {
+        return nonce;
+    }
+
+    log::info!(
+        "Solving maker proof-of-work challenge (difficulty {})",
+        challenge.difficulty
+    );
+    let nonce = challenge.solve();
+    if let Ok(mut cache) = cache.lock() {
+        if cache.len() >= MAX_CACHED_POW_SOLUTIONS {
+            cache.clear();
+        }
+        cache.insert(challenge.clone(), nonce);
+    }
+    nonce
+}
+
+/// Send a [`TakerHello`] and return the maker's [`MakerHello`].
+///
+/// If the maker requires proof-of-work, its challenge is solved and sent in a [`PowSolution`],
+/// which the maker answers with a new hello.
+pub(crate) fn exchange_hello(stream: &mut TcpStream) -> Result<MakerHello, TakerError> {
+    let read_maker_hello = |stream: &mut TcpStream| -> Result<MakerHello, TakerError> {
+        let msg_bytes = read_message(stream)?;
+        match serde_cbor::from_slice(&msg_bytes)? {
+            MakerToTakerMessage::MakerHello(maker_hello) => Ok(maker_hello),
+            _ => Err(TakerError::General(
+                "Expected MakerHello response".to_string(),
+            )),
+        }
+    };
+
+    send_message(stream, &TakerToMakerMessage::TakerHello(TakerHello))?;
+    let maker_hello = read_maker_hello(stream)?;
+
+    let Some(challenge) = maker_hello.pow_challenge else {
+        return Ok(maker_hello);
+    };
+    if challenge.difficulty > MAX_POW_DIFFICULTY {
+        return Err(TakerError::General(format!(
+            "Maker proof-of-work difficulty {} is above {}",
+            challenge.difficulty, MAX_POW_DIFFICULTY
+        )));
+    }
+    let nonce = solve_pow_challenge(&challenge);
+    send_message(
+        stream,
+        &TakerToMakerMessage::PowSolution(PowSolution { nonce }),
+    )?;
+
+    let maker_hello = read_maker_hello(stream)?;
+    if maker_hello.pow_challenge.is_some() {
+        return Err(TakerError::General(
+            "Maker sent a new proof-of-work challenge after a solution".to_string(),
+        ));
+    }
+    Ok(maker_hello)
+}
+
 impl<B: BlockchainBackend> Role for Taker<B> {
     const RUN_DISCOVERY: bool = true;
 }
@@ -504,6 +879,9 @@ impl<B: BlockchainBackend> Taker<B> {
             }
         };
 
+        let faults = FaultInjector::from_env(FaultRole::Taker, wallet.store.network)
+            .map_err(|e| TakerError::General(e.to_string()))?;
+
         let (watch_service, registry, initial_sync_complete) = Self::init_watch_service(
             &config,
             rpc_config.as_ref(),
@@ -511,7 +889,12 @@ impl<B: BlockchainBackend> Taker<B> {
             chain.clone(),
             &data_dir,
         )?;
-        Self::init_taker_config(&config, &data_dir)?;
+        let taker_config = Self::init_taker_config(&config, &data_dir)?;
+        let maker_selection = MakerSelection {
+            bond_value_exponent: taker_config.maker_bond_value_exponent,
+            fee_sensitivity: taker_config.maker_fee_sensitivity,
+            seed: config.maker_selection_seed,
+        };
         let offerbook = OfferBookHandle::load_or_create(&data_dir)?;
         let offer_sync_handle = Self::init_offer_sync(
             &offerbook,
@@ -528,19 +911,68 @@ impl<B: BlockchainBackend> Taker<B> {
             wallet: Arc::new(RwLock::new(wallet)),
             offerbook,
             watch_service,
-            offer_sync_handle,
+            offer_sync_handle: Some(offer_sync_handle),
             ongoing_swap: None,
             swap_tracker,
             recovery_loop: None,
+            route_recovery_loops: Vec::new(),
+            prepared_routes: None,
+            reserved_outpoints: Vec::new(),
             breach_detector: None,
-            #[cfg(feature = "integration-test")]
-            behavior: TakerBehavior::Normal,
+            faults,
+            maker_selection,
+            default_max_total_fee: (taker_config.max_total_fee > 0)
+                .then(|| Amount::from_sat(taker_config.max_total_fee)),
+            default_max_fee_pct: (taker_config.max_fee_pct > 0.0)
+                .then_some(taker_config.max_fee_pct),
         };
 
-        taker.init_recover_wallet();
+        if taker.config.auto_recover {
+            // Resuming needs the makers, so it is left to the commands that run swaps. Until
+            // then, the startup recovery must not unwind the swaps waiting to be resumed.
+            let resumable = taker.swap_tracker.lock().unwrap().resumable_swaps().len();
+            if resumable == 0 {
+                taker.init_recover_wallet();
+            } else {
+                log::info!(
+                    "{} interrupted swap(s) waiting to be resumed, deferring startup recovery",
+                    resumable
+                );
+            }
+        }
         Ok(taker)
     }
 
+    /// A taker that runs one route of a multi-route swap.
+    ///
+    /// It shares the wallet, offerbook, watch service, swap tracker and fault injector with
+    /// this taker, but never funds its swap from `reserved_outpoints`.
+    pub(crate) fn route_worker(&self, reserved_outpoints: Vec<OutPoint>) -> Self {
+        Taker {
+            config: self.config.clone(),
+            wallet: self.wallet.clone(),
+            offerbook: self.offerbook.clone(),
+            watch_service: self.watch_service.clone(),
+            offer_sync_handle: None,
+            ongoing_swap: None,
+            swap_tracker: self.swap_tracker.clone(),
+            recovery_loop: None,
+            route_recovery_loops: Vec::new(),
+            prepared_routes: None,
+            reserved_outpoints,
+            breach_detector: None,
+            faults: self.faults.clone(),
+            maker_selection: self.maker_selection,
+            default_max_total_fee: self.default_max_total_fee,
+            default_max_fee_pct: self.default_max_fee_pct,
+        }
+    }
+
+    /// Whether this taker runs one route of a multi-route swap.
+    pub(crate) fn is_route_worker(&self) -> bool {
+        self.offer_sync_handle.is_none()
+    }
+
     /// Called on startup to recover funds from incomplete swaps.
     ///
     /// Sweeps incoming swapcoins (hashlock path), recovers timelocked outgoing
@@ -552,7 +984,7 @@ impl<B: BlockchainBackend> Taker<B> {
         // Wallet-driven recovery: sweep incoming + recover timelocked
         let has_remaining = match self.write_wallet() {
             Ok(mut wallet) => {
-                match wallet.sweep_incoming_swapcoins(2.0) {
+                match wallet.sweep_incoming_swapcoins(RECOVERY_FEE_RATE) {
                     Ok(ref swept) if !swept.is_empty() => {
                         log::info!(
                             "Startup recovery: swept {} incoming swapcoins",
@@ -563,7 +995,7 @@ impl<B: BlockchainBackend> Taker<B> {
                     Err(e) => log::warn!("Startup incoming sweep failed: {:?}", e),
                 }
 
-                match wallet.recover_timelocked_swapcoins(2.0) {
+                match wallet.recover_timelocked_swapcoins(RECOVERY_FEE_RATE) {
                     Ok(ref recovered) if !recovered.is_empty() => {
                         log::info!(
                             "Startup recovery: recovered {} timelocked outgoing swapcoins",
@@ -574,8 +1006,8 @@ impl<B: BlockchainBackend> Taker<B> {
                     Err(e) => log::warn!("Startup timelock recovery failed: {:?}", e),
                 }
 
-                let has_contracts = !wallet.outgoing_contract_outpoints().is_empty()
-                    || !wallet.incoming_contract_outpoints().is_empty();
+                let has_contracts = !wallet.outgoing_contract_outpoints(None).is_empty()
+                    || !wallet.incoming_contract_outpoints(None).is_empty();
                 drop(wallet);
                 has_contracts
             }
@@ -591,6 +1023,7 @@ impl<B: BlockchainBackend> Taker<B> {
                 self.wallet.clone(),
                 self.swap_tracker.clone(),
                 data_dir,
+                None,
             ));
         }
     }
@@ -650,11 +1083,11 @@ impl<B: BlockchainBackend> Taker<B> {
         ))
     }
 
-    /// Load/merge taker config and check Tor status.
+    /// Load/merge taker config, check Tor status and return the merged config.
     fn init_taker_config(
         config: &TakerInitConfig,
         data_dir: &std::path::Path,
-    ) -> Result<(), TakerError> {
+    ) -> Result<TakerConfig, TakerError> {
         let mut taker_config = TakerConfig::new(Some(&data_dir.join("config.toml")))?;
 
         if let Some(control_port) = config.control_port {
@@ -673,7 +1106,7 @@ impl<B: BlockchainBackend> Taker<B> {
         }
 
         taker_config.write_to_file(&data_dir.join("config.toml"))?;
-        Ok(())
+        Ok(taker_config)
     }
 
     /// Start the background offer sync service.
@@ -707,18 +1140,60 @@ impl<B: BlockchainBackend> Taker<B> {
     /// Check whether the background recovery loop has completed.
     /// Returns `true` if no recovery is needed or if all contracts are resolved.
     pub fn is_recovery_complete(&self) -> bool {
-        match &self.recovery_loop {
+        let main_complete = match &self.recovery_loop {
             Some(loop_) => loop_.is_complete(),
             None => true,
+        };
+        main_complete
+            && self
+                .route_recovery_loops
+                .iter()
+                .all(RecoveryLoop::is_complete)
+    }
+
+    /// Resolve contracts left behind by earlier swaps, the same way startup recovery does.
+    ///
+    /// Does nothing while a swap is in flight or a recovery loop is still running. Long-running
+    /// callers (such as `takerd`) call this periodically so a failed swap's contracts are
+    /// picked up without restarting the process.
+    pub fn recover_unresolved_contracts(&mut self) {
+        if self.ongoing_swap.is_some() || !self.is_recovery_complete() {
+            return;
         }
+        self.init_recover_wallet();
+    }
+
+    /// List all known swaps, oldest first.
+    ///
+    /// Merges the records held by the swap tracker with the taker reports saved next to
+    /// the wallet, so swaps that were aborted before funding (and dropped from the tracker)
+    /// still show up with their reported outcome.
+    pub fn swap_history(&self) -> Result<Vec<SwapHistoryEntry>, TakerError> {
+        let data_dir = self.config.data_dir.clone().unwrap_or_else(get_taker_dir);
+        let wallet_file_name = self.read_wallet()?.get_name().to_string();
+        load_swap_history(&self.swap_tracker, &data_dir, &wallet_file_name)
+    }
+
+    /// Get the tracked state of a swap by ID, including per-maker progress and
+    /// per-contract resolutions. Returns `None` if the tracker holds no such swap.
+    pub fn swap_status(&self, swap_id: &str) -> Option<SwapRecord> {
+        self.swap_tracker
+            .lock()
+            .unwrap()
+            .get_record(swap_id)
+            .cloned()
     }
 
     /// Prepare a coinswap: discover makers, negotiate, and return a summary.
     ///
     /// No funds are committed. The caller reviews the summary and then calls
     /// `start_coinswap` with the returned `swap_id` to execute.
+    ///
+    /// With [`SwapParams::route_count`] above 1, every route is prepared and the summary
+    /// combines them, with the per-route summaries in [`SwapSummary::routes`].
     #[hotpath::measure]
-    pub fn prepare_coinswap(&mut self, params: SwapParams) -> Result<SwapSummary, TakerError> {
+    pub fn prepare_coinswap(&mut self, mut params: SwapParams) -> Result<SwapSummary, TakerError> {
+        self.abort_prepared_routes();
         log::info!(
             "Preparing coinswap: amount={}, makers={}, protocol={:?}",
             params.send_amount,
@@ -747,6 +1222,12 @@ impl<B: BlockchainBackend> Taker<B> {
             }
         }
 
+        self.apply_default_fee_caps(&mut params)?;
+        if params.route_count > 1 {
+            return self.prepare_routes(params);
+        }
+        let fee_budget = params.fee_budget();
+
         let mut preimage = [0u8; 32];
         OsRng.fill_bytes(&mut preimage);
 
@@ -754,7 +1235,6 @@ impl<B: BlockchainBackend> Taker<B> {
         log::info!("Preparing coinswap with id: {}", swap_id);
 
         let send_amount = params.send_amount;
-        let maker_count = params.maker_count;
 
         self.ongoing_swap = Some(OngoingSwapState {
             id: swap_id.clone(),
@@ -769,16 +1249,19 @@ impl<B: BlockchainBackend> Taker<B> {
             spare_makers: Vec::new(),
             phase: SwapPhase::MakersDiscovered,
             reference_height: None,
+            paid_out: Vec::new(),
         });
 
         self.discover_makers()?;
         self.persist_swap(SwapPhase::MakersDiscovered)?;
 
-        #[cfg(feature = "integration-test")]
-        if self.behavior == TakerBehavior::CloseEarly {
-            log::warn!("Test behavior: closing early after maker selection");
+        if self
+            .faults
+            .fire(&FaultPoint::Step(FaultStep::MakersSelected))
+            .is_some()
+        {
             return Err(TakerError::General(
-                "Test: Closing early after maker selection".to_string(),
+                "Fault: closing early after maker selection".to_string(),
             ));
         }
 
@@ -786,40 +1269,12 @@ impl<B: BlockchainBackend> Taker<B> {
         self.persist_swap(SwapPhase::Negotiated)?;
 
         // Build the summary from negotiated state.
-        let swap = self.swap_state()?;
-        let protocol = swap.params.protocol;
-        let mut maker_fees = Vec::with_capacity(maker_count);
-        let mut amount_sats = send_amount.to_sat() as f64;
-
-        for (i, mc) in swap.makers.iter().enumerate() {
-            let locktime =
-                REFUND_LOCKTIME_BASE + REFUND_LOCKTIME_STEP * (maker_count - i - 1) as u16;
-
-            let (base_fee, amt_pct, time_pct) = match &mc.offer {
-                Some(offer) => (
-                    offer.base_fee,
-                    offer.amount_relative_fee_pct,
-                    offer.time_relative_fee_pct,
-                ),
-                None => (0, 0.0, 0.0),
-            };
-
-            let fee = base_fee as f64
-                + (amount_sats * amt_pct) / 100.0
-                + (amount_sats * locktime as f64 * time_pct) / 100.0;
-            let fee_sats = fee.ceil() as u64;
-
-            maker_fees.push(MakerFeeInfo {
-                address: mc.address.to_string(),
-                protocol: mc.protocol,
-                base_fee,
-                amount_relative_fee_pct: amt_pct,
-                time_relative_fee_pct: time_pct,
-                locktime,
-                estimated_fee_sats: fee_sats,
-            });
+        let protocol = self.swap_state()?.params.protocol;
+        let maker_fees = self.route_fee_breakdown()?;
 
-            amount_sats = (amount_sats - fee).max(0.0);
+        if let Err(e) = self.check_fee_budget() {
+            self.abort_prepared_swap();
+            return Err(e);
         }
 
         let total_fee_sats: u64 = maker_fees.iter().map(|m| m.estimated_fee_sats).sum();
@@ -834,6 +1289,8 @@ impl<B: BlockchainBackend> Taker<B> {
             makers: maker_fees,
             total_estimated_fee: Amount::from_sat(total_fee_sats),
             estimated_receive_amount: estimated_receive,
+            fee_budget,
+            routes: Vec::new(),
         };
 
         log::info!(
@@ -846,13 +1303,90 @@ impl<B: BlockchainBackend> Taker<B> {
         Ok(summary)
     }
 
+    /// Per-hop fee estimate of the negotiated route, from each maker's offer.
+    fn route_fee_breakdown(&self) -> Result<Vec<MakerFeeInfo>, TakerError> {
+        let swap = self.swap_state()?;
+        let hops: Vec<_> = swap
+            .makers
+            .iter()
+            .map(|mc| (mc.address.to_string(), mc.protocol, mc.offer.as_ref()))
+            .collect();
+        Ok(estimate_route_fees(swap.params.send_amount, &hops))
+    }
+
+    /// Fail if the negotiated route would cost more in fees than the swap's budget allows.
+    fn check_fee_budget(&self) -> Result<(), TakerError> {
+        let Some(budget) = self.swap_state()?.params.fee_budget() else {
+            return Ok(());
+        };
+        let total_fee = Amount::from_sat(
+            self.route_fee_breakdown()?
+                .iter()
+                .map(|m| m.estimated_fee_sats)
+                .sum(),
+        );
+        if total_fee > budget {
+            log::warn!(
+                "Negotiated route costs {} in fees, above the budget of {}",
+                total_fee,
+                budget
+            );
+            return Err(TakerError::General(format!(
+                "Estimated swap fee {} exceeds the fee budget {}",
+                total_fee, budget
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fill in the fee caps the swap doesn't set from the taker config, and validate them.
+    pub(crate) fn apply_default_fee_caps(&self, params: &mut SwapParams) -> Result<(), TakerError> {
+        if params.max_total_fee.is_none() {
+            params.max_total_fee = self.default_max_total_fee;
+        }
+        if params.max_fee_pct.is_none() {
+            params.max_fee_pct = self.default_max_fee_pct;
+        }
+        if let Some(pct) = params.max_fee_pct {
+            if !pct.is_finite() || pct < 0.0 {
+                return Err(TakerError::General(format!(
+                    "Invalid max fee percentage: {}",
+                    pct
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop a swap that has not committed any funds, forgetting its tracker record.
+    pub(crate) fn abort_prepared_swap(&mut self) {
+        let _ = self
+            .swap_tracker
+            .lock()
+            .unwrap()
+            .remove_record(&self.swap_state().map(|s| s.id.clone()).unwrap_or_default());
+        self.ongoing_swap = None;
+    }
+
     /// Execute a prepared coinswap. Call after reviewing the `SwapSummary`
     /// from `prepare_coinswap`.
     ///
     /// Commits funds on-chain: creates funding transactions, exchanges
     /// contracts with makers, finalizes, and sweeps.
+    ///
+    /// For a multi-route swap the routes run concurrently. A failed route is recovered on
+    /// its own while the others carry on; the combined report is returned as long as one
+    /// route completed.
     #[hotpath::measure]
     pub fn start_coinswap(&mut self, swap_id: &str) -> Result<TakerReport, TakerError> {
+        if self
+            .prepared_routes
+            .as_ref()
+            .is_some_and(|routes| routes.group_id == swap_id)
+        {
+            return self.start_routes();
+        }
+
         let swap_start_time = Instant::now();
 
         // Verify the swap_id matches the prepared swap.
@@ -924,15 +1458,12 @@ impl<B: BlockchainBackend> Taker<B> {
                         if phase >= SwapPhase::FundsBroadcast {
                             log::warn!("Funding txs were broadcast, triggering recovery");
                             self.persist_failure(phase, &e);
-                            if let Err(re) = self.recover_active_swap() {
+                            if let Err(re) = self.recover_failed_swap() {
                                 log::error!("Recovery failed: {:?}", re);
                             }
                         } else {
                             log::info!("No funds on-chain — safe to abort");
-                            let _ = self.swap_tracker.lock().unwrap().remove_record(
-                                &self.swap_state().map(|s| s.id.clone()).unwrap_or_default(),
-                            );
-                            self.ongoing_swap = None;
+                            self.abort_prepared_swap();
                         }
                         return Err(e);
                     }
@@ -950,52 +1481,47 @@ impl<B: BlockchainBackend> Taker<B> {
                     if phase >= SwapPhase::FundsBroadcast {
                         log::warn!("Funds were broadcast, triggering recovery");
                         self.persist_failure(phase, &e);
-                        if let Err(re) = self.recover_active_swap() {
+                        if let Err(re) = self.recover_failed_swap() {
                             log::error!("Recovery failed: {:?}", re);
                         }
                     } else {
                         log::info!("No funds on-chain — safe to abort");
-                        let _ = self.swap_tracker.lock().unwrap().remove_record(
-                            &self.swap_state().map(|s| s.id.clone()).unwrap_or_default(),
-                        );
-                        self.ongoing_swap = None;
+                        self.abort_prepared_swap();
                     }
                     return Err(e);
                 }
             },
         }
 
-        #[cfg(feature = "integration-test")]
-        if self.behavior == TakerBehavior::BroadcastContractAfterFullSetup {
-            log::warn!("Test behavior: broadcasting contract txs after full setup, then closing");
-            // Broadcast outgoing contract transactions to trigger recovery paths
-            let wallet = self.read_wallet()?;
-            for outgoing in &self.swap_state()?.outgoing_swapcoins {
-                let _ = wallet.send_tx(&outgoing.contract_tx);
+        match self
+            .faults
+            .fire(&FaultPoint::Step(FaultStep::FundsBroadcast))
+        {
+            Some(FaultAction::BroadcastContracts) => {
+                // Broadcast outgoing contract transactions to trigger recovery paths
+                self.broadcast_outgoing_contracts()?;
+                let phase = self
+                    .swap_state()
+                    .map(|s| s.phase)
+                    .unwrap_or(SwapPhase::FundsBroadcast);
+                let err =
+                    TakerError::General("Fault: broadcast contract after full setup".to_string());
+                self.persist_failure(phase, &err);
+                return Err(err);
             }
-            drop(wallet);
-            let phase = self
-                .swap_state()
-                .map(|s| s.phase)
-                .unwrap_or(SwapPhase::FundsBroadcast);
-            let err = TakerError::General("Test: broadcast contract after full setup".to_string());
-            self.persist_failure(phase, &err);
-            return Err(err);
-        }
-
-        #[cfg(feature = "integration-test")]
-        if self.behavior == TakerBehavior::DropAfterFundsBroadcast {
-            log::warn!("Test behavior: dropping after contract exchange");
-            let phase = self
-                .swap_state()
-                .map(|s| s.phase)
-                .unwrap_or(SwapPhase::FundsBroadcast);
-            let err = TakerError::General("Test: dropped after contract exchange".to_string());
-            self.persist_failure(phase, &err);
-            if let Err(re) = self.recover_active_swap() {
-                log::error!("Recovery failed: {:?}", re);
+            Some(_) => {
+                let phase = self
+                    .swap_state()
+                    .map(|s| s.phase)
+                    .unwrap_or(SwapPhase::FundsBroadcast);
+                let err = TakerError::General("Fault: dropped after contract exchange".to_string());
+                self.persist_failure(phase, &err);
+                if let Err(re) = self.recover_failed_swap() {
+                    log::error!("Recovery failed: {:?}", re);
+                }
+                return Err(err);
             }
-            return Err(err);
+            None => {}
         }
 
         self.finalize_persist_incoming()?;
@@ -1009,7 +1535,7 @@ impl<B: BlockchainBackend> Taker<B> {
                 log::error!("Finalization failed after retries: {:?}", e);
                 self.emit_failure_report(&initial_utxos, swap_start_time, &e);
                 self.persist_failure(SwapPhase::Finalizing, &e);
-                if let Err(re) = self.recover_active_swap() {
+                if let Err(re) = self.recover_failed_swap() {
                     log::error!("Recovery failed: {:?}", re);
                 }
                 return Err(e);
@@ -1021,15 +1547,35 @@ impl<B: BlockchainBackend> Taker<B> {
             detector.stop();
         }
 
-        // Success path: sweep + report (shared by both protocols)
-        let swap_id_owned = swap_id.to_string();
+        self.complete_swap(&initial_utxos, swap_start_time)
+    }
+
+    /// Sweep the incoming coins of a finalized swap, clean up its swapcoins and report it.
+    fn complete_swap(
+        &mut self,
+        initial_utxos: &[ListUnspentResultEntry],
+        swap_start_time: Instant,
+    ) -> Result<TakerReport, TakerError> {
+        let swap_id_owned = self.swap_state()?.id.clone();
+        let destination = self.swap_state()?.params.destination.clone();
         let swept = {
             let mut wallet = self.write_wallet()?;
-            let swept = wallet.sweep_incoming_swapcoins(2.0)?;
+            // Coins of other swaps must not follow this swap to an external destination,
+            // nor be swept from under a sibling route that is still finalizing.
+            let swept = if destination.is_external() || self.is_route_worker() {
+                wallet.sweep_incoming_swapcoins_to(
+                    RECOVERY_FEE_RATE,
+                    Some(&swap_id_owned),
+                    &destination,
+                )?
+            } else {
+                wallet.sweep_incoming_swapcoins(RECOVERY_FEE_RATE)?
+            };
             log::info!("Swept {} incoming swapcoins", swept.resolved.len());
             wallet.sync_and_save()?;
             swept
         };
+        self.swap_state_mut()?.paid_out = swept.paid_out.clone();
 
         self.populate_success_outcomes(&swap_id_owned, &swept)?;
 
@@ -1048,7 +1594,7 @@ impl<B: BlockchainBackend> Taker<B> {
 
         // Generate, save, and return the SwapReport
         let report =
-            self.generate_swap_report(&initial_utxos, swap_start_time, SwapStatus::Success, None)?;
+            self.generate_swap_report(initial_utxos, swap_start_time, SwapStatus::Success, None)?;
 
         log::info!("Coinswap completed successfully: {:?}", report);
         Ok(report)
@@ -1061,11 +1607,10 @@ impl<B: BlockchainBackend> Taker<B> {
     /// from the offerbook.
     #[hotpath::measure]
     fn discover_makers(&mut self) -> Result<(), TakerError> {
-        let swap = self.swap_state()?;
-        let maker_count = swap.params.maker_count;
-        let send_amount = swap.params.send_amount;
-        let protocol = swap.params.protocol;
-        let preferred = swap.params.preferred_makers.clone();
+        let params = self.swap_state()?.params.clone();
+        let maker_count = params.maker_count;
+        let protocol = params.protocol;
+        let preferred = params.preferred_makers.clone();
 
         log::info!("Discovering makers for {} hops...", maker_count);
 
@@ -1117,40 +1662,7 @@ impl<B: BlockchainBackend> Taker<B> {
             (makers, spare_addrs)
         } else {
             // Auto-select from offerbook.
-            let maker_protocol = match protocol {
-                ProtocolVersion::Legacy => MakerProtocol::Legacy,
-                ProtocolVersion::Taproot => MakerProtocol::Taproot,
-            };
-
-            let available_makers = self.offerbook.active_makers(&maker_protocol);
-
-            if available_makers.is_empty() {
-                return Err(TakerError::NotEnoughMakersInOfferBook);
-            }
-
-            let suitable_makers: Vec<OfferAndAddress> = available_makers
-                .into_iter()
-                .filter(|maker| {
-                    let min_ok = send_amount.to_sat() >= maker.offer.min_size;
-                    let max_ok = send_amount.to_sat() <= maker.offer.max_size;
-                    min_ok && max_ok
-                })
-                .collect();
-
-            if suitable_makers.len() < maker_count {
-                log::error!(
-                    "Not enough suitable makers. Required: {}, Available: {}",
-                    maker_count,
-                    suitable_makers.len()
-                );
-                return Err(TakerError::NotEnoughMakersInOfferBook);
-            }
-
-            let spare_count = suitable_makers.len().saturating_sub(maker_count).min(2);
-            let total_select = maker_count + spare_count;
-
-            let mut selected: Vec<OfferAndAddress> =
-                suitable_makers.into_iter().take(total_select).collect();
+            let mut selected = self.select_makers_from_offerbook(&params, &[])?;
 
             let spare_oas = selected.split_off(maker_count);
             let spare_addrs: Vec<MakerAddress> =
@@ -1198,6 +1710,122 @@ impl<B: BlockchainBackend> Taker<B> {
         Ok(())
     }
 
+    /// Pick an auto-selected route from the offerbook: `maker_count` hops, in route order,
+    /// followed by up to two spares. Makers in `excluded` are never picked.
+    pub(crate) fn select_makers_from_offerbook(
+        &self,
+        params: &SwapParams,
+        excluded: &[MakerAddress],
+    ) -> Result<Vec<OfferAndAddress>, TakerError> {
+        let maker_count = params.maker_count;
+        let send_amount = params.send_amount;
+        let fee_budget = params.fee_budget();
+        let maker_protocol = match params.protocol {
+            ProtocolVersion::Legacy => MakerProtocol::Legacy,
+            ProtocolVersion::Taproot => MakerProtocol::Taproot,
+        };
+
+        let available_makers = self.offerbook.active_makers(&maker_protocol);
+
+        if available_makers.is_empty() {
+            return Err(TakerError::NotEnoughMakersInOfferBook);
+        }
+
+        let suitable_makers: Vec<OfferAndAddress> = available_makers
+            .into_iter()
+            .filter(|maker| !excluded.contains(&maker.address))
+            .filter(|maker| {
+                let min_ok = send_amount.to_sat() >= maker.offer.min_size;
+                let max_ok = send_amount.to_sat() <= maker.offer.max_size;
+                min_ok && max_ok
+            })
+            .filter(|maker| match fee_budget {
+                // Give each hop an equal share of the budget, priced at the longest
+                // locktime, so any route drawn from these makers fits the budget.
+                Some(budget) => {
+                    let fee = calculate_coinswap_fee(
+                        send_amount.to_sat(),
+                        REFUND_LOCKTIME_BASE + REFUND_LOCKTIME_STEP * (maker_count - 1) as u16,
+                        maker.offer.base_fee,
+                        maker.offer.amount_relative_fee_pct,
+                        maker.offer.time_relative_fee_pct,
+                    );
+                    let fits = fee <= budget.to_sat() / maker_count as u64;
+                    if !fits {
+                        log::debug!(
+                            "Skipping maker {}: fee {} sats exceeds its share of the budget {}",
+                            maker.address,
+                            fee,
+                            budget
+                        );
+                    }
+                    fits
+                }
+                None => true,
+            })
+            .collect();
+
+        if suitable_makers.len() < maker_count {
+            log::error!(
+                "Not enough suitable makers. Required: {}, Available: {}",
+                maker_count,
+                suitable_makers.len()
+            );
+            return Err(TakerError::NotEnoughMakersInOfferBook);
+        }
+
+        let spare_count = suitable_makers.len().saturating_sub(maker_count).min(2);
+        let total_select = maker_count + spare_count;
+
+        self.pick_weighted_makers(suitable_makers, total_select, send_amount)
+    }
+
+    /// Draw `count` makers at random, weighted by fidelity bond value and, if configured, by fee.
+    ///
+    /// Makers whose bond cannot be valued (e.g. expired) get no weight and are only picked
+    /// when there are not enough others.
+    fn pick_weighted_makers(
+        &self,
+        makers: Vec<OfferAndAddress>,
+        count: usize,
+        send_amount: Amount,
+    ) -> Result<Vec<OfferAndAddress>, TakerError> {
+        let send_amount = send_amount.to_sat().max(1);
+        let candidates = {
+            let wallet = self.read_wallet()?;
+            makers
+                .into_iter()
+                .map(|maker| {
+                    let bond_value = wallet
+                        .calculate_bond_value(&maker.offer.fidelity.bond)
+                        .unwrap_or_else(|e| {
+                            log::debug!("No bond value for maker {}: {:?}", maker.address, e);
+                            Amount::ZERO
+                        });
+                    // Fee estimated at the base refund locktime, as a percentage of the amount.
+                    let fee_pct = maker.offer.base_fee as f64 * 100.0 / send_amount as f64
+                        + maker.offer.amount_relative_fee_pct
+                        + maker.offer.time_relative_fee_pct * REFUND_LOCKTIME_BASE as f64;
+                    let weight = self.maker_selection.weight(bond_value, fee_pct);
+                    log::debug!(
+                        "Maker {}: bond value {}, fee {:.4}%, weight {}",
+                        maker.address,
+                        bond_value,
+                        fee_pct,
+                        weight
+                    );
+                    (maker, weight)
+                })
+                .collect()
+        };
+
+        Ok(weighted_sample(
+            candidates,
+            count,
+            &mut self.maker_selection.rng(),
+        ))
+    }
+
     /// Negotiate swap details with each maker, substituting spare makers on failure.
     #[hotpath::measure]
     fn negotiate_swap_details(&mut self) -> Result<(), TakerError> {
@@ -1337,10 +1965,9 @@ impl<B: BlockchainBackend> Taker<B> {
             refund_locktime_offset,
         };
 
-        send_message(&mut stream, &TakerToMakerMessage::SwapDetails(swap_details))?;
+        self.send_to_maker(&mut stream, TakerToMakerMessage::SwapDetails(swap_details))?;
 
-        let msg_bytes = read_message(&mut stream)?;
-        let msg: MakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
+        let msg = self.read_from_maker(&mut stream)?;
 
         match msg {
             MakerToTakerMessage::AckSwapDetails(ack) => {
@@ -1351,17 +1978,6 @@ impl<B: BlockchainBackend> Taker<B> {
                     swap.makers[maker_idx].negotiated_timelock = timelock;
                     log::info!("Maker {} accepted swap with tweakable point", maker_idx);
 
-                    #[cfg(feature = "integration-test")]
-                    if self.behavior == TakerBehavior::CloseAtAckResponse {
-                        log::warn!(
-                            "Test behavior: closing after receiving AckSwapDetails from maker {}",
-                            maker_idx
-                        );
-                        return Err(TakerError::General(
-                            "Test: closing at ack response".to_string(),
-                        ));
-                    }
-
                     Ok(())
                 } else {
                     Err(TakerError::General(format!(
@@ -1384,6 +2000,13 @@ impl<B: BlockchainBackend> Taker<B> {
         maker_idx: usize,
         send_amount: Amount,
     ) -> Result<(), TakerError> {
+        if !offer.accepting_swaps {
+            return Err(TakerError::General(format!(
+                "Maker {} is paused and not accepting new swaps",
+                maker_idx
+            )));
+        }
+
         // Fee percentage sanity: must be finite and non-negative, and < 100%
         if offer.amount_relative_fee_pct.is_nan()
             || offer.amount_relative_fee_pct.is_infinite()
@@ -1497,7 +2120,9 @@ impl<B: BlockchainBackend> Taker<B> {
             tx_count,
             maker_count,
             reference_height,
-        )
+        )?;
+        // The spare's fees replace the original maker's, so the route must be re-priced.
+        self.check_fee_budget()
     }
 
     /// Re-initialize funding after substituting the first maker.
@@ -1553,6 +2178,8 @@ impl<B: BlockchainBackend> Taker<B> {
         let send_amount = swap.params.send_amount;
         let swap_id = swap.id.clone();
         let manually_selected_outpoints = swap.params.manually_selected_outpoints.clone();
+        let excluded_outpoints =
+            (!self.reserved_outpoints.is_empty()).then(|| self.reserved_outpoints.clone());
         let reference_height = swap.reference_height;
 
         let (multisig_pubkeys, multisig_nonces, hashlock_pubkeys, hashlock_nonces) =
@@ -1600,6 +2227,7 @@ impl<B: BlockchainBackend> Taker<B> {
                 &swap_id,
                 network,
                 manually_selected_outpoints,
+                excluded_outpoints,
             )?,
             ProtocolVersion::Taproot => Self::funding_create_taproot(
                 &mut wallet,
@@ -1611,6 +2239,7 @@ impl<B: BlockchainBackend> Taker<B> {
                 &swap_id,
                 network,
                 manually_selected_outpoints,
+                excluded_outpoints,
                 reference_height,
             )?,
         };
@@ -1636,28 +2265,61 @@ impl<B: BlockchainBackend> Taker<B> {
         &self,
         stream: &mut TcpStream,
     ) -> Result<ProtocolVersion, TakerError> {
-        // Send TakerHello
-        send_message(stream, &TakerToMakerMessage::TakerHello(TakerHello))?;
+        let maker_hello = exchange_hello(stream)?;
+        let desired = self.swap_state()?.params.protocol;
+        if maker_hello.supported_protocols.contains(&desired) {
+            Ok(desired)
+        } else {
+            Err(TakerError::General(format!(
+                "Maker does not support {:?}. Supported: {:?}",
+                desired, maker_hello.supported_protocols
+            )))
+        }
+    }
 
+    /// Send a swap message to a maker, after any injected fault.
+    pub(crate) fn send_to_maker(
+        &self,
+        stream: &mut TcpStream,
+        mut msg: TakerToMakerMessage,
+    ) -> Result<(), TakerError> {
+        if let Some(action) = self.faults.fire_message(FaultPoint::Send, &mut msg) {
+            return Err(self.inject_fault(action));
+        }
+        send_message(stream, &msg)?;
+        Ok(())
+    }
+
+    /// Read a swap message from a maker, then apply any injected fault.
+    pub(crate) fn read_from_maker(
+        &self,
+        stream: &mut TcpStream,
+    ) -> Result<MakerToTakerMessage, TakerError> {
         let msg_bytes = read_message(stream)?;
-        let msg: MakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
+        let mut msg: MakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
+        if let Some(action) = self.faults.fire_message(FaultPoint::Recv, &mut msg) {
+            return Err(self.inject_fault(action));
+        }
+        Ok(msg)
+    }
 
-        match msg {
-            MakerToTakerMessage::MakerHello(maker_hello) => {
-                let desired = self.swap_state()?.params.protocol;
-                if maker_hello.supported_protocols.contains(&desired) {
-                    Ok(desired)
-                } else {
-                    Err(TakerError::General(format!(
-                        "Maker does not support {:?}. Supported: {:?}",
-                        desired, maker_hello.supported_protocols
-                    )))
-                }
+    /// Carry out a fault injected into a maker connection, returning the error that closes it.
+    fn inject_fault(&self, action: FaultAction) -> TakerError {
+        if action == FaultAction::BroadcastContracts {
+            if let Err(e) = self.broadcast_outgoing_contracts() {
+                log::warn!("Fault: failed to broadcast contract txs: {:?}", e);
             }
-            _ => Err(TakerError::General(
-                "Expected MakerHello response".to_string(),
-            )),
         }
+        TakerError::General(format!("Fault: closing connection ({action:?})"))
+    }
+
+    /// Broadcast the contract transactions of our outgoing swapcoins.
+    fn broadcast_outgoing_contracts(&self) -> Result<(), TakerError> {
+        let wallet = self.read_wallet()?;
+        for outgoing in &self.swap_state()?.outgoing_swapcoins {
+            let _ = wallet.send_tx(&outgoing.contract_tx);
+        }
+        Ok(())
     }
 
     /// Connect to a maker using either direct connection or Tor proxy.
@@ -1780,10 +2442,9 @@ impl<B: BlockchainBackend> Taker<B> {
             log::info!("Sending privkey to maker {} and awaiting response", i);
 
             let msg = Self::msg_build_handover(protocol, swap_id.clone(), current_privkey);
-            send_message(&mut stream, &msg)?;
+            self.send_to_maker(&mut stream, msg)?;
 
-            let msg_bytes = read_message(&mut stream)?;
-            let msg: MakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
+            let msg = self.read_from_maker(&mut stream)?;
 
             let received_privkey = match msg {
                 MakerToTakerMessage::LegacyPrivateKeyHandover(handover)
@@ -1807,6 +2468,7 @@ impl<B: BlockchainBackend> Taker<B> {
             self.swap_state_mut()?.makers[i]
                 .finalization
                 .privkey_forwarded = true;
+            self.persist_progress()?;
 
             // For the last maker: validate and set their privkey on taker's incoming swapcoin.
             // Derive the public key from the received private key and verify it matches
@@ -1999,22 +2661,14 @@ impl<B: BlockchainBackend> Taker<B> {
         }
     }
 
-    /// Generate a detailed swap report for audit trail (matches master's `generate_swap_report`).
+    /// Wallet coins spent and created since `initial_utxos` was taken.
     ///
-    /// Computes UTXO diffs, per-maker fee breakdown, contract txids, and funding txids.
-    /// Prints the report to console and saves it beside the active wallet file.
-    fn generate_swap_report(
-        &self,
+    /// Coins paid to an external destination (`paid_out`) count as swap outputs.
+    pub(crate) fn utxo_changes(
+        wallet: &Wallet<B>,
         initial_utxos: &[ListUnspentResultEntry],
-        start_time: Instant,
-        status: SwapStatus,
-        error_message: Option<String>,
-    ) -> Result<TakerReport, TakerError> {
-        let swap = self.swap_state()?;
-        let swap_duration = start_time.elapsed();
-
-        let wallet = self.read_wallet()?;
-
+        paid_out: &[(bitcoin::Address, Amount)],
+    ) -> UtxoChanges {
         // UTXO tracking: compute consumed inputs and new outputs
         let all_regular_utxo = wallet.list_descriptor_utxo_spend_info();
 
@@ -2057,13 +2711,7 @@ impl<B: BlockchainBackend> Taker<B> {
             })
             .collect();
 
-        let output_change_amounts: Vec<u64> = output_regular_utxos
-            .iter()
-            .map(|(utxo, _)| utxo.amount.to_sat())
-            .collect();
-
         let network = wallet.store.network;
-        let wallet_file_name = wallet.get_name().to_string();
 
         let output_swap_utxos: Vec<(u64, String)> = wallet
             .list_swept_incoming_swap_utxos()
@@ -2079,9 +2727,14 @@ impl<B: BlockchainBackend> Taker<B> {
             })
             .collect();
 
-        let output_swap_amounts: Vec<u64> = output_swap_utxos
-            .iter()
-            .map(|(amount, _)| *amount)
+        // Coins swept to an external destination count as swap outputs too.
+        let output_swap_utxos: Vec<(u64, String)> = output_swap_utxos
+            .into_iter()
+            .chain(
+                paid_out
+                    .iter()
+                    .map(|(address, amount)| (amount.to_sat(), address.to_string())),
+            )
             .collect();
 
         let output_change_utxos: Vec<(u64, String)> = output_regular_utxos
@@ -2097,6 +2750,46 @@ impl<B: BlockchainBackend> Taker<B> {
             })
             .collect();
 
+        UtxoChanges {
+            input_utxos,
+            output_change_utxos,
+            output_swap_utxos,
+        }
+    }
+
+    /// Generate a detailed swap report for audit trail (matches master's `generate_swap_report`).
+    ///
+    /// Computes UTXO diffs, per-maker fee breakdown, contract txids, and funding txids.
+    /// Prints the report to console and saves it beside the active wallet file.
+    fn generate_swap_report(
+        &self,
+        initial_utxos: &[ListUnspentResultEntry],
+        start_time: Instant,
+        status: SwapStatus,
+        error_message: Option<String>,
+    ) -> Result<TakerReport, TakerError> {
+        let swap = self.swap_state()?;
+        let swap_duration = start_time.elapsed();
+
+        let wallet = self.read_wallet()?;
+
+        let network = wallet.store.network;
+        let wallet_file_name = wallet.get_name().to_string();
+
+        let UtxoChanges {
+            input_utxos,
+            output_change_utxos,
+            output_swap_utxos,
+        } = Self::utxo_changes(&wallet, initial_utxos, &swap.paid_out);
+        let output_change_amounts: Vec<u64> = output_change_utxos
+            .iter()
+            .map(|(amount, _)| *amount)
+            .collect();
+        let output_swap_amounts: Vec<u64> = output_swap_utxos
+            .iter()
+            .map(|(amount, _)| *amount)
+            .collect();
+
         let output_utxos = [output_change_amounts.clone(), output_swap_amounts.clone()].concat();
         let total_input_amount: u64 = input_utxos.iter().sum();
         let total_output_amount: u64 = output_utxos.iter().sum();
@@ -2232,6 +2925,7 @@ impl<B: BlockchainBackend> Taker<B> {
             outgoing_contract_txid,
             end_timestamp: swap_end_ts,
             start_timestamp: swap_end_ts.saturating_sub(swap_duration.as_secs()),
+            routes: Vec::new(),
         };
 
         report.print();
@@ -2267,9 +2961,289 @@ impl<B: BlockchainBackend> Taker<B> {
     /// and wallet cleanup are handled by the `RecoveryLoop`.
     #[hotpath::measure]
     pub fn recover_active_swap(&mut self) -> Result<(), TakerError> {
+        self.recover(None)
+    }
+
+    /// Recover the ongoing swap after it failed. A route worker only recovers its own
+    /// route, so the contracts of sibling routes still in flight are left alone.
+    fn recover_failed_swap(&mut self) -> Result<(), TakerError> {
+        if self.is_route_worker() {
+            let swap_id = self.swap_state()?.id.clone();
+            self.recover(Some(&swap_id))
+        } else {
+            self.recover_active_swap()
+        }
+    }
+
+    /// Recover a single swap by ID, leaving the contracts of other swaps untouched.
+    ///
+    /// Works like [`Self::recover_active_swap`], but the background `RecoveryLoop` only
+    /// sweeps and reclaims contracts whose swapcoins belong to `swap_id`.
+    pub fn recover_swap(&mut self, swap_id: &str) -> Result<(), TakerError> {
+        self.assign_swapcoins_from_record(swap_id)?;
+        let known = self.ongoing_swap.as_ref().is_some_and(|s| s.id == swap_id) || {
+            let wallet = self.read_wallet()?;
+            let (incoming, outgoing) = wallet.find_unfinished_swapcoins();
+            incoming
+                .iter()
+                .any(|sc| sc.swap_id.as_deref() == Some(swap_id))
+                || outgoing
+                    .iter()
+                    .any(|sc| sc.swap_id.as_deref() == Some(swap_id))
+        };
+        if !known {
+            return Err(TakerError::General(format!(
+                "No unfinished swapcoins found for swap {swap_id}"
+            )));
+        }
+        self.recover(Some(swap_id))
+    }
+
+    /// List how each live contract would be reclaimed (optionally only those of one swap),
+    /// including the signed recovery transaction, without broadcasting anything.
+    pub fn recovery_plan(
+        &self,
+        swap_id: Option<&str>,
+    ) -> Result<Vec<ContractRecoveryPlan>, TakerError> {
+        if let Some(swap_id) = swap_id {
+            // Only in memory: a dry run leaves the wallet file untouched.
+            self.assign_swapcoins_from_record_unsaved(swap_id)?;
+        }
+        Ok(self
+            .read_wallet()?
+            .plan_contract_recovery(swap_id, RECOVERY_FEE_RATE)?)
+    }
+
+    /// Attribute persisted swapcoins that have no swap id to `swap_id` when its tracker
+    /// record lists their contracts, so that recovering a single swap finds them. Legacy
+    /// incoming swapcoins used to be saved without one, as were all swapcoins of older wallets.
+    fn assign_swapcoins_from_record(&self, swap_id: &str) -> Result<(), TakerError> {
+        if self.assign_swapcoins_from_record_unsaved(swap_id)? > 0 {
+            self.write_wallet()?.save_to_disk()?;
+        }
+        Ok(())
+    }
+
+    fn assign_swapcoins_from_record_unsaved(&self, swap_id: &str) -> Result<usize, TakerError> {
+        let contract_txids: Vec<bitcoin::Txid> =
+            match self.swap_tracker.lock().unwrap().get_record(swap_id) {
+                Some(record) => record
+                    .incoming_contract_txids
+                    .iter()
+                    .chain(&record.outgoing_contract_txids)
+                    .copied()
+                    .collect(),
+                None => return Ok(0),
+            };
+        let assigned = self
+            .write_wallet()?
+            .assign_swap_id(swap_id, &contract_txids);
+        if assigned > 0 {
+            log::info!("Assigned {assigned} swapcoins without a swap id to swap {swap_id}");
+        }
+        Ok(assigned)
+    }
+
+    /// Resume a swap that was interrupted during finalization, e.g. by a crash.
+    ///
+    /// Reconnects to the swap's makers by swap id, completes the private key handover and
+    /// sweeps the received coins, as [`Self::start_coinswap`] would have. The handover is
+    /// redone from the first maker, since each maker's key is forwarded to the next; makers
+    /// that completed their part before the interruption replay it. If the swap can't be
+    /// finished this way, it falls back to [`Self::recover_swap`]. Swaps interrupted before
+    /// finalization can only be recovered.
+    pub fn resume_swap(&mut self, swap_id: &str) -> Result<TakerReport, TakerError> {
+        if self
+            .ongoing_swap
+            .as_ref()
+            .is_some_and(|swap| swap.phase < SwapPhase::Completed)
+        {
+            return Err(TakerError::General(
+                "Cannot resume a swap while another one is in progress".to_string(),
+            ));
+        }
+        let record = self
+            .swap_tracker
+            .lock()
+            .unwrap()
+            .get_record(swap_id)
+            .cloned()
+            .ok_or_else(|| TakerError::General(format!("No swap with id '{}'", swap_id)))?;
+        if !record.phase.is_resumable() {
+            return Err(TakerError::General(format!(
+                "Swap {} is in phase {} and can't be resumed",
+                swap_id, record.phase
+            )));
+        }
+
+        match self.resume_from_record(&record) {
+            Ok(report) => Ok(report),
+            Err(e) => {
+                log::warn!("Falling back to recovery for swap {}", swap_id);
+                if let Err(re) = self.recover_swap(swap_id) {
+                    log::error!("Recovery failed: {:?}", re);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Resume the swaps a previous session left in a resumable phase, then run the startup
+    /// recovery [`Self::init`] deferred for them. Those that can't be resumed are marked as
+    /// failed and recovered.
+    ///
+    /// This connects to the swaps' makers, so it is run before a coinswap, a recovery and when
+    /// takerd starts rather than on every start. Does nothing unless `auto_recover` is set.
+    pub fn resume_interrupted_swaps(&mut self) {
+        if !self.config.auto_recover {
+            return;
+        }
+        let records: Vec<SwapRecord> = self
+            .swap_tracker
+            .lock()
+            .unwrap()
+            .resumable_swaps()
+            .into_iter()
+            .cloned()
+            .collect();
+        if records.is_empty() {
+            return;
+        }
+
+        for record in records {
+            match self.resume_from_record(&record) {
+                Ok(_) => log::info!("Resumed swap {} completed", record.swap_id),
+                Err(e) => log::warn!(
+                    "Could not resume swap {}, leaving it to recovery: {:?}",
+                    record.swap_id,
+                    e
+                ),
+            }
+        }
+        self.init_recover_wallet();
+    }
+
+    /// Rebuild a swap from its record and finish its private key handover. On failure, the
+    /// swap is marked as failed and dropped from memory, without starting recovery.
+    fn resume_from_record(&mut self, record: &SwapRecord) -> Result<TakerReport, TakerError> {
+        log::info!(
+            "Resuming swap {} from phase {}",
+            record.swap_id,
+            record.phase
+        );
+        let start_time = Instant::now();
+        let initial_utxos = self.read_wallet()?.list_all_utxo();
+
+        let result = self
+            .restore_swap_state(record)
+            .and_then(|()| self.finalize_with_retry())
+            .and_then(|()| self.complete_swap(&initial_utxos, start_time));
+
+        if let Err(e) = &result {
+            self.ongoing_swap = None;
+            let reason = format!("{:?}", e);
+            let failed_at = record.phase;
+            if let Err(e) =
+                self.swap_tracker
+                    .lock()
+                    .unwrap()
+                    .update_and_save(&record.swap_id, |r| {
+                        r.phase = SwapPhase::Failed;
+                        r.failed_at_phase = Some(failed_at);
+                        r.failure_reason = Some(reason);
+                    })
+            {
+                log::error!("Failed to persist swap failure: {:?}", e);
+            }
+        }
+        result
+    }
+
+    /// Rebuild the in-memory state of a swap from its tracker record and the swapcoins the
+    /// wallet holds for it, and make it the ongoing swap.
+    fn restore_swap_state(&mut self, record: &SwapRecord) -> Result<(), TakerError> {
+        let wallet = self.read_wallet()?;
+        let outgoing_swapcoins = record
+            .outgoing_contract_txids
+            .iter()
+            .map(|txid| {
+                wallet
+                    .find_outgoing_swapcoin(&txid.to_string())
+                    .cloned()
+                    .ok_or_else(|| {
+                        TakerError::General(format!("Outgoing swapcoin {} not in wallet", txid))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // Only the last hop pays the taker, and only its swapcoin is persisted.
+        let incoming = record
+            .incoming_contract_txids
+            .last()
+            .and_then(|txid| wallet.find_incoming_swapcoin(&txid.to_string()))
+            .cloned()
+            .ok_or_else(|| TakerError::General("Incoming swapcoin not in wallet".to_string()))?;
+        let watchonly_swapcoins = wallet.watchonly_swapcoins_for_swap(&record.swap_id);
+        drop(wallet);
+
+        let cached = self.offerbook.all_makers();
+        let makers = record
+            .makers
+            .iter()
+            .map(|progress| {
+                let address = MakerAddress::try_from(progress.address.clone()).map_err(|e| {
+                    TakerError::General(format!(
+                        "Invalid maker address '{}': {}",
+                        progress.address, e
+                    ))
+                })?;
+                let offer = cached
+                    .iter()
+                    .find(|m| m.address == address)
+                    .and_then(|m| m.offer.clone());
+                Ok(MakerConnection {
+                    address,
+                    protocol: record.protocol,
+                    tweakable_point: None,
+                    offer,
+                    negotiated_timelock: 0,
+                    exchange: progress.exchange.clone(),
+                    finalization: progress.finalization.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, TakerError>>()?;
+
+        self.ongoing_swap = Some(OngoingSwapState {
+            id: record.swap_id.clone(),
+            preimage: record.preimage,
+            params: SwapParams::new(
+                record.protocol,
+                Amount::from_sat(record.send_amount_sat),
+                record.maker_count,
+            ),
+            makers,
+            outgoing_swapcoins,
+            incoming_swapcoins: vec![incoming],
+            watchonly_swapcoins,
+            multisig_nonces: record.multisig_nonces.iter().map(|k| k.0).collect(),
+            hashlock_nonces: record.hashlock_nonces.iter().map(|k| k.0).collect(),
+            phase: record.phase,
+            ..OngoingSwapState::default()
+        });
+        Ok(())
+    }
+
+    /// Persist the ongoing swap's swapcoins if needed, mark the swap as failed and
+    /// hand its contracts to a background `RecoveryLoop`. With a `target`, only that
+    /// swap is recovered; otherwise the ongoing or first persisted swap is.
+    fn recover(&mut self, target: Option<&str>) -> Result<(), TakerError> {
         log::warn!("Starting swap recovery...");
 
-        let swap_id = if let Some(ref swap) = self.ongoing_swap {
+        let ongoing = self
+            .ongoing_swap
+            .as_ref()
+            .filter(|swap| target.is_none_or(|id| id == swap.id));
+        let recovering_ongoing = ongoing.is_some();
+        let swap_id = if let Some(swap) = ongoing {
             let id = swap.id.clone();
             let mut wallet = self.write_wallet()?;
             for outgoing in &swap.outgoing_swapcoins {
@@ -2280,6 +3254,9 @@ impl<B: BlockchainBackend> Taker<B> {
             }
             wallet.save_to_disk()?;
             id
+        } else if let Some(id) = target {
+            self.assign_swapcoins_from_record(id)?;
+            id.to_string()
         } else {
             // Cross-session recovery: get swap_id from persisted swapcoins
             let wallet = self.read_wallet()?;
@@ -2301,7 +3278,9 @@ impl<B: BlockchainBackend> Taker<B> {
                 record.phase = SwapPhase::Failed;
             })?;
 
-        self.ongoing_swap = None;
+        if recovering_ongoing || target.is_none() {
+            self.ongoing_swap = None;
+        }
 
         log::info!("Spawning recovery loop for swap {}", swap_id);
         let data_dir = self.config.data_dir.clone().unwrap_or_else(get_taker_dir);
@@ -2309,6 +3288,7 @@ impl<B: BlockchainBackend> Taker<B> {
             self.wallet.clone(),
             self.swap_tracker.clone(),
             data_dir,
+            target.map(str::to_string),
         ));
 
         Ok(())
@@ -2371,6 +3351,14 @@ impl<B: BlockchainBackend> Taker<B> {
 
     // ── CLI helper methods ──────────────────────────────────────────────
 
+    /// The offer sync service. Only route workers run without one, and they are never
+    /// handed out to callers.
+    fn offer_sync(&self) -> &OfferSyncHandle {
+        self.offer_sync_handle
+            .as_ref()
+            .expect("offer sync is only missing on route workers")
+    }
+
     /// Returns the current offerbook snapshot.
     pub fn fetch_offers(&self) -> Result<OfferBook, TakerError> {
         Ok(self.offerbook.snapshot())
@@ -2378,7 +3366,7 @@ impl<B: BlockchainBackend> Taker<B> {
 
     /// Triggers a manual offerbook sync and blocks until it completes.
     pub fn sync_offerbook_and_wait(&self) -> Result<(), TakerError> {
-        self.offer_sync_handle.sync_and_wait()
+        self.offer_sync().sync_and_wait()
     }
 
     /// Returns a clone-able client for triggering offer sync operations from
@@ -2386,7 +3374,7 @@ impl<B: BlockchainBackend> Taker<B> {
     /// `Taker` itself. Useful for callers that want to run a manual sync off
     /// the main thread while leaving the `Taker` free for concurrent reads.
     pub fn offer_sync_client(&self) -> OfferSyncClient {
-        self.offer_sync_handle.client()
+        self.offer_sync().client()
     }
 
     /// Fetches the offer from a single maker, verifies its fidelity proof, and
@@ -2396,7 +3384,7 @@ impl<B: BlockchainBackend> Taker<B> {
     pub fn poll_maker(&self, address: String) -> Result<MakerOfferCandidate, TakerError> {
         let parsed = MakerAddress::try_from(address)
             .map_err(|e| TakerError::General(format!("Invalid maker address: {e}")))?;
-        self.offer_sync_handle.poll_maker(parsed)
+        self.offer_sync().poll_maker(parsed)
     }
 
     /// Removes a maker from the offerbook by address. Returns `true` if an
@@ -2448,25 +3436,3 @@ impl<B: BlockchainBackend> Taker<B> {
         Self::from_wallet(config, wallet)
     }
 }
-
-/// Taker behavior for testing.
-#[cfg(feature = "integration-test")]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum TakerBehavior {
-    /// Normal behavior.
-    #[default]
-    Normal,
-    /// Close connection early (after maker selection).
-    CloseEarly,
-    /// Drop after funds/contracts are broadcast but before finalization.
-    /// Simulates a taker crash after funds are on-chain.
-    DropAfterFundsBroadcast,
-    /// Broadcast contract transactions after full setup, then close (malice scenario).
-    BroadcastContractAfterFullSetup,
-    /// Close connection after receiving AckSwapDetails (taproot taker abort).
-    CloseAtAckResponse,
-    /// Close connection when sending sender's contract data (taproot taker abort).
-    CloseAtSendersContract,
-    /// Close connection when receiving maker's contract data response (taproot taker abort).
-    CloseAtSendersContractFromMaker,
-}
@@ -30,6 +30,9 @@ const RECOVERY_LOOP_INTERVAL: Duration = Duration::from_secs(60);
 #[cfg(feature = "integration-test")]
 const RECOVERY_LOOP_INTERVAL: Duration = Duration::from_secs(10);
 
+/// Fee rate (sats/vbyte) used for hashlock sweeps and timelock recovery transactions.
+pub(crate) const RECOVERY_FEE_RATE: f64 = 2.0;
+
 /// Background thread that periodically retries wallet-level recovery
 /// (hashlock sweep + timelock recovery) until all contract UTXOs are resolved.
 ///
@@ -45,12 +48,14 @@ impl RecoveryLoop {
     /// Spawn the background recovery thread.
     ///
     /// The `swap_tracker` is used to update per-contract resolution outcomes
-    /// as contracts are resolved in the background.
+    /// as contracts are resolved in the background. If `swap_id` is given, only
+    /// that swap's contracts are recovered.
     #[hotpath::measure]
     pub(crate) fn start<B: BlockchainBackend>(
         wallet: Arc<RwLock<Wallet<B>>>,
         swap_tracker: Arc<Mutex<SwapTracker>>,
         data_dir: PathBuf,
+        swap_id: Option<String>,
     ) -> Self {
         let shutdown = Arc::new(AtomicBool::new(false));
         let complete = Arc::new(AtomicBool::new(false));
@@ -72,7 +77,10 @@ impl RecoveryLoop {
 
                     // Try hashlock sweep (incoming)
                     let incoming_result = if let Ok(mut w) = wallet.write() {
-                        match w.sweep_incoming_swapcoins(2.0) {
+                        match w.sweep_incoming_swapcoins_for_swap(
+                            RECOVERY_FEE_RATE,
+                            swap_id.as_deref(),
+                        ) {
                             Ok(ref swept) if !swept.is_empty() => {
                                 log::info!(
                                     "Recovery loop: swept {} incoming swapcoins",
@@ -92,7 +100,10 @@ impl RecoveryLoop {
 
                     // Try timelock recovery (outgoing)
                     let outgoing_result = if let Ok(mut w) = wallet.write() {
-                        match w.recover_timelocked_swapcoins(2.0) {
+                        match w.recover_timelocked_swapcoins_for_swap(
+                            RECOVERY_FEE_RATE,
+                            swap_id.as_deref(),
+                        ) {
                             Ok(ref recovered) if !recovered.is_empty() => {
                                 log::info!(
                                     "Recovery loop: recovered {} timelocked swapcoins",
@@ -124,8 +135,8 @@ impl RecoveryLoop {
                     // Check if all contract outpoints are resolved
                     let all_resolved = match wallet.read() {
                         Ok(w) => {
-                            let outgoing = w.outgoing_contract_outpoints();
-                            let incoming = w.incoming_contract_outpoints();
+                            let outgoing = w.outgoing_contract_outpoints(swap_id.as_deref());
+                            let incoming = w.incoming_contract_outpoints(swap_id.as_deref());
                             if outgoing.is_empty() && incoming.is_empty() {
                                 true
                             } else {
@@ -149,6 +160,7 @@ impl RecoveryLoop {
                             .map(|t| {
                                 t.incomplete_swaps()
                                     .iter()
+                                    .filter(|r| swap_id.as_ref().is_none_or(|id| *id == r.swap_id))
                                     .map(|r| r.swap_id.clone())
                                     .collect()
                             })
@@ -167,7 +179,11 @@ impl RecoveryLoop {
 
                         if let Ok(mut tracker) = swap_tracker.lock() {
                             // Emit recovery reports before marking as cleaned up
-                            for record in tracker.incomplete_swaps() {
+                            for record in tracker
+                                .incomplete_swaps()
+                                .into_iter()
+                                .filter(|r| swap_ids.contains(&r.swap_id))
+                            {
                                 let network = wallet
                                     .read()
                                     .map(|w| w.store.network.to_string())
@@ -411,11 +427,12 @@ impl BreachDetector {
                     };
 
                     for (outpoint, expected_contract_txid) in &current_sentinels {
-                        watch_service.watch_request(*outpoint);
+                        // The routes of a multi-route swap share the watch service, so query
+                        // it in a way that keeps their answers apart.
                         if let Some(WatcherEvent::UtxoSpent {
                             spending_tx: Some(ref tx),
                             ..
-                        }) = watch_service.wait_for_event()
+                        }) = watch_service.query_spend(*outpoint)
                         {
                             let actual_txid = tx.compute_txid();
                             if actual_txid == *expected_contract_txid {
@@ -17,6 +17,14 @@ pub struct TakerConfig {
     pub socks_port: u16,
     /// Authentication password for Tor interface
     pub tor_auth_password: String,
+    /// Exponent applied to fidelity bond values when picking makers (default: 1.0)
+    pub maker_bond_value_exponent: f64,
+    /// How strongly makers with high fees are avoided when picking makers (default: 0.0, off)
+    pub maker_fee_sensitivity: f64,
+    /// Default cap on the total maker fees of a swap, in sats (default: 0, no cap)
+    pub max_total_fee: u64,
+    /// Default cap on the total maker fees of a swap, as a percentage of the swap amount (default: 0.0, no cap)
+    pub max_fee_pct: f64,
 }
 
 impl Default for TakerConfig {
@@ -25,6 +33,10 @@ impl Default for TakerConfig {
             control_port: 9051,
             socks_port: 9050,
             tor_auth_password: "".to_string(),
+            maker_bond_value_exponent: 1.0,
+            maker_fee_sensitivity: 0.0,
+            max_total_fee: 0,
+            max_fee_pct: 0.0,
         }
     }
 }
@@ -69,6 +81,19 @@ impl TakerConfig {
                 config_map.get("tor_auth_password"),
                 default_config.tor_auth_password,
             ),
+            maker_bond_value_exponent: parse_field(
+                config_map.get("maker_bond_value_exponent"),
+                default_config.maker_bond_value_exponent,
+            ),
+            maker_fee_sensitivity: parse_field(
+                config_map.get("maker_fee_sensitivity"),
+                default_config.maker_fee_sensitivity,
+            ),
+            max_total_fee: parse_field(
+                config_map.get("max_total_fee"),
+                default_config.max_total_fee,
+            ),
+            max_fee_pct: parse_field(config_map.get("max_fee_pct"), default_config.max_fee_pct),
         })
     }
 
@@ -82,8 +107,22 @@ control_port = {}
 # Socks port for Tor proxy
 socks_port = {}
 # Authentication password for Tor control interface
-tor_auth_password = {}",
-            self.control_port, self.socks_port, self.tor_auth_password,
+tor_auth_password = {}
+# Makers are picked at random, weighted by fidelity bond value raised to this exponent
+maker_bond_value_exponent = {:?}
+# Each percent of a maker's fee divides its weight by e^maker_fee_sensitivity (0 = ignore fees)
+maker_fee_sensitivity = {:?}
+# Swaps whose maker fees would exceed this many sats are aborted before funding (0 = no cap)
+max_total_fee = {}
+# Swaps whose maker fees would exceed this percentage of the amount are aborted before funding (0 = no cap)
+max_fee_pct = {:?}",
+            self.control_port,
+            self.socks_port,
+            self.tor_auth_password,
+            self.maker_bond_value_exponent,
+            self.maker_fee_sensitivity,
+            self.max_total_fee,
+            self.max_fee_pct,
         );
 
         std::fs::create_dir_all(path.parent().expect("Path should NOT be root!"))?;
@@ -185,6 +224,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_swap_preferences_roundtrip() {
+        let config_path = PathBuf::from("swap_preferences_taker_config.toml");
+        let config = TakerConfig {
+            maker_bond_value_exponent: 1.5,
+            maker_fee_sensitivity: 0.25,
+            max_total_fee: 5_000,
+            max_fee_pct: 2.5,
+            ..TakerConfig::default()
+        };
+        config.write_to_file(&config_path).unwrap();
+        let loaded = TakerConfig::new(Some(&config_path)).unwrap();
+        remove_temp_config(&config_path);
+        assert_eq!(loaded, config);
+    }
+
     #[test]
     fn test_missing_file() {
         let config_path = get_taker_dir().join("taker.toml");
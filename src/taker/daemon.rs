@@ -0,0 +1,762 @@
+//! Long-running taker daemon, run by the `takerd` binary.
+//!
+//! The one-shot `taker` app opens the wallet, performs a single command and exits. The daemon
+//! instead keeps a [`Taker`] alive, so the offerbook stays synced by the offer sync service and
+//! leftover contracts are recovered as soon as they can be. On top of that it runs coinswaps
+//! from a persistent schedule. Swaps are scheduled through the local RPC (`takerd-cli`) or by
+//! the auto-swap policy, which swaps every new deposit above a threshold through a random
+//! number of makers after a random delay.
+
+use std::{
+    collections::HashSet,
+    fs, io,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering::Relaxed},
+        Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
+    thread,
+    time::Duration,
+};
+
+use bitcoin::{
+    secp256k1::rand::{rngs::OsRng, RngCore},
+    Amount, OutPoint,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    api::{load_swap_history, SwapHistoryEntry, SwapParams, Taker},
+    error::TakerError,
+    rpc::server::start_rpc_server,
+    swap_tracker::{now_secs, SwapTracker},
+};
+use crate::{
+    protocol::common_messages::ProtocolVersion,
+    utill::{
+        get_taker_dir, parse_field, parse_toml, remove_rpc_cookie, write_rpc_cookie,
+        HEART_BEAT_INTERVAL,
+    },
+    wallet::{BlockchainBackend, UTXOSpendInfo, Wallet},
+};
+
+/// Interval between two rounds of wallet sync, recovery, deposit detection and due swaps.
+#[cfg(not(feature = "integration-test"))]
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+#[cfg(feature = "integration-test")]
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Sats held back from an auto-swapped deposit to pay for the funding transaction.
+const DEPOSIT_FEE_RESERVE: u64 = 2_000;
+
+/// Name of the schedule file inside the taker data directory.
+const SCHEDULE_FILE: &str = "takerd_schedule.json";
+
+/// Name of the file listing the deposits already considered by the auto-swap policy.
+const DEPOSITS_FILE: &str = "takerd_deposits.json";
+
+/// Derivation path prefix of the wallet's external (receiving) keychain.
+const EXTERNAL_KEYCHAIN_PREFIX: &str = "m/0/";
+
+/// takerd configuration, stored as `takerd.toml` in the taker data directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TakerdConfig {
+    /// RPC listening port (default: 6203)
+    pub rpc_port: u16,
+    /// Swap incoming deposits automatically (default: false)
+    pub auto_swap: bool,
+    /// Smallest deposit that is auto-swapped, in sats (default: 100,000)
+    pub auto_swap_min_amount: u64,
+    /// Fewest makers an auto-swap is routed through (default: 2)
+    pub auto_swap_min_makers: usize,
+    /// Most makers an auto-swap is routed through (default: 3)
+    pub auto_swap_max_makers: usize,
+    /// Longest random delay before an auto-swap starts, in seconds (default: 3600)
+    pub auto_swap_max_delay_secs: u64,
+    /// Protocol used for auto-swaps (default: legacy)
+    pub auto_swap_protocol: ProtocolVersion,
+}
+
+impl Default for TakerdConfig {
+    fn default() -> Self {
+        Self {
+            rpc_port: 6203,
+            auto_swap: false,
+            auto_swap_min_amount: 100_000,
+            auto_swap_min_makers: 2,
+            auto_swap_max_makers: 3,
+            auto_swap_max_delay_secs: 3600,
+            auto_swap_protocol: ProtocolVersion::Legacy,
+        }
+    }
+}
+
+impl TakerdConfig {
+    /// Loads the config from `config_path`, creating a default one if the file is missing.
+    ///
+    /// Default config location: `~/.coinswap/taker/takerd.toml`.
+    pub fn new(config_path: Option<&Path>) -> io::Result<Self> {
+        let default_config_path = get_taker_dir().join("takerd.toml");
+        let config_path = config_path.unwrap_or(&default_config_path);
+        let default_config = Self::default();
+
+        if !config_path.exists() || fs::metadata(config_path)?.len() == 0 {
+            log::warn!(
+                "takerd config file not found, creating default config file at path: {}",
+                config_path.display()
+            );
+            default_config.write_to_file(config_path)?;
+        }
+
+        let config_map = parse_toml(config_path)?;
+
+        log::info!(
+            "Successfully loaded config file from : {}",
+            config_path.display()
+        );
+
+        let config = TakerdConfig {
+            rpc_port: parse_field(config_map.get("rpc_port"), default_config.rpc_port),
+            auto_swap: parse_field(config_map.get("auto_swap"), default_config.auto_swap),
+            auto_swap_min_amount: parse_field(
+                config_map.get("auto_swap_min_amount"),
+                default_config.auto_swap_min_amount,
+            ),
+            auto_swap_min_makers: parse_field(
+                config_map.get("auto_swap_min_makers"),
+                default_config.auto_swap_min_makers,
+            ),
+            auto_swap_max_makers: parse_field(
+                config_map.get("auto_swap_max_makers"),
+                default_config.auto_swap_max_makers,
+            ),
+            auto_swap_max_delay_secs: parse_field(
+                config_map.get("auto_swap_max_delay_secs"),
+                default_config.auto_swap_max_delay_secs,
+            ),
+            auto_swap_protocol: parse_field(
+                config_map.get("auto_swap_protocol"),
+                default_config.auto_swap_protocol,
+            ),
+        };
+
+        if config.auto_swap_min_makers < 2
+            || config.auto_swap_max_makers < config.auto_swap_min_makers
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "auto_swap_min_makers must be at least 2 and not above auto_swap_max_makers",
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// Serializes the config into TOML and writes it to `path`, creating the parent directory.
+    pub fn write_to_file(&self, path: &Path) -> io::Result<()> {
+        let toml_data = format!(
+            "# takerd Configuration File
+# RPC listening port for takerd-cli
+rpc_port = {}
+# Swap incoming deposits automatically
+auto_swap = {}
+# Smallest deposit that is auto-swapped, in sats
+auto_swap_min_amount = {}
+# Fewest makers an auto-swap is routed through
+auto_swap_min_makers = {}
+# Most makers an auto-swap is routed through
+auto_swap_max_makers = {}
+# Longest random delay before an auto-swap starts, in seconds
+auto_swap_max_delay_secs = {}
+# Protocol used for auto-swaps: legacy or taproot
+auto_swap_protocol = \"{}\"",
+            self.rpc_port,
+            self.auto_swap,
+            self.auto_swap_min_amount,
+            self.auto_swap_min_makers,
+            self.auto_swap_max_makers,
+            self.auto_swap_max_delay_secs,
+            self.auto_swap_protocol,
+        );
+
+        fs::create_dir_all(path.parent().expect("Path should NOT be root!"))?;
+        let mut file = fs::File::create(path)?;
+        file.write_all(toml_data.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// What caused a swap to be scheduled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduleOrigin {
+    /// Requested over RPC.
+    Rpc,
+    /// Auto-swap of the deposit at this outpoint.
+    Deposit(OutPoint),
+}
+
+/// Lifecycle of a scheduled swap.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduleState {
+    /// Waiting for its execution time.
+    Pending,
+    /// Being executed.
+    Running,
+    /// Completed as the swap with this ID.
+    Completed {
+        /// ID of the executed swap.
+        swap_id: String,
+    },
+    /// Could not be executed or the swap failed.
+    Failed {
+        /// Why the swap failed.
+        error: String,
+    },
+    /// Cancelled before it started.
+    Cancelled,
+}
+
+/// A coinswap the daemon will run at a given time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledSwap {
+    /// Schedule entry ID, unique within the data directory.
+    pub id: u64,
+    /// Amount to swap, in sats.
+    pub amount_sat: u64,
+    /// Number of makers to route through.
+    pub makers: usize,
+    /// Protocol to swap with.
+    pub protocol: ProtocolVersion,
+    /// Unix time after which the swap starts.
+    pub execute_at: u64,
+    /// Outpoints funding the swap. Empty when coins are selected automatically.
+    pub outpoints: Vec<OutPoint>,
+    /// What caused the swap to be scheduled.
+    pub origin: ScheduleOrigin,
+    /// Current state.
+    pub state: ScheduleState,
+}
+
+/// The persisted schedule.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SwapSchedule {
+    next_id: u64,
+    swaps: Vec<ScheduledSwap>,
+}
+
+impl SwapSchedule {
+    /// Loads the schedule, starting empty if the file does not exist yet.
+    ///
+    /// Entries left `Running` by a crash are marked failed; their funds are handled by the
+    /// regular swap recovery.
+    fn load(path: &Path) -> io::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let mut schedule: Self = serde_json::from_slice(&fs::read(path)?)?;
+        for swap in schedule.swaps.iter_mut() {
+            if swap.state == ScheduleState::Running {
+                swap.state = ScheduleState::Failed {
+                    error: "Interrupted by a takerd restart".to_string(),
+                };
+            }
+        }
+        Ok(schedule)
+    }
+
+    /// Writes the schedule atomically through a temporary file.
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(tmp_path, path)
+    }
+
+    fn add(
+        &mut self,
+        amount_sat: u64,
+        makers: usize,
+        protocol: ProtocolVersion,
+        execute_at: u64,
+        outpoints: Vec<OutPoint>,
+        origin: ScheduleOrigin,
+    ) -> ScheduledSwap {
+        self.next_id += 1;
+        let swap = ScheduledSwap {
+            id: self.next_id,
+            amount_sat,
+            makers,
+            protocol,
+            execute_at,
+            outpoints,
+            origin,
+            state: ScheduleState::Pending,
+        };
+        self.swaps.push(swap.clone());
+        swap
+    }
+
+    /// The pending swap that has been due the longest, if any.
+    fn next_due(&self, now: u64) -> Option<&ScheduledSwap> {
+        self.swaps
+            .iter()
+            .filter(|s| s.state == ScheduleState::Pending && s.execute_at <= now)
+            .min_by_key(|s| (s.execute_at, s.id))
+    }
+
+    fn set_state(&mut self, id: u64, state: ScheduleState) {
+        if let Some(swap) = self.swaps.iter_mut().find(|s| s.id == id) {
+            swap.state = state;
+        }
+    }
+}
+
+/// A taker kept running to execute scheduled coinswaps.
+///
+/// The wallet and the swap tracker are shared with the [`Taker`], so RPC queries are answered
+/// while a swap holds the taker.
+pub struct TakerDaemon<B: BlockchainBackend> {
+    taker: Mutex<Taker<B>>,
+    wallet: Arc<RwLock<Wallet<B>>>,
+    swap_tracker: Arc<Mutex<SwapTracker>>,
+    data_dir: PathBuf,
+    wallet_file_name: String,
+    config: TakerdConfig,
+    schedule: Mutex<SwapSchedule>,
+    /// Deposits already considered by the auto-swap policy.
+    seen_deposits: Mutex<HashSet<OutPoint>>,
+    shutdown: AtomicBool,
+}
+
+impl<B: BlockchainBackend> TakerDaemon<B> {
+    /// Wraps an initialized taker.
+    ///
+    /// Deposits already in the wallet when the daemon is first started are not auto-swapped.
+    /// The deposits considered so far are persisted, so the ones arriving while the daemon is
+    /// down are swapped after the next start.
+    pub fn new(taker: Taker<B>, config: TakerdConfig) -> Result<Self, TakerError> {
+        let data_dir = taker.config.data_dir.clone().unwrap_or_else(get_taker_dir);
+        let wallet = taker.wallet.clone();
+        let swap_tracker = taker.swap_tracker.clone();
+        let schedule = SwapSchedule::load(&data_dir.join(SCHEDULE_FILE))?;
+
+        let (wallet_file_name, deposits) = {
+            let wallet = wallet
+                .read()
+                .map_err(|_| TakerError::General("Failed to lock wallet".to_string()))?;
+            let deposits = list_deposits(&wallet, config.auto_swap_min_amount);
+            (wallet.get_name().to_string(), deposits)
+        };
+
+        let deposits_path = data_dir.join(DEPOSITS_FILE);
+        let seen_deposits = if deposits_path.exists() {
+            load_seen_deposits(&deposits_path, &schedule)?
+        } else {
+            let seen = deposits.into_iter().map(|(op, _)| op).collect();
+            save_seen_deposits(&deposits_path, &seen)?;
+            seen
+        };
+
+        Ok(Self {
+            taker: Mutex::new(taker),
+            wallet,
+            swap_tracker,
+            data_dir,
+            wallet_file_name,
+            config,
+            schedule: Mutex::new(schedule),
+            seen_deposits: Mutex::new(seen_deposits),
+            shutdown: AtomicBool::new(false),
+        })
+    }
+
+    /// The daemon configuration.
+    pub fn config(&self) -> &TakerdConfig {
+        &self.config
+    }
+
+    /// The taker data directory.
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    pub(crate) fn read_wallet(&self) -> Result<RwLockReadGuard<'_, Wallet<B>>, TakerError> {
+        self.wallet
+            .read()
+            .map_err(|_| TakerError::General("Failed to lock wallet".to_string()))
+    }
+
+    pub(crate) fn write_wallet(&self) -> Result<RwLockWriteGuard<'_, Wallet<B>>, TakerError> {
+        self.wallet
+            .write()
+            .map_err(|_| TakerError::General("Failed to lock wallet".to_string()))
+    }
+
+    /// Request the daemon to stop once the swap in progress, if any, has finished.
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Relaxed);
+    }
+
+    /// Whether a shutdown was requested.
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.load(Relaxed)
+    }
+
+    /// All known swaps, oldest first. See [`Taker::swap_history`].
+    pub fn swap_history(&self) -> Result<Vec<SwapHistoryEntry>, TakerError> {
+        load_swap_history(&self.swap_tracker, &self.data_dir, &self.wallet_file_name)
+    }
+
+    /// Schedule a coinswap to start `delay_secs` from now.
+    pub fn schedule_swap(
+        &self,
+        amount: Amount,
+        makers: usize,
+        protocol: ProtocolVersion,
+        delay_secs: u64,
+    ) -> Result<ScheduledSwap, TakerError> {
+        if makers < 2 {
+            return Err(TakerError::General(
+                "Swapping with less than 2 makers is not allowed".to_string(),
+            ));
+        }
+        self.add_to_schedule(
+            amount.to_sat(),
+            makers,
+            protocol,
+            delay_secs,
+            Vec::new(),
+            ScheduleOrigin::Rpc,
+        )
+    }
+
+    /// All scheduled swaps, including finished ones.
+    pub fn scheduled_swaps(&self) -> Vec<ScheduledSwap> {
+        self.schedule.lock().unwrap().swaps.clone()
+    }
+
+    /// Cancel a scheduled swap that has not started yet.
+    pub fn cancel_scheduled_swap(&self, id: u64) -> Result<ScheduledSwap, TakerError> {
+        let mut schedule = self.schedule.lock().unwrap();
+        let swap = schedule
+            .swaps
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| TakerError::General(format!("No scheduled swap with id {id}")))?;
+        if swap.state != ScheduleState::Pending {
+            return Err(TakerError::General(format!(
+                "Scheduled swap {id} is no longer pending"
+            )));
+        }
+        swap.state = ScheduleState::Cancelled;
+        let swap = swap.clone();
+        schedule.save(&self.data_dir.join(SCHEDULE_FILE))?;
+        Ok(swap)
+    }
+
+    fn add_to_schedule(
+        &self,
+        amount_sat: u64,
+        makers: usize,
+        protocol: ProtocolVersion,
+        delay_secs: u64,
+        outpoints: Vec<OutPoint>,
+        origin: ScheduleOrigin,
+    ) -> Result<ScheduledSwap, TakerError> {
+        let mut schedule = self.schedule.lock().unwrap();
+        let swap = schedule.add(
+            amount_sat,
+            makers,
+            protocol,
+            now_secs() + delay_secs,
+            outpoints,
+            origin,
+        );
+        schedule.save(&self.data_dir.join(SCHEDULE_FILE))?;
+        log::info!(
+            "Scheduled swap {}: {} sats through {} makers in {}s",
+            swap.id,
+            swap.amount_sat,
+            swap.makers,
+            delay_secs
+        );
+        Ok(swap)
+    }
+
+    /// Schedule an auto-swap for every deposit not seen before.
+    fn schedule_new_deposits(&self) -> Result<(), TakerError> {
+        let deposits = list_deposits(&*self.read_wallet()?, self.config.auto_swap_min_amount);
+        let mut seen = self.seen_deposits.lock().unwrap();
+        let seen_before = seen.clone();
+        // Spent deposits never come back, so only the ones still in the wallet are kept.
+        seen.retain(|op| deposits.iter().any(|(deposit, _)| deposit == op));
+        for (outpoint, amount) in deposits {
+            if !seen.insert(outpoint) {
+                continue;
+            }
+            let amount_sat = amount.to_sat().saturating_sub(DEPOSIT_FEE_RESERVE);
+            let min = self.config.auto_swap_min_makers as u64;
+            let max = self.config.auto_swap_max_makers as u64;
+            let makers = (min + OsRng.next_u64() % (max - min + 1)) as usize;
+            let delay_secs = OsRng.next_u64() % (self.config.auto_swap_max_delay_secs + 1);
+            log::info!("New deposit {outpoint} of {amount}, scheduling an auto-swap");
+            self.add_to_schedule(
+                amount_sat,
+                makers,
+                self.config.auto_swap_protocol,
+                delay_secs,
+                vec![outpoint],
+                ScheduleOrigin::Deposit(outpoint),
+            )?;
+        }
+        if *seen != seen_before {
+            save_seen_deposits(&self.data_dir.join(DEPOSITS_FILE), &seen)?;
+        }
+        Ok(())
+    }
+
+    /// Run the scheduled swaps that are due, one after the other.
+    fn run_due_swaps(&self) -> Result<(), TakerError> {
+        while !self.is_shutting_down() {
+            let swap = {
+                let mut schedule = self.schedule.lock().unwrap();
+                let Some(swap) = schedule.next_due(now_secs()).cloned() else {
+                    return Ok(());
+                };
+                schedule.set_state(swap.id, ScheduleState::Running);
+                schedule.save(&self.data_dir.join(SCHEDULE_FILE))?;
+                swap
+            };
+
+            log::info!("Starting scheduled swap {}", swap.id);
+            let state = match self.execute(&swap) {
+                Ok(swap_id) => {
+                    log::info!("Scheduled swap {} completed as {}", swap.id, swap_id);
+                    ScheduleState::Completed { swap_id }
+                }
+                Err(e) => {
+                    log::error!("Scheduled swap {} failed: {:?}", swap.id, e);
+                    ScheduleState::Failed {
+                        error: format!("{e:?}"),
+                    }
+                }
+            };
+
+            let mut schedule = self.schedule.lock().unwrap();
+            schedule.set_state(swap.id, state);
+            schedule.save(&self.data_dir.join(SCHEDULE_FILE))?;
+        }
+        Ok(())
+    }
+
+    fn execute(&self, swap: &ScheduledSwap) -> Result<String, TakerError> {
+        let mut params = SwapParams::new(
+            swap.protocol,
+            Amount::from_sat(swap.amount_sat),
+            swap.makers,
+        );
+        if !swap.outpoints.is_empty() {
+            params = params.with_utxos(swap.outpoints.clone());
+        }
+
+        let mut taker = self.taker.lock().unwrap();
+        let summary = taker.prepare_coinswap(params)?;
+        taker.start_coinswap(&summary.swap_id)?;
+        Ok(summary.swap_id)
+    }
+
+    /// One round of the main loop.
+    fn tick(&self) -> Result<(), TakerError> {
+        self.write_wallet()?.sync_and_save()?;
+        self.taker.lock().unwrap().recover_unresolved_contracts();
+        if self.config.auto_swap {
+            self.schedule_new_deposits()?;
+        }
+        self.run_due_swaps()
+    }
+}
+
+/// Confirmed coins received on the external keychain, worth at least `min_amount` sats.
+fn list_deposits<B: BlockchainBackend>(
+    wallet: &Wallet<B>,
+    min_amount: u64,
+) -> Vec<(OutPoint, Amount)> {
+    wallet
+        .list_descriptor_utxo_spend_info()
+        .into_iter()
+        .filter(|(utxo, spend_info)| {
+            matches!(spend_info, UTXOSpendInfo::SeedCoin { path, .. }
+                if path.starts_with(EXTERNAL_KEYCHAIN_PREFIX))
+                && utxo.confirmations > 0
+                && utxo.amount.to_sat() >= min_amount
+        })
+        .map(|(utxo, _)| (OutPoint::new(utxo.txid, utxo.vout), utxo.amount))
+        .collect()
+}
+
+/// Loads the deposits already considered by the auto-swap policy.
+///
+/// Deposits with an auto-swap in the schedule count as seen too, in case the daemon stopped
+/// between saving the schedule and the deposits file.
+fn load_seen_deposits(path: &Path, schedule: &SwapSchedule) -> io::Result<HashSet<OutPoint>> {
+    let mut seen: HashSet<OutPoint> = serde_json::from_slice(&fs::read(path)?)?;
+    seen.extend(schedule.swaps.iter().filter_map(|s| match s.origin {
+        ScheduleOrigin::Deposit(outpoint) => Some(outpoint),
+        ScheduleOrigin::Rpc => None,
+    }));
+    Ok(seen)
+}
+
+/// Writes the seen deposits atomically through a temporary file.
+fn save_seen_deposits(path: &Path, seen: &HashSet<OutPoint>) -> io::Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_vec_pretty(seen)?)?;
+    fs::rename(tmp_path, path)
+}
+
+/// Run the daemon until a shutdown is requested over RPC.
+///
+/// Serves the RPC on `127.0.0.1:<rpc_port>`, authenticated with a token written to the
+/// `.cookie` file in the data directory, and runs the main loop every poll interval.
+pub fn start_takerd<B: BlockchainBackend + 'static>(
+    daemon: Arc<TakerDaemon<B>>,
+) -> Result<(), TakerError> {
+    daemon.taker.lock().unwrap().resume_interrupted_swaps();
+    let rpc_token = write_rpc_cookie(&daemon.data_dir)?;
+
+    let rpc_daemon = daemon.clone();
+    let rpc_handle = thread::Builder::new()
+        .name("takerd rpc".to_string())
+        .spawn(move || {
+            if let Err(e) = start_rpc_server(rpc_daemon.clone(), &rpc_token) {
+                log::error!("takerd RPC server failed: {e:?}");
+                rpc_daemon.shutdown();
+            }
+        })?;
+
+    log::info!("takerd started");
+    let mut last_tick = None;
+    while !daemon.is_shutting_down() {
+        if last_tick.is_none_or(|t: std::time::Instant| t.elapsed() >= POLL_INTERVAL) {
+            if let Err(e) = daemon.tick() {
+                log::error!("takerd round failed: {e:?}");
+            }
+            last_tick = Some(std::time::Instant::now());
+        }
+        thread::sleep(HEART_BEAT_INTERVAL);
+    }
+
+    log::info!("Shutting down takerd");
+    let _ = rpc_handle.join();
+    remove_rpc_cookie(&daemon.data_dir);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::{hashes::Hash, Txid};
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("takerd-{name}-{}", OsRng.next_u64()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_takerd_config_roundtrip() {
+        let dir = temp_dir("config");
+        let path = dir.join("takerd.toml");
+
+        let default_config = TakerdConfig::new(Some(&path)).unwrap();
+        assert_eq!(default_config, TakerdConfig::default());
+
+        let config = TakerdConfig {
+            auto_swap: true,
+            auto_swap_min_amount: 250_000,
+            auto_swap_protocol: ProtocolVersion::Taproot,
+            ..TakerdConfig::default()
+        };
+        config.write_to_file(&path).unwrap();
+        assert_eq!(TakerdConfig::new(Some(&path)).unwrap(), config);
+
+        fs::write(&path, "auto_swap_min_makers = 1").unwrap();
+        assert!(TakerdConfig::new(Some(&path)).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_schedule_due_order_and_restart() {
+        let dir = temp_dir("schedule");
+        let path = dir.join(SCHEDULE_FILE);
+
+        let mut schedule = SwapSchedule::default();
+        let late = schedule.add(
+            50_000,
+            2,
+            ProtocolVersion::Legacy,
+            200,
+            vec![],
+            ScheduleOrigin::Rpc,
+        );
+        let early = schedule.add(
+            60_000,
+            3,
+            ProtocolVersion::Taproot,
+            100,
+            vec![],
+            ScheduleOrigin::Rpc,
+        );
+        assert_ne!(late.id, early.id);
+
+        assert!(schedule.next_due(99).is_none());
+        assert_eq!(schedule.next_due(300).unwrap().id, early.id);
+
+        schedule.set_state(early.id, ScheduleState::Running);
+        assert_eq!(schedule.next_due(300).unwrap().id, late.id);
+        schedule.save(&path).unwrap();
+
+        // A swap left running by a crash is not retried after a restart.
+        let reloaded = SwapSchedule::load(&path).unwrap();
+        assert!(matches!(
+            reloaded
+                .swaps
+                .iter()
+                .find(|s| s.id == early.id)
+                .unwrap()
+                .state,
+            ScheduleState::Failed { .. }
+        ));
+        assert_eq!(reloaded.next_id, schedule.next_id);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_seen_deposits_roundtrip() {
+        let dir = temp_dir("deposits");
+        let path = dir.join(DEPOSITS_FILE);
+
+        let saved = OutPoint::new(Txid::from_byte_array([1; 32]), 0);
+        let scheduled = OutPoint::new(Txid::from_byte_array([2; 32]), 1);
+        save_seen_deposits(&path, &HashSet::from([saved])).unwrap();
+
+        // A deposit scheduled before the deposits file was saved is still seen after a restart.
+        let mut schedule = SwapSchedule::default();
+        schedule.add(
+            100_000,
+            2,
+            ProtocolVersion::Legacy,
+            0,
+            vec![scheduled],
+            ScheduleOrigin::Deposit(scheduled),
+        );
+        let seen = load_seen_deposits(&path, &schedule).unwrap();
+        assert_eq!(seen, HashSet::from([saved, scheduled]));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
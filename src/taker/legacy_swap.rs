@@ -15,7 +15,7 @@ use crate::{
             RespContractSigsForRecvrAndSender, SenderContractTxInfo,
         },
     },
-    utill::{generate_keypair, generate_maker_keys, read_message, send_message, MIN_FEE_RATE},
+    utill::{generate_keypair, generate_maker_keys, MIN_FEE_RATE},
     wallet::{
         swapcoin::{IncomingSwapCoin, OutgoingSwapCoin, WatchOnlySwapCoin},
         BlockchainBackend, Wallet,
@@ -28,7 +28,10 @@ use bitcoin::{
     Amount, Network, OutPoint, PublicKey, ScriptBuf, Transaction, Txid,
 };
 
-use super::{api::Taker, error::TakerError};
+use super::{
+    api::{Taker, FUNDING_FEE_RATE},
+    error::TakerError,
+};
 
 /// Delay to allow the Maker to broadcast its funding transactions before we poll.
 const MAKER_BROADCAST_DELAY: Duration = Duration::from_secs(2);
@@ -46,6 +49,7 @@ impl<B: BlockchainBackend> Taker<B> {
         swap_id: &str,
         network: Network,
         manually_selected_outpoints: Option<Vec<OutPoint>>,
+        excluded_outpoints: Option<Vec<OutPoint>>,
     ) -> Result<Vec<OutgoingSwapCoin>, TakerError> {
         let secp = Secp256k1::new();
         let mut swapcoins = Vec::new();
@@ -76,9 +80,9 @@ impl<B: BlockchainBackend> Taker<B> {
             let funding_result = wallet.create_funding_txes(
                 send_amount,
                 &[coinswap_address],
-                MIN_FEE_RATE,
+                FUNDING_FEE_RATE,
                 manually_selected_outpoints.clone(),
-                None,
+                excluded_outpoints.clone(),
             )?;
 
             for (funding_tx, &output_pos) in funding_result
@@ -748,6 +752,7 @@ impl<B: BlockchainBackend> Taker<B> {
                     *hashlock_privkey,
                     info.funding_amount,
                 );
+                incoming.swap_id = Some(self.swap_state()?.id.clone());
                 incoming.set_preimage(self.swap_state()?.preimage);
                 self.swap_state_mut()?.incoming_swapcoins.push(incoming);
             }
@@ -862,10 +867,9 @@ impl<B: BlockchainBackend> Taker<B> {
             locktime,
         };
 
-        send_message(stream, &TakerToMakerMessage::ReqContractSigsForSender(req))?;
+        self.send_to_maker(stream, TakerToMakerMessage::ReqContractSigsForSender(req))?;
 
-        let msg_bytes = read_message(stream)?;
-        let msg: MakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
+        let msg = self.read_from_maker(stream)?;
 
         match msg {
             MakerToTakerMessage::RespContractSigsForSender(resp) => {
@@ -969,10 +973,9 @@ impl<B: BlockchainBackend> Taker<B> {
             contract_feerate: MIN_FEE_RATE,
         };
 
-        send_message(stream, &TakerToMakerMessage::ProofOfFunding(pof))?;
+        self.send_to_maker(stream, TakerToMakerMessage::ProofOfFunding(pof))?;
 
-        let msg_bytes = read_message(stream)?;
-        let msg: MakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
+        let msg = self.read_from_maker(stream)?;
 
         match msg {
             MakerToTakerMessage::ReqContractSigsAsRecvrAndSender(req) => {
@@ -1020,9 +1023,9 @@ impl<B: BlockchainBackend> Taker<B> {
             senders_sigs,
         };
 
-        send_message(
+        self.send_to_maker(
             stream,
-            &TakerToMakerMessage::RespContractSigsForRecvrAndSender(resp),
+            TakerToMakerMessage::RespContractSigsForRecvrAndSender(resp),
         )?;
 
         log::info!(
@@ -1062,10 +1065,9 @@ impl<B: BlockchainBackend> Taker<B> {
             locktime,
         };
 
-        send_message(stream, &TakerToMakerMessage::ReqContractSigsForSender(req))?;
+        self.send_to_maker(stream, TakerToMakerMessage::ReqContractSigsForSender(req))?;
 
-        let msg_bytes = read_message(stream)?;
-        let msg: MakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
+        let msg = self.read_from_maker(stream)?;
 
         match msg {
             MakerToTakerMessage::RespContractSigsForSender(resp) => {
@@ -1105,10 +1107,9 @@ impl<B: BlockchainBackend> Taker<B> {
             txs,
         };
 
-        send_message(stream, &TakerToMakerMessage::ReqContractSigsForRecvr(req))?;
+        self.send_to_maker(stream, TakerToMakerMessage::ReqContractSigsForRecvr(req))?;
 
-        let msg_bytes = read_message(stream)?;
-        let msg: MakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
+        let msg = self.read_from_maker(stream)?;
 
         match msg {
             MakerToTakerMessage::RespContractSigsForRecvr(resp) => {
@@ -7,18 +7,22 @@
 mod config;
 pub mod error;
 pub mod offers;
+pub mod quote;
 
 pub mod api;
 mod background_services;
+pub mod daemon;
 mod legacy_swap;
 mod legacy_verification;
+mod multi_route;
+pub mod rpc;
+pub mod selection;
 pub mod swap_tracker;
 mod taproot_swap;
 mod taproot_verification;
 
 pub use config::TakerConfig;
 
-#[cfg(feature = "integration-test")]
-pub use api::TakerBehavior;
-pub use api::{MakerFeeInfo, SwapParams, SwapSummary, Taker, TakerInitConfig};
+pub use api::{MakerFeeInfo, SwapHistoryEntry, SwapParams, SwapSummary, Taker, TakerInitConfig};
 pub use offers::{format_state, MakerOfferCandidate, MakerProtocol, MakerState, OfferBook};
+pub use quote::SwapQuote;
@@ -0,0 +1,583 @@
+//! Multi-route swaps.
+//!
+//! A single route is limited by the smallest `max_size` among its makers and moves one large,
+//! linkable amount. A multi-route swap splits `send_amount` at random into several routes, each
+//! a complete coinswap with its own preimage, makers and funding UTXOs, and runs them
+//! concurrently. No maker takes part in more than one route, and a route that fails is
+//! recovered on its own while the others complete.
+
+use std::{thread, time::Instant};
+
+use bitcoin::{
+    hashes::{hash160::Hash as Hash160, Hash},
+    hex::DisplayHex,
+    secp256k1::rand::RngCore,
+    Amount, OutPoint,
+};
+
+use super::{
+    api::{SwapParams, SwapSummary, Taker, UtxoChanges, FUNDING_FEE_RATE},
+    error::TakerError,
+    offers::MakerAddress,
+    selection::unit_f64,
+};
+use crate::wallet::{BlockchainBackend, SwapStatus, TakerReport};
+
+/// Margin multiplier on the funding fee rate when setting coins aside for a route. Coin
+/// selection only estimates the funding transaction's size, so the coins of a route must
+/// still cover its funding if the transaction turns out larger.
+const ROUTE_FUNDING_FEE_MARGIN: f64 = 1.5;
+
+/// The routes of a prepared multi-route swap, waiting to be started.
+pub(crate) struct PreparedRoutes<B: BlockchainBackend> {
+    /// ID the combined swap is started with. Never sent to any maker.
+    pub(crate) group_id: String,
+    /// One taker per route, each holding its prepared swap.
+    workers: Vec<Taker<B>>,
+}
+
+/// Split `total` into `count` random amounts that add up to `total`.
+///
+/// Route weights are drawn uniformly from `[0.5, 1.5)`, so no route is more than about three
+/// times the size of another.
+pub(crate) fn split_amount<R: RngCore>(total: Amount, count: usize, rng: &mut R) -> Vec<Amount> {
+    let weights: Vec<f64> = (0..count).map(|_| 0.5 + unit_f64(rng)).collect();
+    let weight_sum: f64 = weights.iter().sum();
+
+    let mut remaining = total.to_sat();
+    let mut amounts = Vec::with_capacity(count);
+    for weight in weights.iter().take(count.saturating_sub(1)) {
+        let sats = ((total.to_sat() as f64 * weight / weight_sum) as u64).min(remaining);
+        amounts.push(Amount::from_sat(sats));
+        remaining -= sats;
+    }
+    // The last route takes what rounding left over.
+    amounts.push(Amount::from_sat(remaining));
+    amounts
+}
+
+/// Fold the reports of the completed routes into one report under `swap_id`.
+///
+/// Makers and maker fees come from the route reports. Received amounts and the fee paid come
+/// from `changes`, taken over the whole swap, since concurrent routes see each other's wallet
+/// changes. The status is `Success` only if no route failed.
+fn combine_route_reports(
+    swap_id: String,
+    route_ids: Vec<String>,
+    send_amount: Amount,
+    completed: &[TakerReport],
+    failures: &[String],
+    changes: UtxoChanges,
+) -> TakerReport {
+    let output_change_amounts: Vec<u64> = changes
+        .output_change_utxos
+        .iter()
+        .map(|(amount, _)| *amount)
+        .collect();
+    let output_swap_amounts: Vec<u64> = changes
+        .output_swap_utxos
+        .iter()
+        .map(|(amount, _)| *amount)
+        .collect();
+    let total_input: u64 = changes.input_utxos.iter().sum();
+    let total_output: u64 =
+        output_change_amounts.iter().sum::<u64>() + output_swap_amounts.iter().sum::<u64>();
+    let fee_paid = total_input.saturating_sub(total_output);
+    let total_maker_fees: u64 = completed.iter().map(|r| r.total_maker_fees).sum();
+
+    let maker_addresses: Vec<String> = completed
+        .iter()
+        .flat_map(|r| r.maker_addresses.iter().cloned())
+        .collect();
+
+    TakerReport {
+        swap_id,
+        status: if failures.is_empty() {
+            SwapStatus::Success
+        } else {
+            SwapStatus::Failed
+        },
+        network: completed
+            .first()
+            .map(|r| r.network.clone())
+            .unwrap_or_default(),
+        swap_duration_seconds: 0.0,
+        start_timestamp: completed
+            .iter()
+            .map(|r| r.start_timestamp)
+            .min()
+            .unwrap_or_default(),
+        end_timestamp: completed
+            .iter()
+            .map(|r| r.end_timestamp)
+            .max()
+            .unwrap_or_default(),
+        error_message: (!failures.is_empty()).then(|| failures.join("; ")),
+        outgoing_amount: send_amount.to_sat(),
+        incoming_amount: output_swap_amounts.iter().sum(),
+        fee_paid,
+        mining_fee: fee_paid.saturating_sub(total_maker_fees),
+        fee_percentage: fee_paid as f64 / send_amount.to_sat().max(1) as f64 * 100.0,
+        total_maker_fees,
+        outgoing_contract_txid: None,
+        incoming_contract_txid: None,
+        funding_txids: completed.iter().map(|r| r.funding_txids.concat()).collect(),
+        makers_count: maker_addresses.len(),
+        maker_addresses,
+        maker_fee_info: completed
+            .iter()
+            .flat_map(|r| r.maker_fee_info.iter().cloned())
+            .collect(),
+        input_utxos: changes.input_utxos,
+        output_change_amounts,
+        output_swap_amounts,
+        output_change_utxos: changes.output_change_utxos,
+        output_swap_utxos: changes.output_swap_utxos,
+        routes: route_ids,
+    }
+}
+
+impl<B: BlockchainBackend> Taker<B> {
+    /// Prepare a multi-route swap: split the amount, give every route its own makers and
+    /// funding UTXOs, and prepare each route on a route worker.
+    ///
+    /// If any route cannot be prepared, the routes prepared so far are dropped as well.
+    pub(crate) fn prepare_routes(&mut self, params: SwapParams) -> Result<SwapSummary, TakerError> {
+        let route_count = params.route_count;
+        let amounts = split_amount(
+            params.send_amount,
+            route_count,
+            &mut self.maker_selection.rng(),
+        );
+        if amounts.contains(&Amount::ZERO) {
+            return Err(TakerError::General(format!(
+                "Swap amount {} is too small to split into {} routes",
+                params.send_amount, route_count
+            )));
+        }
+        log::info!(
+            "Splitting {} into {} routes: {}",
+            params.send_amount,
+            route_count,
+            amounts
+                .iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let mut route_params: Vec<SwapParams> = amounts
+            .iter()
+            .map(|amount| {
+                let mut route = params.clone();
+                route.send_amount = *amount;
+                route.route_count = 1;
+                route.max_total_fee = params.max_total_fee.map(|fee| {
+                    Amount::from_sat(
+                        (fee.to_sat() as u128 * amount.to_sat() as u128
+                            / params.send_amount.to_sat() as u128) as u64,
+                    )
+                });
+                route
+            })
+            .collect();
+
+        self.assign_route_makers(&params, &mut route_params)?;
+        let route_outpoints = self.assign_route_utxos(&params, &amounts)?;
+
+        let mut workers: Vec<Taker<B>> = Vec::with_capacity(route_count);
+        let mut summaries = Vec::with_capacity(route_count);
+        for (i, (mut route, outpoints)) in route_params
+            .into_iter()
+            .zip(route_outpoints.iter())
+            .enumerate()
+        {
+            route.manually_selected_outpoints = Some(outpoints.clone());
+            let reserved: Vec<OutPoint> = route_outpoints
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .flat_map(|(_, ops)| ops.iter().copied())
+                .collect();
+
+            let mut worker = self.route_worker(reserved);
+            match worker.prepare_coinswap(route) {
+                Ok(summary) => {
+                    summaries.push(summary);
+                    workers.push(worker);
+                }
+                Err(e) => {
+                    log::error!("Route {} could not be prepared: {:?}", i + 1, e);
+                    worker.abort_prepared_swap();
+                    for worker in &mut workers {
+                        worker.abort_prepared_swap();
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        let route_ids: Vec<&str> = summaries.iter().map(|s| s.swap_id.as_str()).collect();
+        let group_id = Hash160::hash(route_ids.concat().as_bytes())[0..8].to_lower_hex_string();
+
+        let total_fee = Amount::from_sat(
+            summaries
+                .iter()
+                .map(|s| s.total_estimated_fee.to_sat())
+                .sum(),
+        );
+        let summary = SwapSummary {
+            swap_id: group_id.clone(),
+            protocol: params.protocol,
+            send_amount: params.send_amount,
+            makers: summaries.iter().flat_map(|s| s.makers.clone()).collect(),
+            total_estimated_fee: total_fee,
+            estimated_receive_amount: params
+                .send_amount
+                .checked_sub(total_fee)
+                .unwrap_or(Amount::ZERO),
+            fee_budget: params.fee_budget(),
+            routes: summaries,
+        };
+
+        log::info!(
+            "Multi-route swap prepared: id={}, routes={}, estimated_fee={}",
+            group_id,
+            summary.routes.len(),
+            summary.total_estimated_fee
+        );
+        self.prepared_routes = Some(PreparedRoutes { group_id, workers });
+        Ok(summary)
+    }
+
+    /// Give every route its own makers, as the route's preferred makers followed by its spares.
+    ///
+    /// Preferred makers are dealt out in order, `maker_count` per route, with any left over
+    /// shared out as spares. Otherwise each route is drawn from the offerbook without the
+    /// makers (and spares) of the routes before it.
+    fn assign_route_makers(
+        &self,
+        params: &SwapParams,
+        route_params: &mut [SwapParams],
+    ) -> Result<(), TakerError> {
+        let maker_count = params.maker_count;
+        let route_count = route_params.len();
+
+        if let Some(preferred) = &params.preferred_makers {
+            if preferred.len() < maker_count * route_count {
+                return Err(TakerError::General(format!(
+                    "Not enough preferred makers for {} routes. Required: {}, Given: {}",
+                    route_count,
+                    maker_count * route_count,
+                    preferred.len()
+                )));
+            }
+            let (route_makers, spares) = preferred.split_at(maker_count * route_count);
+            for (i, route) in route_params.iter_mut().enumerate() {
+                let mut makers = route_makers[i * maker_count..(i + 1) * maker_count].to_vec();
+                makers.extend(spares.iter().skip(i).step_by(route_count).cloned());
+                route.preferred_makers = Some(makers);
+            }
+            return Ok(());
+        }
+
+        let mut taken: Vec<MakerAddress> = Vec::new();
+        for (i, route) in route_params.iter_mut().enumerate() {
+            let selected = self
+                .select_makers_from_offerbook(route, &taken)
+                .inspect_err(|_| {
+                    log::error!("No disjoint set of makers left for route {}", i + 1);
+                })?;
+            taken.extend(selected.iter().map(|oa| oa.address.clone()));
+            route.preferred_makers =
+                Some(selected.iter().map(|oa| oa.address.to_string()).collect());
+        }
+        Ok(())
+    }
+
+    /// Set aside disjoint funding UTXOs for every route, so concurrent routes never try to
+    /// spend the same coin. Manually selected UTXOs are used first. Each route gets enough
+    /// to pay its funding fee at the funding fee rate, plus a margin.
+    ///
+    /// Coin selection only considers the wallet's own spendable coins and skips the ones
+    /// already locked, so the bitcoind locks are left as they are.
+    fn assign_route_utxos(
+        &self,
+        params: &SwapParams,
+        amounts: &[Amount],
+    ) -> Result<Vec<Vec<OutPoint>>, TakerError> {
+        let wallet = self.read_wallet()?;
+
+        let mut assigned: Vec<Vec<OutPoint>> = Vec::with_capacity(amounts.len());
+        for amount in amounts {
+            let taken: Vec<OutPoint> = assigned.iter().flatten().copied().collect();
+            let manual = params
+                .manually_selected_outpoints
+                .as_ref()
+                .map(|outpoints| {
+                    outpoints
+                        .iter()
+                        .filter(|outpoint| !taken.contains(outpoint))
+                        .copied()
+                        .collect::<Vec<_>>()
+                })
+                .filter(|outpoints| !outpoints.is_empty());
+            let selected = wallet.coin_select(
+                *amount,
+                FUNDING_FEE_RATE * ROUTE_FUNDING_FEE_MARGIN,
+                manual,
+                Some(taken),
+            )?;
+            assigned.push(
+                selected
+                    .iter()
+                    .map(|(utxo, _)| OutPoint::new(utxo.txid, utxo.vout))
+                    .collect(),
+            );
+        }
+        Ok(assigned)
+    }
+
+    /// Run the routes of the prepared multi-route swap concurrently and combine their reports.
+    ///
+    /// Fails only if every route failed. A failed route has already been handed to its own
+    /// recovery loop, which keeps running on this taker.
+    pub(crate) fn start_routes(&mut self) -> Result<TakerReport, TakerError> {
+        let PreparedRoutes {
+            group_id,
+            mut workers,
+        } = self
+            .prepared_routes
+            .take()
+            .ok_or_else(|| TakerError::General("No prepared multi-route swap".to_string()))?;
+
+        let start_time = Instant::now();
+        let initial_utxos = self.read_wallet()?.list_all_utxo();
+        let route_ids: Vec<String> = workers
+            .iter()
+            .map(|worker| worker.swap_state().map(|swap| swap.id.clone()))
+            .collect::<Result<_, _>>()?;
+        let send_amount = workers
+            .iter()
+            .filter_map(|worker| worker.swap_state().ok())
+            .map(|swap| swap.params.send_amount)
+            .sum::<Amount>();
+
+        log::info!(
+            "Starting multi-route swap {} over {} routes: {}",
+            group_id,
+            route_ids.len(),
+            route_ids.join(", ")
+        );
+
+        let mut unstarted = Vec::new();
+        let results: Vec<Result<TakerReport, TakerError>> = thread::scope(|scope| {
+            let handles: Vec<_> = workers
+                .iter_mut()
+                .zip(&route_ids)
+                .map(|(worker, swap_id)| {
+                    thread::Builder::new()
+                        .name(format!("Route {swap_id}"))
+                        .spawn_scoped(scope, move || worker.start_coinswap(swap_id))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .enumerate()
+                .map(|(i, handle)| match handle {
+                    Ok(handle) => handle.join().unwrap_or_else(|_| {
+                        Err(TakerError::General("Route thread panicked".to_string()))
+                    }),
+                    Err(e) => {
+                        log::error!("Failed to start the thread of route {}: {:?}", i + 1, e);
+                        unstarted.push(i);
+                        Err(TakerError::IO(e))
+                    }
+                })
+                .collect()
+        });
+
+        // A route that never started has nothing on-chain, so it is dropped like a route
+        // failing before its funding.
+        for i in unstarted {
+            workers[i].abort_prepared_swap();
+        }
+
+        let paid_out: Vec<_> = workers
+            .iter()
+            .filter_map(|worker| worker.ongoing_swap.as_ref())
+            .flat_map(|swap| swap.paid_out.clone())
+            .collect();
+        for worker in &mut workers {
+            if let Some(recovery) = worker.recovery_loop.take() {
+                self.route_recovery_loops.push(recovery);
+            }
+        }
+        drop(workers);
+
+        let mut completed = Vec::new();
+        let mut failures = Vec::new();
+        for (swap_id, result) in route_ids.iter().zip(results) {
+            match result {
+                Ok(report) => completed.push(report),
+                Err(e) => {
+                    log::error!("Route {} failed: {:?}", swap_id, e);
+                    failures.push(format!("route {}: {:?}", swap_id, e));
+                }
+            }
+        }
+        if completed.is_empty() {
+            return Err(TakerError::General(format!(
+                "All {} routes of swap {} failed: {}",
+                route_ids.len(),
+                group_id,
+                failures.join("; ")
+            )));
+        }
+
+        let changes = {
+            let wallet = self.read_wallet()?;
+            Self::utxo_changes(&wallet, &initial_utxos, &paid_out)
+        };
+        let mut report = combine_route_reports(
+            group_id,
+            route_ids,
+            send_amount,
+            &completed,
+            &failures,
+            changes,
+        );
+        report.swap_duration_seconds = start_time.elapsed().as_secs_f64();
+        report.print();
+
+        log::info!(
+            "Multi-route swap {} finished: {} of {} routes completed",
+            report.swap_id,
+            completed.len(),
+            report.routes.len()
+        );
+        Ok(report)
+    }
+
+    /// Forget a prepared multi-route swap that was never started.
+    pub(crate) fn abort_prepared_routes(&mut self) {
+        if let Some(mut routes) = self.prepared_routes.take() {
+            log::info!("Dropping prepared multi-route swap {}", routes.group_id);
+            for worker in &mut routes.workers {
+                worker.abort_prepared_swap();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{taker::selection::MakerSelection, wallet::MakerFeeInfo};
+
+    fn route_report(swap_id: &str, maker: &str, maker_fee: u64) -> TakerReport {
+        TakerReport {
+            swap_id: swap_id.to_string(),
+            status: SwapStatus::Success,
+            network: "regtest".to_string(),
+            swap_duration_seconds: 1.0,
+            start_timestamp: 10,
+            end_timestamp: 20,
+            error_message: None,
+            outgoing_amount: 50_000,
+            incoming_amount: 0,
+            fee_paid: 0,
+            mining_fee: 0,
+            fee_percentage: 0.0,
+            total_maker_fees: maker_fee,
+            outgoing_contract_txid: Some("contract".to_string()),
+            incoming_contract_txid: Some("contract".to_string()),
+            funding_txids: vec![vec![format!("{swap_id}-funding")]],
+            makers_count: 1,
+            maker_addresses: vec![maker.to_string()],
+            maker_fee_info: vec![MakerFeeInfo {
+                maker_index: 0,
+                maker_address: maker.to_string(),
+                base_fee: maker_fee as f64,
+                amount_relative_fee: 0.0,
+                time_relative_fee: 0.0,
+                total_fee: maker_fee as f64,
+            }],
+            input_utxos: vec![],
+            output_change_amounts: vec![],
+            output_swap_amounts: vec![],
+            output_change_utxos: vec![],
+            output_swap_utxos: vec![],
+            routes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_split_amount_adds_up_and_varies() {
+        let mut rng = MakerSelection {
+            seed: Some(3),
+            ..MakerSelection::default()
+        }
+        .rng();
+        let total = Amount::from_sat(1_000_003);
+
+        for count in 1..=5 {
+            let amounts = split_amount(total, count, &mut rng);
+            assert_eq!(amounts.len(), count);
+            assert_eq!(amounts.iter().copied().sum::<Amount>(), total);
+            let (min, max) = (amounts.iter().min().unwrap(), amounts.iter().max().unwrap());
+            assert!(max.to_sat() <= min.to_sat() * 3 + 1);
+        }
+
+        let amounts = split_amount(total, 4, &mut rng);
+        assert!(amounts.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn test_combine_route_reports() {
+        let changes = UtxoChanges {
+            input_utxos: vec![60_000, 50_000],
+            output_change_utxos: vec![(9_000, "change".to_string())],
+            output_swap_utxos: vec![(48_000, "a".to_string()), (49_000, "b".to_string())],
+        };
+        let completed = [
+            route_report("r1", "m1", 1_000),
+            route_report("r2", "m2", 500),
+        ];
+        let report = combine_route_reports(
+            "group".to_string(),
+            vec!["r1".to_string(), "r2".to_string()],
+            Amount::from_sat(100_000),
+            &completed,
+            &[],
+            changes,
+        );
+
+        assert_eq!(report.status, SwapStatus::Success);
+        assert_eq!(report.routes, ["r1", "r2"]);
+        assert_eq!(report.outgoing_amount, 100_000);
+        assert_eq!(report.incoming_amount, 97_000);
+        assert_eq!(report.fee_paid, 4_000);
+        assert_eq!(report.total_maker_fees, 1_500);
+        assert_eq!(report.mining_fee, 2_500);
+        assert_eq!(report.maker_addresses, ["m1", "m2"]);
+        assert_eq!(report.funding_txids.len(), 2);
+        assert_eq!(report.outgoing_contract_txid, None);
+
+        let partial = combine_route_reports(
+            "group".to_string(),
+            vec!["r1".to_string(), "r2".to_string()],
+            Amount::from_sat(100_000),
+            &completed[..1],
+            &["route r2: timeout".to_string()],
+            UtxoChanges {
+                input_utxos: vec![],
+                output_change_utxos: vec![],
+                output_swap_utxos: vec![],
+            },
+        );
+        assert_eq!(partial.status, SwapStatus::Failed);
+        assert_eq!(partial.error_message.as_deref(), Some("route r2: timeout"));
+        assert_eq!(partial.maker_addresses, ["m1"]);
+    }
+}
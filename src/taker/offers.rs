@@ -28,10 +28,11 @@ use crate::{
         common_messages::{
             FidelityProof, GetOffer as RouterGetOffer,
             MakerToTakerMessage as RouterMakerToTakerMessage, Offer,
-            TakerHello as RouterTakerHello, TakerToMakerMessage as RouterTakerToMakerMessage,
+            TakerToMakerMessage as RouterTakerToMakerMessage,
         },
         error::ProtocolError,
     },
+    taker::api::exchange_hello,
     utill::{read_message, send_message},
     wallet::verify_fidelity_checks,
     watch_tower::{registry_storage::FileRegistry, zmq_backend::ChainSource},
@@ -584,6 +585,19 @@ impl OfferSyncService {
                     &oa.offer.tweakable_point,
                     &oa.offer.tweak_chain_code,
                 ) {
+                    Ok(_)
+                        if oa.offer.fidelity.bond.has_external_key()
+                            && book.bond_advertised_by_other(
+                                &oa.address,
+                                oa.offer.fidelity.bond.outpoint(),
+                            ) =>
+                    {
+                        log::warn!(
+                            "Fidelity bond of {} is already advertised by another maker",
+                            oa.address
+                        );
+                        book.mark_failure(&oa.address, now);
+                    }
                     Ok(_) => {
                         book.mark_success(&oa.address, oa.offer, oa.protocol, now);
                     }
@@ -834,6 +848,20 @@ impl OfferBook {
         }
     }
 
+    /// Whether a maker other than `address` is advertising the bond at `outpoint`.
+    ///
+    /// A bond held by an external key is not derived from the maker's wallet, so its owner could
+    /// certify it for several makers. It only counts for the first one.
+    fn bond_advertised_by_other(&self, address: &MakerAddress, outpoint: OutPoint) -> bool {
+        self.makers.iter().any(|m| {
+            &m.address != address
+                && matches!(m.state, MakerState::Good)
+                && m.offer
+                    .as_ref()
+                    .is_some_and(|offer| offer.fidelity.bond.outpoint() == outpoint)
+        })
+    }
+
     fn mark_failure(&mut self, address: &MakerAddress, now_ts: u64) {
         if let Some(m) = self.makers.iter_mut().find(|m| &m.address == address) {
             m.mark_failure(now_ts);
@@ -866,6 +894,7 @@ impl OfferBook {
 
     /// Gets all active (good) offers for a given protocol.
     /// Makers are included for both Legacy and Taproot requests.
+    /// Makers that are paused and not accepting swaps are skipped.
     fn active_makers(&self, protocol: &MakerProtocol) -> Vec<OfferAndAddress> {
         let mut result: Vec<_> = self
             .makers
@@ -878,6 +907,7 @@ impl OfferBook {
                     .unwrap_or(false)
             })
             .filter_map(|m| m.as_offer_and_address())
+            .filter(|oa| oa.offer.accepting_swaps)
             .collect();
         result.sort_by(|a, b| a.address.cmp(&b.address));
         result
@@ -1021,26 +1051,9 @@ impl MakerAddress {
         socket.set_read_timeout(Some(Duration::from_secs(FIRST_CONNECT_ATTEMPT_TIMEOUT_SEC)))?;
         socket.set_write_timeout(Some(Duration::from_secs(FIRST_CONNECT_ATTEMPT_TIMEOUT_SEC)))?;
 
-        // Send TakerHello
-        let taker_hello = RouterTakerToMakerMessage::TakerHello(RouterTakerHello);
-        send_message(&mut socket, &taker_hello)?;
-
-        // Read MakerHello
-        let msg_bytes = read_message(&mut socket)?;
-        let msg: RouterMakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
-
-        match msg {
-            RouterMakerToTakerMessage::MakerHello(_hello) => {
-                // Maker - supports both Legacy and Taproot
-            }
-            msg => {
-                return Err(ProtocolError::WrongMessage {
-                    expected: "MakerHello".to_string(),
-                    received: format!("{msg:?}"),
-                }
-                .into());
-            }
-        };
+        // Exchange hellos, solving the maker's proof-of-work if it asks for one.
+        // Maker - supports both Legacy and Taproot
+        exchange_hello(&mut socket)?;
 
         // Send GetOffer
         let get_offer = RouterTakerToMakerMessage::GetOffer(RouterGetOffer);
@@ -1077,6 +1090,7 @@ impl MakerAddress {
                 cert_sig: router_offer.fidelity.cert_sig,
             },
             tweak_chain_code: router_offer.tweak_chain_code,
+            accepting_swaps: router_offer.accepting_swaps,
         };
 
         log::info!(
@@ -1130,6 +1144,8 @@ mod tests {
             conf_height: Some(1000),
             is_spent: false,
             bond_index: 0,
+            external_key: false,
+            external_cert: None,
         };
 
         let cert_hash = bond.generate_cert_hash(maker_addr, &pubkey);
@@ -1151,6 +1167,7 @@ mod tests {
                 cert_sig,
             },
             tweak_chain_code: bitcoin::bip32::ChainCode::from([0u8; 32]),
+            accepting_swaps: true,
         }
     }
 
@@ -1217,6 +1234,30 @@ mod tests {
         assert_eq!(candidate.last_offer_update_ts, Some(now_ts));
     }
 
+    #[test]
+    fn bond_counts_for_one_maker_only() {
+        let now_ts = 170000;
+        let mut book = OfferBook { makers: vec![] };
+        for id in ["6101", "6102"] {
+            book.makers.push(MakerOfferCandidate {
+                address: addr(id),
+                fidelity_outpoint: None,
+                offer: None,
+                state: MakerState::Unresponsive { retries: 1 },
+                protocol: None,
+                last_offer_update_ts: None,
+                next_offer_check_ts: None,
+            });
+        }
+        let offer = dummy_offer(&addr("6101").to_string());
+        let outpoint = offer.fidelity.bond.outpoint();
+        assert!(!book.bond_advertised_by_other(&addr("6102"), outpoint));
+
+        book.mark_success(&addr("6101"), offer, MakerProtocol::Taproot, now_ts);
+        assert!(book.bond_advertised_by_other(&addr("6102"), outpoint));
+        assert!(!book.bond_advertised_by_other(&addr("6101"), outpoint));
+    }
+
     #[test]
     fn makers_to_poll_respects_backoff_timer() {
         let now_ts = 170000;
@@ -1237,4 +1278,30 @@ mod tests {
         let to_poll_after = book.makers_to_poll(now_ts + 11);
         assert_eq!(to_poll_after, vec![addr("6103")]);
     }
+
+    #[test]
+    fn active_makers_skips_paused_makers() {
+        let now_ts = 170000;
+        let mut book = OfferBook { makers: vec![] };
+        for (port, accepting_swaps) in [("6103", true), ("6104", false)] {
+            let mut candidate = MakerOfferCandidate {
+                address: addr(port),
+                fidelity_outpoint: None,
+                offer: None,
+                state: MakerState::Good,
+                protocol: None,
+                last_offer_update_ts: None,
+                next_offer_check_ts: None,
+            };
+            let mut offer = dummy_offer(&candidate.address.to_string());
+            offer.accepting_swaps = accepting_swaps;
+            candidate.mark_success(offer, MakerProtocol::Unified, now_ts);
+            book.makers.push(candidate);
+        }
+
+        let active = book.active_makers(&MakerProtocol::Taproot);
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].address, addr("6103"));
+        assert_eq!(book.good_makers().len(), 2);
+    }
 }
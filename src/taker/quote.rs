@@ -0,0 +1,238 @@
+//! Offline swap quotes.
+//!
+//! A quote prices a coinswap from the cached offerbook alone: no maker is contacted, no swap
+//! state is created and nothing is written to disk. It is meant for previews before
+//! committing to [`Taker::prepare_coinswap`], whose negotiated figures may differ if makers have
+//! changed their offers since the offerbook was last synced.
+
+use std::convert::TryFrom;
+
+use bitcoin::Amount;
+
+use crate::{
+    protocol::common_messages::ProtocolVersion, utill::MIN_FEE_RATE, wallet::BlockchainBackend,
+};
+
+use super::{
+    api::{estimate_route_fees, MakerFeeInfo, SwapParams, Taker},
+    error::TakerError,
+    offers::MakerAddress,
+};
+
+/// Confirmation target used to estimate the feerate of the quote, in blocks.
+const QUOTE_CONF_TARGET: u16 = 6;
+
+/// Weight of the parts of a funding transaction that don't depend on its inputs: version,
+/// locktime, counts, segwit marker, one contract output and one change output.
+const FUNDING_TX_FIXED_WEIGHT: u64 = 43 + 2 * 172;
+
+/// Non-witness weight of one input: outpoint, empty scriptsig and sequence.
+const INPUT_BASE_WEIGHT: u64 = (32 + 4 + 1 + 4) * 4;
+
+/// Size the wallet assumes for the transaction sweeping one incoming swapcoin, in vbytes.
+const SWEEP_TX_VSIZE: u64 = 150;
+
+/// Average time between blocks, for the recovery timeline.
+const BLOCK_INTERVAL_SECS: u64 = 600;
+
+/// A side-effect-free estimate of what a coinswap would cost, returned by
+/// [`Taker::quote_coinswap`].
+#[derive(Debug, Clone)]
+pub struct SwapQuote {
+    /// Protocol version.
+    pub protocol: ProtocolVersion,
+    /// Amount the taker would send.
+    pub send_amount: Amount,
+    /// Candidate route with per-maker fees, from the cached offers (one entry per hop, in route order).
+    pub makers: Vec<MakerFeeInfo>,
+    /// Total maker fees across all hops.
+    pub total_maker_fee: Amount,
+    /// Feerate the mining fees are estimated at, in sats/vbyte.
+    pub feerate: f64,
+    /// Mining fee of the taker's funding transaction, paid on top of the swap amount.
+    pub funding_fee: Amount,
+    /// Mining fee of sweeping the received coins, deducted from what is received.
+    pub sweep_fee: Amount,
+    /// Estimated amount received after maker fees and the sweep.
+    pub estimated_receive_amount: Amount,
+    /// Fee cap the swap would be held to, if any. See [`SwapParams::fee_budget`].
+    pub fee_budget: Option<Amount>,
+    /// Locktime of the taker's own contract, in blocks. The longest of the route.
+    pub refund_locktime: u16,
+    /// Blocks from funding broadcast until, in the worst case, the taker can reclaim its funds
+    /// through the timelock: the funding confirmations plus `refund_locktime`.
+    pub worst_case_recovery_blocks: u32,
+}
+
+impl SwapQuote {
+    /// Total cost of the swap to the taker: maker fees plus mining fees.
+    pub fn total_cost(&self) -> Amount {
+        self.total_maker_fee + self.funding_fee + self.sweep_fee
+    }
+
+    /// Rough wall-clock time of [`SwapQuote::worst_case_recovery_blocks`], at ten minutes per block.
+    pub fn worst_case_recovery_secs(&self) -> u64 {
+        self.worst_case_recovery_blocks as u64 * BLOCK_INTERVAL_SECS
+    }
+
+    /// Whether the route's maker fees exceed the fee budget, in which case
+    /// [`Taker::prepare_coinswap`] would refuse it.
+    pub fn exceeds_fee_budget(&self) -> bool {
+        self.fee_budget
+            .is_some_and(|budget| self.total_maker_fee > budget)
+    }
+}
+
+impl<B: BlockchainBackend> Taker<B> {
+    /// Price a coinswap from the cached offerbook, without contacting makers.
+    ///
+    /// Picks a candidate route the way [`Taker::prepare_coinswap`] would (or uses the preferred
+    /// makers, which must have a cached offer), estimates the per-hop fees at the locktimes the
+    /// route would get, and the mining fees of the funding and sweep transactions at the
+    /// backend's current feerate estimate. Makers are picked at random, so two quotes may show
+    /// different routes unless a maker selection seed is configured.
+    pub fn quote_coinswap(&self, params: &SwapParams) -> Result<SwapQuote, TakerError> {
+        let mut params = params.clone();
+        self.apply_default_fee_caps(&mut params)?;
+
+        let hops: Vec<(String, ProtocolVersion, _)> = match &params.preferred_makers {
+            Some(preferred) => {
+                let cached = self.offerbook.all_makers();
+                preferred
+                    .iter()
+                    .take(params.maker_count)
+                    .map(|addr| {
+                        let address = MakerAddress::try_from(addr.clone()).map_err(|e| {
+                            TakerError::General(format!("Invalid maker address '{}': {}", addr, e))
+                        })?;
+                        let offer = cached
+                            .iter()
+                            .find(|m| m.address == address)
+                            .and_then(|m| m.offer.clone())
+                            .ok_or_else(|| {
+                                TakerError::General(format!("No cached offer for maker {}", addr))
+                            })?;
+                        Ok((address.to_string(), params.protocol, offer))
+                    })
+                    .collect::<Result<_, TakerError>>()?
+            }
+            None => self
+                .select_makers_from_offerbook(&params, &[])?
+                .into_iter()
+                .take(params.maker_count)
+                .map(|oa| (oa.address.to_string(), params.protocol, oa.offer))
+                .collect(),
+        };
+        if hops.len() < params.maker_count {
+            return Err(TakerError::General(format!(
+                "Not enough preferred makers. Required: {}, Given: {}",
+                params.maker_count,
+                hops.len()
+            )));
+        }
+
+        let route: Vec<_> = hops
+            .iter()
+            .map(|(address, protocol, offer)| (address.clone(), *protocol, Some(offer)))
+            .collect();
+        let makers = estimate_route_fees(params.send_amount, &route);
+        let total_maker_fee = Amount::from_sat(makers.iter().map(|m| m.estimated_fee_sats).sum());
+
+        let wallet = self.read_wallet()?;
+        let feerate = wallet
+            .rpc
+            .estimate_smart_fee(QUOTE_CONF_TARGET, None)
+            .map_err(|e| log::debug!("Feerate estimation failed: {:?}", e))
+            .ok()
+            .and_then(|estimate| estimate.fee_rate)
+            // BTC/kvB to sats/vbyte.
+            .map_or(MIN_FEE_RATE, |rate| {
+                (rate.to_sat() as f64 / 1000.0).max(MIN_FEE_RATE)
+            });
+
+        let inputs = wallet.coin_select(
+            params.send_amount,
+            feerate,
+            params.manually_selected_outpoints.clone(),
+            None,
+        )?;
+        let funding_weight = FUNDING_TX_FIXED_WEIGHT
+            + inputs
+                .iter()
+                .map(|(_, spend_info)| {
+                    INPUT_BASE_WEIGHT + spend_info.estimate_witness_size() as u64
+                })
+                .sum::<u64>();
+        let funding_fee =
+            Amount::from_sat((funding_weight.div_ceil(4) as f64 * feerate).ceil() as u64);
+        let sweep_fee = Amount::from_sat(
+            (SWEEP_TX_VSIZE as f64 * feerate).ceil() as u64 * params.tx_count.max(1) as u64,
+        );
+
+        let estimated_receive_amount = params
+            .send_amount
+            .checked_sub(total_maker_fee + sweep_fee)
+            .unwrap_or(Amount::ZERO);
+        let refund_locktime = makers.first().map_or(0, |m| m.locktime);
+
+        Ok(SwapQuote {
+            protocol: params.protocol,
+            send_amount: params.send_amount,
+            makers,
+            total_maker_fee,
+            feerate,
+            funding_fee,
+            sweep_fee,
+            estimated_receive_amount,
+            fee_budget: params.fee_budget(),
+            refund_locktime,
+            worst_case_recovery_blocks: params.required_confirms + refund_locktime as u32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::taker::api::{REFUND_LOCKTIME_BASE, REFUND_LOCKTIME_STEP};
+
+    #[test]
+    fn test_quote_totals_and_timeline() {
+        let route: Vec<_> = (0..3)
+            .map(|i| (format!("maker{i}"), ProtocolVersion::Legacy, None))
+            .collect();
+        let makers = estimate_route_fees(Amount::from_sat(100_000), &route);
+        let locktimes: Vec<u16> = makers.iter().map(|m| m.locktime).collect();
+        assert_eq!(
+            locktimes,
+            [
+                REFUND_LOCKTIME_BASE + 2 * REFUND_LOCKTIME_STEP,
+                REFUND_LOCKTIME_BASE + REFUND_LOCKTIME_STEP,
+                REFUND_LOCKTIME_BASE
+            ]
+        );
+
+        let mut quote = SwapQuote {
+            protocol: ProtocolVersion::Legacy,
+            send_amount: Amount::from_sat(100_000),
+            refund_locktime: locktimes[0],
+            makers,
+            total_maker_fee: Amount::from_sat(1_500),
+            feerate: 2.0,
+            funding_fee: Amount::from_sat(400),
+            sweep_fee: Amount::from_sat(300),
+            estimated_receive_amount: Amount::from_sat(98_200),
+            fee_budget: None,
+            worst_case_recovery_blocks: 10,
+        };
+        assert_eq!(quote.total_cost(), Amount::from_sat(2_200));
+        assert_eq!(quote.worst_case_recovery_secs(), 6_000);
+        assert!(!quote.exceeds_fee_budget());
+
+        // The budget caps maker fees only, mining fees aside.
+        quote.fee_budget = Some(Amount::from_sat(1_500));
+        assert!(!quote.exceeds_fee_budget());
+        quote.fee_budget = Some(Amount::from_sat(1_499));
+        assert!(quote.exceeds_fee_budget());
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, to_string_pretty};
+
+use crate::{
+    protocol::common_messages::ProtocolVersion,
+    taker::{
+        api::SwapHistoryEntry,
+        daemon::{ScheduleState, ScheduledSwap},
+    },
+    wallet::Balances,
+};
+
+/// Requests served by the takerd RPC.
+#[derive(Serialize, Deserialize, Debug)]
+pub enum RpcMsgReq {
+    /// Ping request to check connectivity.
+    Ping,
+    /// Request to retrieve the total wallet balances of different categories.
+    Balances,
+    /// Request for generating a new wallet address.
+    NewAddress,
+    /// Request to list past and in-flight swaps.
+    History,
+    /// Request to schedule a coinswap.
+    ScheduleSwap {
+        /// Amount to swap, in sats.
+        amount: u64,
+        /// Number of makers to route through.
+        makers: usize,
+        /// Protocol to swap with.
+        protocol: ProtocolVersion,
+        /// Seconds to wait before starting the swap.
+        delay_secs: u64,
+    },
+    /// Request to list the scheduled swaps.
+    ListScheduled,
+    /// Request to cancel a scheduled swap that has not started.
+    CancelScheduled {
+        /// Schedule entry ID.
+        id: u64,
+    },
+    /// Request to stop takerd once the swap in progress, if any, has finished.
+    Stop,
+}
+
+/// An authenticated RPC request, as sent over the wire.
+///
+/// Every [`RpcMsgReq`] is wrapped together with the RPC token, which is the content of
+/// the `.cookie` file takerd writes in its data directory at startup.
+#[derive(Serialize, Deserialize)]
+pub struct RpcRequest {
+    /// The RPC authentication token.
+    pub auth: String,
+    /// The actual request.
+    pub msg: RpcMsgReq,
+}
+
+/// Responses of the takerd RPC.
+#[derive(Serialize, Deserialize, Debug)]
+pub enum RpcMsgResp {
+    /// Response to a Ping request.
+    Pong,
+    /// Response containing the total wallet balances of different categories.
+    TotalBalanceResp(Balances),
+    /// Response containing a newly generated wallet address.
+    NewAddressResp(String),
+    /// Response listing past and in-flight swaps, oldest first.
+    HistoryResp(Vec<SwapHistoryEntry>),
+    /// Response with the swap just scheduled or cancelled.
+    ScheduledResp(ScheduledSwap),
+    /// Response listing all scheduled swaps.
+    ListScheduledResp(Vec<ScheduledSwap>),
+    /// Response indicating takerd is shutting down.
+    Shutdown,
+    /// Response with the internal server error.
+    ServerError(String),
+}
+
+impl Display for RpcMsgResp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pong => write!(f, "Pong"),
+            Self::NewAddressResp(addr) => write!(f, "{addr}"),
+            Self::TotalBalanceResp(balances) => {
+                write!(
+                    f,
+                    "{}",
+                    to_string_pretty(&json!({
+                        "regular": balances.regular.to_sat(),
+                        "swap": balances.swap.to_sat(),
+                        "contract": balances.contract.to_sat(),
+                        "spendable": balances.spendable.to_sat(),
+                    }))
+                    .unwrap()
+                )
+            }
+            Self::HistoryResp(entries) => write!(
+                f,
+                "{}",
+                to_string_pretty(entries).expect("History JSON serialization failed")
+            ),
+            Self::ScheduledResp(swap) => write!(f, "{}", ScheduledLine(swap)),
+            Self::ListScheduledResp(swaps) => {
+                if swaps.is_empty() {
+                    return write!(f, "No scheduled swaps");
+                }
+                for swap in swaps {
+                    writeln!(f, "{}", ScheduledLine(swap))?;
+                }
+                Ok(())
+            }
+            Self::Shutdown => write!(f, "Shutdown Initiated"),
+            Self::ServerError(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// One-line summary of a scheduled swap.
+struct ScheduledLine<'a>(&'a ScheduledSwap);
+
+impl Display for ScheduledLine<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let swap = self.0;
+        let state = match &swap.state {
+            ScheduleState::Pending => "pending".to_string(),
+            ScheduleState::Running => "running".to_string(),
+            ScheduleState::Completed { swap_id } => format!("completed ({swap_id})"),
+            ScheduleState::Failed { error } => format!("failed: {error}"),
+            ScheduleState::Cancelled => "cancelled".to_string(),
+        };
+        write!(
+            f,
+            "#{} {} sats, {} makers, {}, at {}: {}",
+            swap.id, swap.amount_sat, swap.makers, swap.protocol, swap.execute_at, state
+        )
+    }
+}
@@ -0,0 +1,10 @@
+//! Local RPC of the `takerd` daemon, used by `takerd-cli`.
+//!
+//! Mirrors the maker RPC: CBOR messages over a TCP socket bound to localhost, authenticated
+//! with the token in the `.cookie` file that takerd writes into its data directory.
+
+mod messages;
+pub(crate) mod server;
+
+pub use crate::utill::{read_rpc_cookie, rpc_cookie_path, RPC_COOKIE_FILE};
+pub use messages::{RpcMsgReq, RpcMsgResp, RpcRequest};
@@ -0,0 +1,114 @@
+use std::{
+    io::ErrorKind,
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+    thread::sleep,
+    time::Duration,
+};
+
+use bitcoin::Amount;
+
+use super::messages::{RpcMsgReq, RpcMsgResp, RpcRequest};
+use crate::{
+    taker::{daemon::TakerDaemon, error::TakerError},
+    utill::{read_message, send_message, token_matches, HEART_BEAT_INTERVAL},
+    wallet::{AddressType, BlockchainBackend},
+};
+
+fn handle_request<B: BlockchainBackend>(
+    daemon: &TakerDaemon<B>,
+    socket: &mut TcpStream,
+    rpc_token: &str,
+) -> Result<(), TakerError> {
+    let msg_bytes = read_message(socket)?;
+    let RpcRequest { auth, msg } = serde_cbor::from_slice(&msg_bytes)?;
+
+    let resp = if token_matches(rpc_token, &auth) {
+        log::info!("RPC request received: {msg:?}");
+        process_request(daemon, msg)?
+    } else {
+        log::warn!("Rejected unauthenticated RPC request");
+        RpcMsgResp::ServerError("Unauthorized: invalid RPC auth token".to_string())
+    };
+
+    if let Err(e) = send_message(socket, &resp) {
+        log::error!("Error sending RPC response {e:?}");
+    }
+    Ok(())
+}
+
+fn process_request<B: BlockchainBackend>(
+    daemon: &TakerDaemon<B>,
+    request: RpcMsgReq,
+) -> Result<RpcMsgResp, TakerError> {
+    let resp = match request {
+        RpcMsgReq::Ping => RpcMsgResp::Pong,
+        RpcMsgReq::Balances => RpcMsgResp::TotalBalanceResp(daemon.read_wallet()?.get_balances()?),
+        RpcMsgReq::NewAddress => {
+            let address = daemon
+                .write_wallet()?
+                .get_next_external_address(AddressType::P2TR)?;
+            RpcMsgResp::NewAddressResp(address.to_string())
+        }
+        RpcMsgReq::History => RpcMsgResp::HistoryResp(daemon.swap_history()?),
+        RpcMsgReq::ScheduleSwap {
+            amount,
+            makers,
+            protocol,
+            delay_secs,
+        } => RpcMsgResp::ScheduledResp(daemon.schedule_swap(
+            Amount::from_sat(amount),
+            makers,
+            protocol,
+            delay_secs,
+        )?),
+        RpcMsgReq::ListScheduled => RpcMsgResp::ListScheduledResp(daemon.scheduled_swaps()),
+        RpcMsgReq::CancelScheduled { id } => {
+            RpcMsgResp::ScheduledResp(daemon.cancel_scheduled_swap(id)?)
+        }
+        RpcMsgReq::Stop => {
+            daemon.shutdown();
+            RpcMsgResp::Shutdown
+        }
+    };
+    Ok(resp)
+}
+
+pub(crate) fn start_rpc_server<B: BlockchainBackend>(
+    daemon: Arc<TakerDaemon<B>>,
+    rpc_token: &str,
+) -> Result<(), TakerError> {
+    let rpc_port = daemon.config().rpc_port;
+    let listener = TcpListener::bind(("127.0.0.1", rpc_port))?;
+    log::info!("takerd RPC socket binding successful at 127.0.0.1:{rpc_port}");
+
+    listener.set_nonblocking(true)?;
+
+    while !daemon.is_shutting_down() {
+        match listener.accept() {
+            Ok((mut stream, addr)) => {
+                log::info!("Got RPC request from: {addr}");
+                stream.set_read_timeout(Some(Duration::from_secs(20)))?;
+                stream.set_write_timeout(Some(Duration::from_secs(20)))?;
+                // Do not cause hard error if a rpc request fails
+                if let Err(e) = handle_request(&daemon, &mut stream, rpc_token) {
+                    log::error!("Error processing RPC Request: {e:?}");
+                    if let Err(e) =
+                        send_message(&mut stream, &RpcMsgResp::ServerError(format!("{e:?}")))
+                    {
+                        log::error!("Error sending RPC response {e:?}");
+                    }
+                }
+            }
+            Err(e) => {
+                if e.kind() != ErrorKind::WouldBlock {
+                    log::error!("Error accepting RPC connection: {e:?}");
+                }
+            }
+        }
+
+        sleep(HEART_BEAT_INTERVAL);
+    }
+
+    Ok(())
+}
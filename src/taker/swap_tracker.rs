@@ -83,6 +83,17 @@ pub enum SwapPhase {
     Failed,
 }
 
+impl SwapPhase {
+    /// Whether a swap interrupted in this phase can be resumed with its makers: all contracts
+    /// are in place and only the private key handover is left.
+    ///
+    /// Earlier phases are not resumable. The exchange progress only records the milestones
+    /// reached, while the contracts and signatures of an unfinished exchange are kept in memory.
+    pub fn is_resumable(self) -> bool {
+        matches!(self, SwapPhase::Finalizing | SwapPhase::PrivkeysForwarded)
+    }
+}
+
 impl fmt::Display for SwapPhase {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -478,6 +489,25 @@ impl SwapTracker {
             .collect()
     }
 
+    /// Returns all swap records, oldest first.
+    pub fn records(&self) -> Vec<&SwapRecord> {
+        let mut records: Vec<&SwapRecord> = self.data.swaps.values().collect();
+        records.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.swap_id.cmp(&b.swap_id))
+        });
+        records
+    }
+
+    /// Returns the swap records that were interrupted in a resumable phase, oldest first.
+    pub fn resumable_swaps(&self) -> Vec<&SwapRecord> {
+        self.records()
+            .into_iter()
+            .filter(|r| r.phase.is_resumable())
+            .collect()
+    }
+
     /// Get a reference to a swap record by ID.
     pub fn get_record(&self, swap_id: &str) -> Option<&SwapRecord> {
         self.data.swaps.get(swap_id)
@@ -508,8 +538,9 @@ impl SwapTracker {
     /// Clean up incomplete swap records from a previous session.
     ///
     /// - Early-phase swaps (Negotiating, FundingCreated) have no funds at risk — removes them.
-    /// - Late-phase swaps (FundsBroadcast, ContractsExchanged, etc.) are marked as Failed
+    /// - Late-phase swaps (FundsBroadcast, ContractsExchanged) are marked as Failed
     ///   so wallet-driven recovery can handle them.
+    /// - Resumable swaps (Finalizing, PrivkeysForwarded) are left for the taker to resume.
     /// - Completed swaps are left untouched.
     pub fn cleanup_incomplete(&mut self) {
         let incomplete_ids: Vec<String> = self
@@ -541,6 +572,13 @@ impl SwapTracker {
                     }
                 }
                 SwapPhase::Completed => {}
+                phase if phase.is_resumable() => {
+                    log::info!(
+                        "Swap {} was in phase {} — left to be resumed with its makers",
+                        swap_id,
+                        phase
+                    );
+                }
                 _ => {
                     log::warn!(
                         "Swap {} was in phase {} — marking as Failed, wallet recovery will handle it",
@@ -697,6 +735,35 @@ mod tests {
         assert!(SwapPhase::PrivkeysForwarded < SwapPhase::Completed);
     }
 
+    #[test]
+    fn test_cleanup_keeps_resumable_swaps() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker = SwapTracker::load_or_create(dir.path()).unwrap();
+
+        for (id, phase) in [
+            ("negotiated", SwapPhase::Negotiated),
+            ("broadcast", SwapPhase::FundsBroadcast),
+            ("finalizing", SwapPhase::Finalizing),
+            ("forwarded", SwapPhase::PrivkeysForwarded),
+        ] {
+            tracker.save_record(&make_test_record(id, phase)).unwrap();
+        }
+        tracker.cleanup_incomplete();
+
+        assert!(tracker.get_record("negotiated").is_none());
+        assert_eq!(
+            tracker.get_record("broadcast").unwrap().phase,
+            SwapPhase::Failed
+        );
+        let mut resumable: Vec<_> = tracker
+            .resumable_swaps()
+            .iter()
+            .map(|r| r.swap_id.as_str())
+            .collect();
+        resumable.sort();
+        assert_eq!(resumable, ["finalizing", "forwarded"]);
+    }
+
     #[test]
     fn test_recovery_phase_ordering() {
         assert!(RecoveryPhase::NotStarted < RecoveryPhase::PreimageStamped);
@@ -765,4 +832,24 @@ mod tests {
             SwapPhase::FundsBroadcast
         );
     }
+
+    #[test]
+    fn test_records_sorted_by_creation() {
+        let dir = TempDir::new().unwrap();
+        let mut tracker = SwapTracker::load_or_create(dir.path()).unwrap();
+
+        let mut newer = make_test_record("swap_b", SwapPhase::Completed);
+        newer.created_at = 200;
+        let mut older = make_test_record("swap_a", SwapPhase::FundsBroadcast);
+        older.created_at = 100;
+        tracker.save_record(&newer).unwrap();
+        tracker.save_record(&older).unwrap();
+
+        let ids: Vec<&str> = tracker
+            .records()
+            .iter()
+            .map(|r| r.swap_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["swap_a", "swap_b"]);
+    }
 }
@@ -12,14 +12,18 @@ use crate::{
         contract2::{create_hashlock_script, create_timelock_script},
         taproot_messages::{SerializableScalar, TaprootContractData},
     },
-    utill::{read_message, send_message, MIN_FEE_RATE},
+    utill::send_message,
     wallet::{
         swapcoin::{IncomingSwapCoin, OutgoingSwapCoin, WatchOnlySwapCoin},
         BlockchainBackend, Wallet,
     },
 };
 
-use super::{api::Taker, error::TakerError, swap_tracker::SwapPhase};
+use super::{
+    api::{Taker, FUNDING_FEE_RATE},
+    error::TakerError,
+    swap_tracker::SwapPhase,
+};
 
 impl<B: BlockchainBackend> Taker<B> {
     /// Build contract data from a previous maker's response (for forwarding to the next maker).
@@ -58,6 +62,7 @@ impl<B: BlockchainBackend> Taker<B> {
         swap_id: &str,
         network: Network,
         manually_selected_outpoints: Option<Vec<OutPoint>>,
+        excluded_outpoints: Option<Vec<OutPoint>>,
         reference_height: Option<u32>,
     ) -> Result<Vec<OutgoingSwapCoin>, TakerError> {
         let secp = Secp256k1::new();
@@ -133,9 +138,9 @@ impl<B: BlockchainBackend> Taker<B> {
             let funding_result = wallet.create_funding_txes(
                 send_amount,
                 std::slice::from_ref(&taproot_address),
-                MIN_FEE_RATE,
+                FUNDING_FEE_RATE,
                 manually_selected_outpoints.clone(),
-                None,
+                excluded_outpoints.clone(),
             )?;
 
             for (contract_tx, &output_pos) in funding_result
@@ -265,27 +270,15 @@ impl<B: BlockchainBackend> Taker<B> {
                 },
             );
 
-            #[cfg(feature = "integration-test")]
-            if self.behavior == super::api::TakerBehavior::CloseAtSendersContract {
-                log::warn!(
-                    "Test behavior: closing at sender's contract (before sending to maker {})",
-                    i
-                );
-                return Err(TakerError::General(
-                    "Test: closing at sender's contract".to_string(),
-                ));
-            }
-
-            send_message(
+            self.send_to_maker(
                 &mut stream,
-                &TakerToMakerMessage::TaprootContractData(Box::new(contract_data)),
+                TakerToMakerMessage::TaprootContractData(Box::new(contract_data)),
             )?;
             self.swap_state_mut()?.makers[i]
                 .taproot_exchange_mut()?
                 .contract_data_sent = true;
 
-            let msg_bytes = read_message(&mut stream)?;
-            let msg: MakerToTakerMessage = serde_cbor::from_slice(&msg_bytes)?;
+            let msg = self.read_from_maker(&mut stream)?;
 
             match msg {
                 MakerToTakerMessage::TaprootContractData(maker_contract) => {
@@ -295,17 +288,6 @@ impl<B: BlockchainBackend> Taker<B> {
                         maker_contract.contract_txs.len()
                     );
 
-                    #[cfg(feature = "integration-test")]
-                    if self.behavior == super::api::TakerBehavior::CloseAtSendersContractFromMaker {
-                        log::warn!(
-                            "Test behavior: closing after receiving maker {}'s contract data",
-                            i
-                        );
-                        return Err(TakerError::General(
-                            "Test: closing at sender's contract from maker".to_string(),
-                        ));
-                    }
-
                     // Verify contract data before creating swapcoins
                     let expected_locktime = self.swap_state()?.makers[i].negotiated_timelock;
                     let min_expected = self.min_expected_amount_for_hop(i);
@@ -962,6 +962,71 @@ pub fn interactive_select(
     Ok(selected_utxo)
 }
 
+/// Name of the cookie file inside the daemon's data directory.
+pub const RPC_COOKIE_FILE: &str = ".cookie";
+
+/// Path of the RPC cookie file for a given data directory.
+pub fn rpc_cookie_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(RPC_COOKIE_FILE)
+}
+
+/// Generates a fresh random token and writes it to the cookie file, readable only by the owner.
+pub(crate) fn write_rpc_cookie(data_dir: &Path) -> io::Result<String> {
+    use bitcoin::{
+        hex::DisplayHex,
+        secp256k1::rand::{rngs::OsRng, RngCore},
+    };
+
+    let mut secret = [0u8; 32];
+    OsRng.fill_bytes(&mut secret);
+    let token = secret.to_lower_hex_string();
+
+    fs::create_dir_all(data_dir)?;
+    let path = rpc_cookie_path(data_dir);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    file.write_all(token.as_bytes())?;
+    file.sync_all()?;
+
+    Ok(token)
+}
+
+/// Reads the RPC token from the cookie file in the given data directory.
+pub fn read_rpc_cookie(data_dir: &Path) -> io::Result<String> {
+    Ok(fs::read_to_string(rpc_cookie_path(data_dir))?
+        .trim()
+        .to_string())
+}
+
+/// Removes the cookie file, ignoring a file that is already gone.
+pub(crate) fn remove_rpc_cookie(data_dir: &Path) {
+    if let Err(e) = fs::remove_file(rpc_cookie_path(data_dir)) {
+        if e.kind() != io::ErrorKind::NotFound {
+            log::warn!("Failed to remove RPC cookie file: {e:?}");
+        }
+    }
+}
+
+/// Compares two tokens without short-circuiting on the first differing byte.
+pub(crate) fn token_matches(expected: &str, provided: &str) -> bool {
+    let (expected, provided) = (expected.as_bytes(), provided.as_bytes());
+    if expected.len() != provided.len() {
+        return false;
+    }
+    expected
+        .iter()
+        .zip(provided)
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+        == 0
+}
+
 #[cfg(test)]
 mod tests {
     use std::{net::TcpListener, thread};
@@ -983,6 +1048,7 @@ mod tests {
 
         let message = MakerToTakerMessage::MakerHello(MakerHello {
             supported_protocols: vec![ProtocolVersion::Legacy, ProtocolVersion::Taproot],
+            pow_challenge: None,
         });
 
         thread::spawn(move || {
@@ -1319,4 +1385,33 @@ mod tests {
             assert!(msg.contains("Details"));
         }
     }
+
+    #[test]
+    fn test_cookie_roundtrip() {
+        let dir = env::temp_dir().join("coinswap_rpc_cookie_test");
+        let _ = fs::remove_dir_all(&dir);
+
+        let token = write_rpc_cookie(&dir).unwrap();
+        assert_eq!(token.len(), 64);
+        assert_eq!(read_rpc_cookie(&dir).unwrap(), token);
+
+        // A restart rotates the token.
+        let rotated = write_rpc_cookie(&dir).unwrap();
+        assert_ne!(token, rotated);
+        assert_eq!(read_rpc_cookie(&dir).unwrap(), rotated);
+
+        remove_rpc_cookie(&dir);
+        assert!(read_rpc_cookie(&dir).is_err());
+        remove_rpc_cookie(&dir);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches("abcd", "abcd"));
+        assert!(!token_matches("abcd", "abce"));
+        assert!(!token_matches("abcd", "abc"));
+        assert!(!token_matches("abcd", ""));
+    }
 }
@@ -40,6 +40,7 @@ use rust_coinselect::{
 use super::{
     error::WalletError,
     rpc::{BitcoindBackend, BlockchainBackend, HdOrigin},
+    spend::SweepDestination,
     storage::{AddressType, WalletStore},
 };
 
@@ -67,6 +68,8 @@ pub struct Wallet<B: BlockchainBackend = BitcoindBackend> {
     /// If present, wallet data will be encrypted/decrypted using AES-GCM.
     /// The original passphrase is never stored—only the derived key is kept in memory.
     pub(crate) store_enc_material: Option<KeyMaterial>,
+    /// How long the last [`Wallet::sync_and_save`] took.
+    pub(crate) last_sync_duration: Option<Duration>,
 }
 /// Compares two wallets for cryptographic equivalence.
 ///
@@ -236,6 +239,8 @@ pub struct RecoveryOutcome {
     pub resolved: Vec<(Txid, Txid)>,
     /// Contract txids that were discarded (already spent or never broadcast).
     pub discarded: Vec<Txid>,
+    /// Sweep outputs paid to an external destination, as (address, amount).
+    pub paid_out: Vec<(Address, Amount)>,
 }
 
 impl RecoveryOutcome {
@@ -250,6 +255,44 @@ impl RecoveryOutcome {
     }
 }
 
+/// Script path used to reclaim a live contract output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractSpendPath {
+    /// Outgoing contract, reclaimed after its timelock matures.
+    Timelock,
+    /// Incoming contract, claimed with the known hash preimage.
+    Hashlock,
+}
+
+impl Display for ContractSpendPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContractSpendPath::Timelock => write!(f, "timelock"),
+            ContractSpendPath::Hashlock => write!(f, "hashlock"),
+        }
+    }
+}
+
+/// How a live contract output would be reclaimed, computed without broadcasting anything.
+#[derive(Debug, Clone)]
+pub struct ContractRecoveryPlan {
+    /// Swap the contract belongs to, if recorded on the swapcoin.
+    pub swap_id: Option<String>,
+    /// The contract output.
+    pub outpoint: OutPoint,
+    /// Value locked in the contract output.
+    pub amount: Amount,
+    /// Script path the recovery transaction spends.
+    pub spend_path: ContractSpendPath,
+    /// Chain height from which the recovery transaction can be broadcast.
+    /// `None` for hashlock spends, which are valid immediately.
+    pub spendable_at_height: Option<u32>,
+    /// Blocks to wait before the recovery transaction can be broadcast (0 if it can be now).
+    pub blocks_remaining: u32,
+    /// The signed recovery transaction, or `None` if it could not be built.
+    pub recovery_tx: Option<Transaction>,
+}
+
 /// Represents total wallet balances of different categories.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Balances {
@@ -309,6 +352,7 @@ impl<B: BlockchainBackend> Wallet<B> {
             wallet_file_path: path.to_path_buf(),
             store,
             store_enc_material,
+            last_sync_duration: None,
         })
     }
 
@@ -341,6 +385,7 @@ impl<B: BlockchainBackend> Wallet<B> {
             wallet_file_path: path.to_path_buf(),
             store,
             store_enc_material,
+            last_sync_duration: None,
         })
     }
 
@@ -371,6 +416,11 @@ impl<B: BlockchainBackend> Wallet<B> {
         &self.store.file_name
     }
 
+    /// The network the wallet operates on.
+    pub fn network(&self) -> bitcoin::Network {
+        self.store.network
+    }
+
     /// Persist wallet data to disk, creating missing parent directories and file as needed.
     pub(crate) fn save_to_disk(&self) -> Result<(), WalletError> {
         self.store
@@ -407,8 +457,7 @@ impl<B: BlockchainBackend> Wallet<B> {
         );
     }
 
-    /// Finds a incoming swap coin by swap_id.
-    #[allow(dead_code)]
+    /// Finds a incoming swap coin by contract txid.
     pub(crate) fn find_incoming_swapcoin(
         &self,
         contract_txid: &str,
@@ -424,6 +473,14 @@ impl<B: BlockchainBackend> Wallet<B> {
         self.store.incoming_swapcoins.get_mut(contract_txid)
     }
 
+    /// Finds a outgoing swap coin by contract txid.
+    pub(crate) fn find_outgoing_swapcoin(
+        &self,
+        contract_txid: &str,
+    ) -> Option<&super::swapcoin::OutgoingSwapCoin> {
+        self.store.outgoing_swapcoins.get(contract_txid)
+    }
+
     /// Finds a outgoing swap coin by multisig redeemscript.
     pub(crate) fn find_outgoing_swapcoin_by_multisig(
         &self,
@@ -487,6 +544,13 @@ impl<B: BlockchainBackend> Wallet<B> {
         removed
     }
 
+    /// Assign `swap_id` to the persisted swapcoins without one whose contract txid is in
+    /// `contract_txids`, e.g. those listed in the swap's tracker record. Returns how many
+    /// were assigned.
+    pub(crate) fn assign_swap_id(&mut self, swap_id: &str, contract_txids: &[Txid]) -> usize {
+        self.store.assign_swap_id(swap_id, contract_txids)
+    }
+
     /// Adds watch-only swapcoins for a given swap.
     pub(crate) fn add_watchonly_swapcoins(
         &mut self,
@@ -502,6 +566,18 @@ impl<B: BlockchainBackend> Wallet<B> {
         log::info!("Added {} watch-only swapcoins for swap {}", count, swap_id);
     }
 
+    /// Returns the watch-only swapcoins of a given swap.
+    pub(crate) fn watchonly_swapcoins_for_swap(
+        &self,
+        swap_id: &str,
+    ) -> Vec<super::swapcoin::WatchOnlySwapCoin> {
+        self.store
+            .watchonly_swapcoins
+            .get(swap_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Removes watch-only swapcoins for a given swap.
     pub(crate) fn remove_watchonly_swapcoins(
         &mut self,
@@ -520,11 +596,12 @@ impl<B: BlockchainBackend> Wallet<B> {
         self.store.outgoing_swapcoins.len()
     }
 
-    /// Returns contract outpoints for all persisted outgoing swapcoins.
-    pub(crate) fn outgoing_contract_outpoints(&self) -> Vec<OutPoint> {
+    /// Returns contract outpoints for persisted outgoing swapcoins, optionally only those of one swap.
+    pub(crate) fn outgoing_contract_outpoints(&self, swap_id: Option<&str>) -> Vec<OutPoint> {
         self.store
             .outgoing_swapcoins
             .values()
+            .filter(|sc| swap_id.is_none() || sc.swap_id.as_deref() == swap_id)
             .map(|sc| OutPoint {
                 txid: sc.contract_tx.compute_txid(),
                 vout: 0,
@@ -532,11 +609,12 @@ impl<B: BlockchainBackend> Wallet<B> {
             .collect()
     }
 
-    /// Returns contract outpoints for all persisted incoming swapcoins.
-    pub(crate) fn incoming_contract_outpoints(&self) -> Vec<OutPoint> {
+    /// Returns contract outpoints for persisted incoming swapcoins, optionally only those of one swap.
+    pub(crate) fn incoming_contract_outpoints(&self, swap_id: Option<&str>) -> Vec<OutPoint> {
         self.store
             .incoming_swapcoins
             .values()
+            .filter(|sc| swap_id.is_none() || sc.swap_id.as_deref() == swap_id)
             .map(|sc| OutPoint {
                 txid: sc.contract_tx.compute_txid(),
                 vout: 0,
@@ -571,11 +649,21 @@ impl<B: BlockchainBackend> Wallet<B> {
     }
 
     /// Attempt to recover timelocked outgoing swapcoins.
-    #[hotpath::measure]
     pub fn recover_timelocked_swapcoins(
         &mut self,
         fee_rate: f64,
     ) -> Result<RecoveryOutcome, WalletError> {
+        self.recover_timelocked_swapcoins_for_swap(fee_rate, None)
+    }
+
+    /// Attempt to recover timelocked outgoing swapcoins, only those of `swap_id` if given.
+    #[hotpath::measure]
+    pub fn recover_timelocked_swapcoins_for_swap(
+        &mut self,
+        fee_rate: f64,
+        swap_id: Option<&str>,
+    ) -> Result<RecoveryOutcome, WalletError> {
+        let only_swap = swap_id;
         let mut outcome = RecoveryOutcome::default();
         let mut recovered_keys = Vec::new();
 
@@ -590,6 +678,9 @@ impl<B: BlockchainBackend> Wallet<B> {
         );
 
         for (swap_id, swapcoin) in &self.store.outgoing_swapcoins {
+            if only_swap.is_some() && swapcoin.swap_id.as_deref() != only_swap {
+                continue;
+            }
             if swapcoin.my_privkey.is_some() {
                 if let Some(timelock) = swapcoin.get_timelock() {
                     if swapcoin.protocol == crate::protocol::ProtocolVersion::Taproot {
@@ -908,6 +999,87 @@ impl<B: BlockchainBackend> Wallet<B> {
         swapcoin.sign_timelock_recovery(recovery_tx)
     }
 
+    /// Work out how every live contract output (see [`Self::list_live_timelock_contract_spend_info`]
+    /// and [`Self::list_live_hashlock_contract_spend_info`]) would be reclaimed, optionally only
+    /// those of one swap. Recovery transactions are signed but never broadcast.
+    pub fn plan_contract_recovery(
+        &self,
+        swap_id: Option<&str>,
+        fee_rate: f64,
+    ) -> Result<Vec<ContractRecoveryPlan>, WalletError> {
+        let current_height = self.rpc.get_block_count()? as u32;
+        let mut plans = Vec::new();
+
+        for (utxo, _) in self.list_live_timelock_contract_spend_info() {
+            let Some(swapcoin) = self.store.outgoing_swapcoins.values().find(|sc| {
+                sc.contract_tx.compute_txid() == utxo.txid
+                    && sc.get_contract_output_vout() == utxo.vout
+            }) else {
+                continue;
+            };
+            if swap_id.is_some() && swapcoin.swap_id.as_deref() != swap_id {
+                continue;
+            }
+            // Taproot contracts use an absolute CLTV height. Legacy contracts use a CSV
+            // delay, which recovery waits out as confirmations of the contract output.
+            let spendable_at_height = swapcoin.get_timelock().map(|timelock| {
+                if swapcoin.protocol == crate::protocol::ProtocolVersion::Taproot {
+                    timelock
+                } else {
+                    (current_height + timelock).saturating_sub(utxo.confirmations)
+                }
+            });
+            let recovery_tx = self
+                .create_timelock_recovery_tx(swapcoin, fee_rate)
+                .map_err(|e| log::warn!("Failed to build recovery tx for {}: {:?}", utxo.txid, e))
+                .ok();
+            plans.push(ContractRecoveryPlan {
+                swap_id: swapcoin.swap_id.clone(),
+                outpoint: OutPoint::new(utxo.txid, utxo.vout),
+                amount: utxo.amount,
+                spend_path: ContractSpendPath::Timelock,
+                spendable_at_height,
+                blocks_remaining: spendable_at_height
+                    .map_or(0, |height| height.saturating_sub(current_height)),
+                recovery_tx,
+            });
+        }
+
+        for (utxo, _) in self.list_live_hashlock_contract_spend_info() {
+            let Some(swapcoin) = self.store.incoming_swapcoins.values().find(|sc| {
+                sc.contract_tx.compute_txid() == utxo.txid
+                    && sc.get_contract_output_vout() == utxo.vout
+            }) else {
+                continue;
+            };
+            if swap_id.is_some() && swapcoin.swap_id.as_deref() != swap_id {
+                continue;
+            }
+            let recovery_tx = self
+                .get_next_internal_addresses(1, AddressType::P2TR)?
+                .first()
+                .and_then(|address| {
+                    swapcoin
+                        .sign_spend_transaction(utxo.amount, &address.script_pubkey(), fee_rate)
+                        .map_err(|e| {
+                            log::warn!("Failed to build sweep tx for {}: {:?}", utxo.txid, e)
+                        })
+                        .ok()
+                });
+            plans.push(ContractRecoveryPlan {
+                swap_id: swapcoin.swap_id.clone(),
+                outpoint: OutPoint::new(utxo.txid, utxo.vout),
+                amount: utxo.amount,
+                spend_path: ContractSpendPath::Hashlock,
+                spendable_at_height: None,
+                blocks_remaining: 0,
+                recovery_tx,
+            });
+        }
+
+        Ok(plans)
+    }
+
     /// Calculates the total balances of different categories in the wallet.
     /// Includes regular, swap, contract, fidelity, and spendable (regular + swap) utxos.
     /// Optionally takes in a list of UTXOs to reduce rpc call. If None is provided, the full list is fetched from core rpc.
@@ -1230,13 +1402,19 @@ impl<B: BlockchainBackend> Wallet<B> {
     }
 
     /// Checks if a UTXO belongs to fidelity bonds, and then returns corresponding UTXOSpendInfo
+    ///
+    /// Bonds held by an external key are not the wallet's to spend, so their UTXOs (which the
+    /// Electrum backend reports as it watches their scripts) are not counted.
     fn check_if_fidelity(&self, utxo: &ListUnspentResultEntry) -> Option<UTXOSpendInfo> {
         self.store
             .fidelity_bond
             .iter()
             .enumerate()
             .find_map(|(i, bond)| {
-                if bond.script_pub_key() == utxo.script_pub_key && bond.amount == utxo.amount {
+                if !bond.external_key
+                    && bond.script_pub_key() == utxo.script_pub_key
+                    && bond.amount == utxo.amount
+                {
                     Some(UTXOSpendInfo::FidelityBondCoin {
                         index: i as u32,
                         input_value: bond.amount,
@@ -2551,11 +2729,62 @@ impl<B: BlockchainBackend> Wallet<B> {
     pub fn send_tx(&self, tx: &Transaction) -> Result<Txid, WalletError> {
         Ok(self.rpc.send_raw_transaction(tx)?)
     }
+
+    /// Mining fee paid by a transaction found on-chain or in the mempool.
+    ///
+    /// Input values are looked up from the previous transactions, so this relies on the
+    /// backend being able to serve arbitrary transactions (`txindex` for Bitcoin Core).
+    pub fn transaction_fee(&self, txid: &Txid) -> Result<Amount, WalletError> {
+        let tx = self.rpc.get_raw_transaction(txid, None)?;
+        let mut input_value = Amount::ZERO;
+        for input in &tx.input {
+            let prev_tx = self
+                .rpc
+                .get_raw_transaction(&input.previous_output.txid, None)?;
+            let prev_out = prev_tx
+                .output
+                .get(input.previous_output.vout as usize)
+                .ok_or(WalletError::General(format!(
+                    "Missing output {} spent by {txid}",
+                    input.previous_output
+                )))?;
+            input_value += prev_out.value;
+        }
+        let output_value = tx.output.iter().map(|o| o.value).sum::<Amount>();
+        input_value
+            .checked_sub(output_value)
+            .ok_or(WalletError::General(format!(
+                "Transaction {txid} spends more than its inputs"
+            )))
+    }
+
     /// Sweeps all completed incoming swap coins.
-    #[hotpath::measure]
     pub fn sweep_incoming_swapcoins(
         &mut self,
         feerate: f64,
+    ) -> Result<RecoveryOutcome, WalletError> {
+        self.sweep_incoming_swapcoins_for_swap(feerate, None)
+    }
+
+    /// Sweeps completed incoming swap coins, only those of `swap_id` if given.
+    pub fn sweep_incoming_swapcoins_for_swap(
+        &mut self,
+        feerate: f64,
+        swap_id: Option<&str>,
+    ) -> Result<RecoveryOutcome, WalletError> {
+        self.sweep_incoming_swapcoins_to(feerate, swap_id, &SweepDestination::Wallet)
+    }
+
+    /// Sweeps completed incoming swap coins, only those of `swap_id` if given, to `destination`.
+    ///
+    /// Each swapcoin is swept in its own transaction. With a descriptor destination, the
+    /// n-th successful sweep pays derivation index `next_index + n`.
+    #[hotpath::measure]
+    pub fn sweep_incoming_swapcoins_to(
+        &mut self,
+        feerate: f64,
+        swap_id: Option<&str>,
+        destination: &SweepDestination,
     ) -> Result<RecoveryOutcome, WalletError> {
         let mut outcome = RecoveryOutcome::default();
 
@@ -2563,6 +2792,7 @@ impl<B: BlockchainBackend> Wallet<B> {
             .store
             .incoming_swapcoins
             .iter()
+            .filter(|(_, swapcoin)| swap_id.is_none() || swapcoin.swap_id.as_deref() == swap_id)
             .filter(|(_, swapcoin)| {
                 swapcoin.other_privkey.is_some() || swapcoin.hash_preimage.is_some()
             })
@@ -2747,21 +2977,40 @@ impl<B: BlockchainBackend> Wallet<B> {
                 }
             }
 
-            // Get next internal address for receiving the swept funds
-            let internal_address =
-                self.get_next_internal_addresses(1, AddressType::P2TR)?[0].clone();
+            let sweep_address = match destination {
+                // Get next internal address for receiving the swept funds
+                SweepDestination::Wallet => {
+                    self.get_next_internal_addresses(1, AddressType::P2TR)?[0].clone()
+                }
+                SweepDestination::Address(address) => address.clone(),
+                SweepDestination::Descriptor {
+                    descriptor,
+                    next_index,
+                } => {
+                    let index = next_index + outcome.paid_out.len() as u32;
+                    self.rpc
+                        .derive_addresses(descriptor, Some([index, index]))?[0]
+                        .clone()
+                        .assume_checked()
+                }
+            };
 
             log::info!(
-                "Sweeping incoming swap coin {} (utxo: {}:{}) to internal address {}",
+                "Sweeping incoming swap coin {} (utxo: {}:{}) to {} address {}",
                 swap_id,
                 utxo_txid,
                 utxo_vout,
-                internal_address
+                if destination.is_external() {
+                    "external"
+                } else {
+                    "internal"
+                },
+                sweep_address
             );
 
             match swapcoin.sign_spend_transaction(
                 input_value,
-                &internal_address.script_pubkey(),
+                &sweep_address.script_pubkey(),
                 feerate,
             ) {
                 Ok(spend_tx) => {
@@ -2781,11 +3030,15 @@ impl<B: BlockchainBackend> Wallet<B> {
                             // Remove the swapcoin from wallet
                             self.remove_incoming_swapcoin(&swap_id);
 
-                            // Track the output scriptpubkey to prevent mixing with regular UTXOs
-                            let output_scriptpubkey = internal_address.script_pubkey();
-                            self.store
-                                .swept_incoming_swapcoins
-                                .insert(output_scriptpubkey);
+                            if destination.is_external() {
+                                let amount = spend_tx.output.iter().map(|o| o.value).sum();
+                                outcome.paid_out.push((sweep_address, amount));
+                            } else {
+                                // Track the output scriptpubkey to prevent mixing with regular UTXOs
+                                self.store
+                                    .swept_incoming_swapcoins
+                                    .insert(sweep_address.script_pubkey());
+                            }
                         }
                         Err(e) => {
                             log::warn!(
@@ -100,6 +100,7 @@ impl<B: BlockchainBackend> Wallet<B> {
             wallet_file_path: wallet_path.to_path_buf(),
             store,
             store_enc_material: restored_enc_material,
+            last_sync_duration: None,
         };
         tmp_wallet.sync_and_save()?;
         Ok(tmp_wallet)
@@ -1,7 +1,7 @@
 use crate::{
     protocol::common_messages::FidelityProof,
     utill::{redeemscript_to_scriptpubkey, MIN_FEE_RATE},
-    wallet::{rpc::BlockchainBackend, AddressType, Wallet},
+    wallet::{rpc::BlockchainBackend, AddressType, UTXOSpendInfo, Wallet},
 };
 use bitcoin::{
     absolute::LockTime,
@@ -10,8 +10,11 @@ use bitcoin::{
     hashes::{sha256d, Hash},
     opcodes::all::{OP_CHECKSIGVERIFY, OP_CLTV},
     script::{Builder, Instruction},
-    secp256k1::{Keypair, Message, Secp256k1},
-    Address, Amount, OutPoint, PublicKey, ScriptBuf, Transaction, Txid,
+    secp256k1::{ecdsa::Signature, Keypair, Message, Secp256k1},
+    sign_message::MessageSignature,
+    transaction::Version,
+    Address, Amount, EcdsaSighashType, OutPoint, Psbt, PublicKey, ScriptBuf, Sequence, Transaction,
+    TxIn, TxOut, Txid, Witness,
 };
 use serde::{Deserialize, Serialize};
 use std::{
@@ -59,6 +62,8 @@ pub enum FidelityError {
     General(String),
     InvalidBondLocktime,
     BondUncomfirmed,
+    ExternalKey,
+    MissingCertificate,
 }
 
 impl std::fmt::Display for FidelityError {
@@ -75,6 +80,11 @@ impl std::fmt::Display for FidelityError {
                 write!(f, "Fidelity bond locktime is outside the acceptable range")
             }
             FidelityError::BondUncomfirmed => write!(f, "Fidelity bond transaction is unconfirmed"),
+            FidelityError::ExternalKey => write!(f, "Fidelity bond key is held outside the wallet"),
+            FidelityError::MissingCertificate => write!(
+                f,
+                "Fidelity bond certificate is not signed for the current maker address"
+            ),
             FidelityError::General(msg) => write!(f, "{}", msg),
         }
     }
@@ -140,28 +150,32 @@ pub(crate) fn verify_fidelity_checks(
     // Verify fidelity pubkey derivation from tweak key.
     // Only public_key and chain_code are used in BIP32 derivation;
     // network, depth, parent_fingerprint, child_number are metadata only.
-    let offer_xpub = bitcoin::bip32::Xpub {
-        network: bitcoin::NetworkKind::Main,
-        depth: 1,
-        parent_fingerprint: Default::default(),
-        child_number: ChildNumber::Hardened { index: 0 },
-        public_key: tweakable_point.inner,
-        chain_code: *tweak_chain_code,
-    };
+    // Bonds held by an external key are not derived from the maker's wallet: they are tied to
+    // the maker only by the certificate signature verified below.
     let secp = Secp256k1::new();
-    let derived = offer_xpub.derive_pub(
-        &secp,
-        &[
-            ChildNumber::Normal { index: 2 },
-            ChildNumber::Normal {
-                index: proof.bond.bond_index,
-            },
-        ],
-    )?;
-    if derived.public_key != proof.bond.pubkey.inner {
-        return Err(WalletError::General(
-            "Fidelity bond does not correspond to the provided tweak point".to_string(),
-        ));
+    if !proof.bond.external_key {
+        let offer_xpub = bitcoin::bip32::Xpub {
+            network: bitcoin::NetworkKind::Main,
+            depth: 1,
+            parent_fingerprint: Default::default(),
+            child_number: ChildNumber::Hardened { index: 0 },
+            public_key: tweakable_point.inner,
+            chain_code: *tweak_chain_code,
+        };
+        let derived = offer_xpub.derive_pub(
+            &secp,
+            &[
+                ChildNumber::Normal { index: 2 },
+                ChildNumber::Normal {
+                    index: proof.bond.bond_index,
+                },
+            ],
+        )?;
+        if derived.public_key != proof.bond.pubkey.inner {
+            return Err(WalletError::General(
+                "Fidelity bond does not correspond to the provided tweak point".to_string(),
+            ));
+        }
     }
 
     // Validate redeem script and corresponding output scriptPubKey
@@ -245,9 +259,106 @@ pub struct FidelityBond {
     /// The child index used in the HD derivation path `m/175'/2/<bond_index>`.
     /// Note: Fidelity bonds must only be appended to the store; they should never be removed or reordered.
     pub bond_index: u32,
+    /// Whether the bond key is held outside the wallet, e.g. in an offline wallet, instead of
+    /// being derived at `m/175'/2/<bond_index>`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) external_key: bool,
+    /// Certificate signed with the external key. Never part of a [`FidelityProof`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) external_cert: Option<ExternalFidelityCert>,
+}
+
+/// A fidelity bond certificate signed outside the wallet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Hash)]
+pub struct ExternalFidelityCert {
+    /// Maker address the certificate was signed for.
+    pub maker_address: String,
+    /// Signature over the certificate hash with the bond key.
+    pub cert_sig: Signature,
+}
+
+/// Certificate message of a bond, to be signed by its external key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FidelityCertRequest {
+    /// Index of the bond.
+    pub index: u32,
+    /// Public key the certificate must be signed with.
+    pub pubkey: PublicKey,
+    /// Certificate message, to be signed as a Bitcoin signed message (`signmessage`).
+    pub message: String,
+    /// Hash of the signed message, for signers that sign a raw digest.
+    pub cert_hash: sha256d::Hash,
+}
+
+/// Lifecycle state of a fidelity bond.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FidelityBondStatus {
+    /// Broadcast, but not yet confirmed.
+    Unconfirmed,
+    /// Confirmed and still timelocked. Only live bonds can be advertised.
+    Live,
+    /// The timelock has passed and the bond can be redeemed.
+    Expired,
+    /// The bond has been spent.
+    Redeemed,
+}
+
+impl std::fmt::Display for FidelityBondStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unconfirmed => write!(f, "Unconfirmed"),
+            Self::Live => write!(f, "Live"),
+            Self::Expired => write!(f, "Expired"),
+            Self::Redeemed => write!(f, "Redeemed"),
+        }
+    }
+}
+
+/// Rough human readable duration of a number of blocks, assuming 10 minutes per block.
+fn blocks_to_duration_string(blocks: u32) -> String {
+    let minutes = blocks as u64 * 10;
+    let (days, hours) = (minutes / (24 * 60), (minutes % (24 * 60)) / 60);
+    format!("~{days}d {hours}h")
+}
+
+/// Parse a certificate signature, either as produced by `signmessage` (base64) or as a
+/// DER-encoded hex ECDSA signature.
+pub fn parse_cert_signature(sig: &str) -> Result<Signature, WalletError> {
+    let sig = sig.trim();
+    let mut sig = match MessageSignature::from_base64(sig) {
+        Ok(message_sig) => message_sig.signature.to_standard(),
+        Err(_) => Signature::from_str(sig).map_err(|_| {
+            WalletError::General(
+                "Certificate signature is neither a signed message nor a DER hex signature"
+                    .to_string(),
+            )
+        })?,
+    };
+    sig.normalize_s();
+    Ok(sig)
 }
 
 impl FidelityBond {
+    /// The state of this bond at the given block height.
+    pub fn status(&self, current_height: u32) -> FidelityBondStatus {
+        if self.is_spent {
+            FidelityBondStatus::Redeemed
+        } else if self.conf_height.is_none() {
+            FidelityBondStatus::Unconfirmed
+        } else if current_height > self.lock_time.to_consensus_u32() {
+            FidelityBondStatus::Expired
+        } else {
+            FidelityBondStatus::Live
+        }
+    }
+
+    /// Blocks remaining until the timelock expires, zero once it has.
+    pub fn blocks_to_expiry(&self, current_height: u32) -> u32 {
+        self.lock_time
+            .to_consensus_u32()
+            .saturating_sub(current_height)
+    }
+
     /// The UTXO outpoint backing this bond.
     pub fn outpoint(&self) -> OutPoint {
         self.outpoint
@@ -267,16 +378,26 @@ impl FidelityBond {
         redeemscript_to_scriptpubkey(&self.redeem_script()).expect("This can never panic as fidelity redeemscript template is hardcoded in a private function.")
     }
 
+    /// Whether the bond key is held outside the wallet.
+    pub fn has_external_key(&self) -> bool {
+        self.external_key
+    }
+
+    /// The certificate message binding this bond to a maker address.
+    pub(crate) fn cert_message(&self, addr: &str, tweakable_point: &PublicKey) -> String {
+        format!(
+            "fidelity-bond-cert|{}|{}|{}|{}|{}|{}",
+            self.outpoint, self.pubkey, self.lock_time, self.amount, addr, tweakable_point
+        )
+    }
+
     /// Generate the bond's certificate hash.
     pub(crate) fn generate_cert_hash(
         &self,
         addr: &str,
         tweakable_point: &PublicKey,
     ) -> sha256d::Hash {
-        let cert_msg_str = format!(
-            "fidelity-bond-cert|{}|{}|{}|{}|{}|{}",
-            self.outpoint, self.pubkey, self.lock_time, self.amount, addr, tweakable_point
-        );
+        let cert_msg_str = self.cert_message(addr, tweakable_point);
         let cert_msg = cert_msg_str.as_bytes();
         let mut btc_signed_msg = Vec::<u8>::new();
         btc_signed_msg.extend("\x18Bitcoin Signed Message:\n".as_bytes());
@@ -296,31 +417,88 @@ impl<B: BlockchainBackend> Wallet<B> {
 
     /// Display the fidelity bonds
     pub fn display_fidelity_bonds(&self) -> Result<String, WalletError> {
-        let serialized = self
+        serde_json::to_string_pretty(&self.fidelity_bonds_info()?)
+            .map_err(|e| WalletError::General(e.to_string()))
+    }
+
+    /// Status, value and time to expiry of every fidelity bond, as JSON objects.
+    pub fn fidelity_bonds_info(&self) -> Result<Vec<serde_json::Value>, WalletError> {
+        let current_height = self.rpc.get_block_count()? as u32;
+
+        Ok(self
             .store
             .fidelity_bond
             .iter()
             .enumerate()
             .map(|(index, bond)| {
+                let status = bond.status(current_height);
                 let mut bond_info = serde_json::json!({
                         "index": index,
                         "outpoint": bond.outpoint.to_string(),
                         "amount": bond.amount.to_sat(),
-                        "status": if bond.is_spent {"Redeemed"} else {"Live"}
+                        "locktime": bond.lock_time.to_consensus_u32(),
+                        "conf_height": bond.conf_height,
+                        "status": status.to_string(),
                 });
 
-                if !bond.is_spent {
-                    let bond_value = self
-                        .calculate_bond_value(bond)
-                        .expect("Bond value calculation must not fail for valid bonds.");
-                    bond_info["bond_value"] = serde_json::json!(bond_value);
+                if bond.external_key {
+                    bond_info["external_key"] = serde_json::json!(true);
+                    bond_info["certified"] = serde_json::json!(bond.external_cert.is_some());
+                }
+
+                if status != FidelityBondStatus::Redeemed {
+                    let blocks_to_expiry = bond.blocks_to_expiry(current_height);
+                    bond_info["blocks_to_expiry"] = serde_json::json!(blocks_to_expiry);
+                    bond_info["expires_in"] =
+                        serde_json::json!(blocks_to_duration_string(blocks_to_expiry));
+                }
+
+                if status == FidelityBondStatus::Live {
+                    match self.calculate_bond_value(bond) {
+                        Ok(bond_value) => {
+                            bond_info["bond_value"] = serde_json::json!(bond_value.to_sat())
+                        }
+                        Err(e) => log::warn!("Fidelity valuation failed for index {index}: {e:?}"),
+                    }
                 }
 
                 bond_info
             })
-            .collect::<Vec<serde_json::Value>>();
+            .collect())
+    }
+
+    /// Record the confirmation height of unspent bonds that were broadcast but not yet seen
+    /// confirmed. Returns the indices of the bonds that got confirmed.
+    pub fn confirm_pending_fidelity_bonds(&mut self) -> Result<Vec<u32>, WalletError> {
+        let current_height = self.rpc.get_block_count()? as u32;
 
-        serde_json::to_string_pretty(&serialized).map_err(|e| WalletError::General(e.to_string()))
+        let pending = self
+            .store
+            .fidelity_bond
+            .iter()
+            .enumerate()
+            .filter(|(_, bond)| bond.status(current_height) == FidelityBondStatus::Unconfirmed)
+            .map(|(i, bond)| (i as u32, bond.outpoint.txid))
+            .collect::<Vec<_>>();
+
+        let mut confirmed = Vec::new();
+        for (index, txid) in pending {
+            let confirmations = match self.rpc.get_raw_transaction_info(&txid, None) {
+                Ok(info) => info.confirmations.unwrap_or(0),
+                Err(e) => {
+                    log::debug!("Fidelity tx {txid} not found yet: {e:?}");
+                    continue;
+                }
+            };
+            if confirmations > 0 {
+                let conf_height = current_height.saturating_sub(confirmations) + 1;
+                self.update_fidelity_bond_conf_details(index, conf_height)?;
+                log::info!("Fidelity Bond at index: {index} confirmed at height {conf_height}");
+                confirmed.push(index);
+            }
+        }
+
+        Ok(confirmed)
     }
 
     /// Get the highest value fidelity bond. Returns None, if no bond exists.
@@ -331,7 +509,8 @@ impl<B: BlockchainBackend> Wallet<B> {
             .iter()
             .enumerate()
             .filter_map(|(i, bond)| {
-                if !bond.is_spent {
+                // An external bond can't be advertised until its certificate is imported.
+                if !bond.is_spent && (!bond.external_key || bond.external_cert.is_some()) {
                     match self.calculate_bond_value(bond) {
                         Ok(v) => {
                             log::info!("Fidelity Bond found | Index: {i} | Bond Value : {v}");
@@ -489,6 +668,8 @@ impl<B: BlockchainBackend> Wallet<B> {
                 conf_height: None,
                 is_spent: false,
                 bond_index: index,
+                external_key: false,
+                external_cert: None,
             };
             self.store.fidelity_bond.push(bond);
             self.save_to_disk()?;
@@ -529,7 +710,11 @@ impl<B: BlockchainBackend> Wallet<B> {
             .iter()
             .enumerate()
             .filter_map(|(i, bond)| {
-                if !bond.is_spent && curr_height > bond.lock_time.to_consensus_u32() {
+                // External bonds are redeemed by their key holder, see `fidelity_redeem_psbt`.
+                if !bond.is_spent
+                    && !bond.external_key
+                    && curr_height > bond.lock_time.to_consensus_u32()
+                {
                     Some(i as u32)
                 } else {
                     None
@@ -561,24 +746,257 @@ impl<B: BlockchainBackend> Wallet<B> {
             return Err(FidelityError::BondAlreadyRedeemed.into());
         }
 
-        let fidelity_privkey = self.get_fidelity_keypair(index)?.secret_key();
         let (_, tweakable_point, _) = self.get_tweakable_keypair()?;
 
         let cert_hash = bond.generate_cert_hash(maker_addr, &tweakable_point);
 
-        let secp = Secp256k1::new();
-        let cert_sig = secp.sign_ecdsa_low_r(
-            &Message::from_digest_slice(cert_hash.as_byte_array())?,
-            &fidelity_privkey,
-        );
+        let cert_sig = if bond.external_key {
+            match &bond.external_cert {
+                Some(cert) if cert.maker_address == maker_addr => cert.cert_sig,
+                _ => return Err(FidelityError::MissingCertificate.into()),
+            }
+        } else {
+            let fidelity_privkey = self.get_fidelity_keypair(index)?.secret_key();
+            let secp = Secp256k1::new();
+            secp.sign_ecdsa_low_r(
+                &Message::from_digest_slice(cert_hash.as_byte_array())?,
+                &fidelity_privkey,
+            )
+        };
 
+        let mut bond = bond.clone();
+        bond.external_cert = None;
         Ok(FidelityProof {
-            bond: bond.clone(),
+            bond,
             cert_hash,
             cert_sig,
         })
     }
 
+    /// Register a bond locked to an external key, e.g. one held in an offline wallet.
+    ///
+    /// The wallet only watches the bond: it is advertised once its certificate is imported with
+    /// [`Wallet::import_fidelity_cert`], and redeemed by the key holder from the PSBT built by
+    /// [`Wallet::fidelity_redeem_psbt`]. Returns the index of the bond.
+    pub fn import_fidelity_bond(
+        &mut self,
+        outpoint: OutPoint,
+        pubkey: PublicKey,
+        locktime: LockTime,
+    ) -> Result<u32, WalletError> {
+        if !locktime.is_block_height() {
+            return Err(WalletError::General(
+                "fidelity locktime must be height-based".to_string(),
+            ));
+        }
+        if self
+            .store
+            .fidelity_bond
+            .iter()
+            .any(|bond| bond.outpoint == outpoint)
+        {
+            return Err(WalletError::General(format!(
+                "Fidelity bond {outpoint} is already registered"
+            )));
+        }
+
+        let tx = self.rpc.get_raw_transaction(&outpoint.txid, None)?;
+        let tx_out = tx
+            .output
+            .get(outpoint.vout as usize)
+            .ok_or(FidelityError::BondDoesNotExist)?;
+        let index = self.store.fidelity_bond.len() as u32;
+        let mut bond = FidelityBond {
+            outpoint,
+            amount: tx_out.value,
+            lock_time: locktime,
+            pubkey,
+            conf_height: None,
+            is_spent: false,
+            bond_index: index,
+            external_key: true,
+            external_cert: None,
+        };
+        if tx_out.script_pubkey != bond.script_pub_key() {
+            return Err(FidelityError::WrongScriptType.into());
+        }
+        if self
+            .rpc
+            .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+            .is_none()
+        {
+            return Err(FidelityError::BondAlreadyRedeemed.into());
+        }
+
+        // Takers check the timelock from the confirmation height, or it would be from the next block.
+        let current_height = self.rpc.get_block_count()? as u32;
+        let confirmations = self
+            .rpc
+            .get_raw_transaction_info(&outpoint.txid, None)
+            .ok()
+            .and_then(|info| info.confirmations)
+            .unwrap_or(0);
+        let start_height = if confirmations > 0 {
+            let conf_height = current_height.saturating_sub(confirmations) + 1;
+            bond.conf_height = Some(conf_height);
+            conf_height
+        } else {
+            current_height + 1
+        };
+        let timelock = locktime.to_consensus_u32().saturating_sub(start_height);
+        if !(MIN_FIDELITY_TIMELOCK..=MAX_FIDELITY_TIMELOCK).contains(&timelock) {
+            return Err(FidelityError::InvalidBondLocktime.into());
+        }
+
+        self.rpc.watch_script(&bond.script_pub_key(), None);
+        self.store.fidelity_bond.push(bond);
+        self.save_to_disk()?;
+
+        Ok(index)
+    }
+
+    /// The certificate message the external key of the bond at `index` must sign for
+    /// `maker_addr`.
+    pub fn fidelity_cert_request(
+        &self,
+        index: u32,
+        maker_addr: &str,
+    ) -> Result<FidelityCertRequest, WalletError> {
+        let bond = self.external_fidelity_bond(index)?;
+        let (_, tweakable_point, _) = self.get_tweakable_keypair()?;
+
+        Ok(FidelityCertRequest {
+            index,
+            pubkey: bond.pubkey,
+            message: bond.cert_message(maker_addr, &tweakable_point),
+            cert_hash: bond.generate_cert_hash(maker_addr, &tweakable_point),
+        })
+    }
+
+    /// Store the certificate signature of the external bond at `index` for `maker_addr`,
+    /// after checking it against the bond key.
+    pub fn import_fidelity_cert(
+        &mut self,
+        index: u32,
+        maker_addr: &str,
+        cert_sig: Signature,
+    ) -> Result<(), WalletError> {
+        let cert_hash = self.fidelity_cert_request(index, maker_addr)?.cert_hash;
+        let bond = self.external_fidelity_bond(index)?;
+
+        let secp = Secp256k1::new();
+        secp.verify_ecdsa(
+            &Message::from_digest_slice(cert_hash.as_byte_array())?,
+            &cert_sig,
+            &bond.pubkey.inner,
+        )?;
+
+        self.store.fidelity_bond[index as usize].external_cert = Some(ExternalFidelityCert {
+            maker_address: maker_addr.to_string(),
+            cert_sig,
+        });
+        self.save_to_disk()
+    }
+
+    /// Build an unsigned PSBT sweeping the external bond at `index` to `destination`.
+    ///
+    /// The transaction is final once the bond expires. The key holder signs it and completes the
+    /// witness with the signature and the bond's witness script.
+    pub fn fidelity_redeem_psbt(
+        &self,
+        index: u32,
+        destination: &Address,
+        feerate: f64,
+    ) -> Result<Psbt, WalletError> {
+        let bond = self.external_fidelity_bond(index)?;
+        if bond.is_spent {
+            return Err(FidelityError::BondAlreadyRedeemed.into());
+        }
+
+        let mut tx = Transaction {
+            version: Version::TWO,
+            lock_time: bond.lock_time,
+            input: vec![TxIn {
+                previous_output: bond.outpoint,
+                sequence: Sequence::ZERO,
+                script_sig: ScriptBuf::new(),
+                witness: Witness::new(),
+            }],
+            output: vec![TxOut {
+                script_pubkey: destination.script_pubkey(),
+                value: Amount::ZERO,
+            }],
+        };
+        let spend_info = UTXOSpendInfo::FidelityBondCoin {
+            index,
+            input_value: bond.amount,
+        };
+        // base * 4 + witness size + marker + flag
+        let vsize = (tx.base_size() * 4 + spend_info.estimate_witness_size() + 2).div_ceil(4);
+        let fee = Amount::from_sat((vsize as f64 * feerate).ceil() as u64);
+        if fee >= bond.amount {
+            return Err(WalletError::InsufficientFund {
+                available: bond.amount.to_sat(),
+                required: fee.to_sat(),
+            });
+        }
+        tx.output[0].value = bond.amount - fee;
+
+        let mut psbt =
+            Psbt::from_unsigned_tx(tx).map_err(|e| WalletError::General(e.to_string()))?;
+        psbt.inputs[0].witness_utxo = Some(TxOut {
+            value: bond.amount,
+            script_pubkey: bond.script_pub_key(),
+        });
+        psbt.inputs[0].witness_script = Some(bond.redeem_script());
+        psbt.inputs[0].sighash_type = Some(EcdsaSighashType::All.into());
+
+        Ok(psbt)
+    }
+
+    /// Mark the confirmed external bonds whose output was spent, i.e. redeemed by their key
+    /// holder, as spent. Returns their indices.
+    pub fn mark_redeemed_external_fidelity_bonds(&mut self) -> Result<Vec<u32>, WalletError> {
+        let candidates = self
+            .store
+            .fidelity_bond
+            .iter()
+            .enumerate()
+            .filter(|(_, bond)| bond.external_key && !bond.is_spent && bond.conf_height.is_some())
+            .map(|(i, bond)| (i as u32, bond.outpoint))
+            .collect::<Vec<_>>();
+
+        let mut redeemed = Vec::new();
+        for (index, outpoint) in candidates {
+            if self
+                .rpc
+                .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+                .is_none()
+            {
+                self.store.fidelity_bond[index as usize].is_spent = true;
+                log::info!("External fidelity bond at index: {index} was redeemed");
+                redeemed.push(index);
+            }
+        }
+
+        Ok(redeemed)
+    }
+
+    /// The bond at `index`, if it is held by an external key.
+    fn external_fidelity_bond(&self, index: u32) -> Result<&FidelityBond, WalletError> {
+        let bond = self
+            .store
+            .fidelity_bond
+            .get(index as usize)
+            .ok_or(FidelityError::BondDoesNotExist)?;
+        if !bond.external_key {
+            return Err(WalletError::General(format!(
+                "Fidelity bond {index} is held by the wallet, not an external key"
+            )));
+        }
+        Ok(bond)
+    }
+
     fn encode_fidelity_op_return(
         &self,
         onion: &str,
@@ -725,6 +1143,112 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_fidelity_bond_status() {
+        let mut bond = FidelityBond {
+            outpoint: OutPoint::null(),
+            amount: Amount::from_sat(100_000),
+            lock_time: LockTime::from_height(1_000).unwrap(),
+            pubkey: PublicKey::from_str(
+                "031499764842691088897cff51efd85347dd3215912cbb8fb9b121b1da3b15bec8",
+            )
+            .unwrap(),
+            conf_height: None,
+            is_spent: false,
+            bond_index: 0,
+            external_key: false,
+            external_cert: None,
+        };
+        assert_eq!(bond.status(900), FidelityBondStatus::Unconfirmed);
+
+        bond.conf_height = Some(850);
+        assert_eq!(bond.status(900), FidelityBondStatus::Live);
+        assert_eq!(bond.status(1_000), FidelityBondStatus::Live);
+        assert_eq!(bond.status(1_001), FidelityBondStatus::Expired);
+        assert_eq!(bond.blocks_to_expiry(900), 100);
+        assert_eq!(bond.blocks_to_expiry(1_001), 0);
+
+        bond.is_spent = true;
+        assert_eq!(bond.status(1_001), FidelityBondStatus::Redeemed);
+
+        assert_eq!(blocks_to_duration_string(0), "~0d 0h");
+        assert_eq!(blocks_to_duration_string(150), "~1d 1h");
+    }
+
+    #[test]
+    fn test_external_key_certificate() {
+        use bitcoin::{
+            bip32::ChainCode,
+            secp256k1::SecretKey,
+            sign_message::{signed_msg_hash, MessageSignature},
+        };
+
+        let secp = Secp256k1::new();
+        let offline_key = SecretKey::from_slice(&[7; 32]).unwrap();
+        let tweakable_point =
+            PublicKey::new(SecretKey::from_slice(&[9; 32]).unwrap().public_key(&secp));
+        let maker_addr = "maker.onion:6102";
+
+        let mut bond = FidelityBond {
+            outpoint: OutPoint::new(Txid::all_zeros(), 0),
+            amount: Amount::from_sat(100_000),
+            lock_time: LockTime::from_height(1_000 + MIN_FIDELITY_TIMELOCK + 10).unwrap(),
+            pubkey: PublicKey::new(offline_key.public_key(&secp)),
+            conf_height: Some(1_000),
+            is_spent: false,
+            bond_index: 3,
+            external_key: true,
+            external_cert: None,
+        };
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: bond.amount,
+                script_pubkey: bond.script_pub_key(),
+            }],
+        };
+
+        // The certificate is a regular Bitcoin signed message.
+        let message = bond.cert_message(maker_addr, &tweakable_point);
+        let cert_hash = bond.generate_cert_hash(maker_addr, &tweakable_point);
+        assert_eq!(signed_msg_hash(&message), cert_hash);
+
+        let recoverable = secp.sign_ecdsa_recoverable(
+            &Message::from_digest(cert_hash.to_byte_array()),
+            &offline_key,
+        );
+        let signed_message = MessageSignature::new(recoverable, true).to_base64();
+        let cert_sig = parse_cert_signature(&signed_message).unwrap();
+        assert_eq!(
+            parse_cert_signature(&cert_sig.to_string()).unwrap(),
+            cert_sig
+        );
+        assert!(parse_cert_signature("not a signature").is_err());
+
+        let verify = |bond: &FidelityBond| {
+            let proof = FidelityProof {
+                bond: bond.clone(),
+                cert_hash,
+                cert_sig,
+            };
+            verify_fidelity_checks(
+                &proof,
+                maker_addr,
+                tx.clone(),
+                1_100,
+                &tweakable_point,
+                &ChainCode::from([0; 32]),
+            )
+        };
+        assert!(verify(&bond).is_ok());
+
+        // Without the flag, the key must be derived from the maker's tweakable point.
+        bond.external_key = false;
+        assert!(verify(&bond).is_err());
+    }
 }
 
 #[test]
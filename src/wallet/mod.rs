@@ -13,10 +13,15 @@ mod split_utxos;
 mod storage;
 pub(crate) mod swapcoin;
 
-pub use api::{Balances, RecoveryOutcome, UTXOSpendInfo, Wallet};
+pub use api::{
+    Balances, ContractRecoveryPlan, ContractSpendPath, RecoveryOutcome, UTXOSpendInfo, Wallet,
+};
 pub use backup::WalletBackup;
 pub use error::WalletError;
-pub use fidelity::FidelityBond;
+pub use fidelity::{
+    parse_cert_signature, ExternalFidelityCert, FidelityBond, FidelityBondStatus,
+    FidelityCertRequest,
+};
 pub(crate) use fidelity::{
     verify_fidelity_checks, FidelityError, MAX_FIDELITY_TIMELOCK, MIN_FIDELITY_TIMELOCK,
 };
@@ -24,5 +29,5 @@ pub use report::{MakerFeeInfo, MakerReport, RecoveryReport, SwapRole, SwapStatus
 pub use rpc::{
     BackendConfig, BitcoindBackend, BlockchainBackend, ElectrumBackend, ElectrumConfig, RPCConfig,
 };
-pub use spend::Destination;
+pub use spend::{Destination, SweepDestination};
 pub use storage::AddressType;
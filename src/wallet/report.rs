@@ -165,6 +165,10 @@ pub struct TakerReport {
     pub output_change_utxos: Vec<(u64, String)>,
     /// Swap UTXOs with amounts and addresses.
     pub output_swap_utxos: Vec<(u64, String)>,
+
+    /// Swap IDs of the routes this report combines, for a multi-route swap.
+    #[serde(default)]
+    pub routes: Vec<String>,
 }
 
 impl TakerReport {
@@ -179,6 +183,16 @@ impl TakerReport {
         write_to_path(&file_path, |f| f.taker.push(report))
     }
 
+    /// Load all taker reports from the given wallet's report file, or the discovered/default
+    /// taker wallet if none is given. Returns an empty list if no report file exists yet.
+    pub fn load_for_wallet(
+        data_dir: &Path,
+        wallet_file_name: Option<&str>,
+    ) -> std::io::Result<Vec<TakerReport>> {
+        let file_path = report_file_path_for_wallet(data_dir, SwapRole::Taker, wallet_file_name);
+        Ok(read_from_path(&file_path)?.taker)
+    }
+
     /// Print a human-readable summary to stdout.
     pub fn print(&self) {
         println!("\n\x1b[1;36m================================================================================");
@@ -187,6 +201,12 @@ impl TakerReport {
 
         println!("\x1b[1;37mSwap ID           :\x1b[0m {}", self.swap_id);
         println!("\x1b[1;37mStatus            :\x1b[0m {}", self.status);
+        if !self.routes.is_empty() {
+            println!(
+                "\x1b[1;37mRoutes            :\x1b[0m {}",
+                self.routes.join(", ")
+            );
+        }
         if self.swap_duration_seconds > 0.0 {
             println!(
                 "\x1b[1;37mDuration          :\x1b[0m {:.2} seconds",
@@ -238,8 +258,13 @@ impl TakerReport {
         }
         if !self.funding_txids.is_empty() {
             println!("\x1b[1;37mFunding Txs       :\x1b[0m");
+            let label = if self.routes.is_empty() {
+                "Hop"
+            } else {
+                "Route"
+            };
             for (hop_idx, hop_txids) in self.funding_txids.iter().enumerate() {
-                println!("  Hop {}:", hop_idx + 1);
+                println!("  {} {}:", label, hop_idx + 1);
                 for (i, txid) in hop_txids.iter().enumerate() {
                     println!("    {}. {}", i + 1, txid);
                 }
@@ -710,12 +735,7 @@ where
 
     let _lock = acquire_lock(&lock_path)?;
 
-    let mut report_file: SwapReportFile = if file_path.exists() {
-        let content = std::fs::read_to_string(file_path)?;
-        serde_json::from_str(&content).map_err(std::io::Error::other)?
-    } else {
-        SwapReportFile::default()
-    };
+    let mut report_file = read_from_path(file_path)?;
 
     mutate(&mut report_file);
 
@@ -733,6 +753,14 @@ where
     Ok(())
 }
 
+fn read_from_path(file_path: &Path) -> std::io::Result<SwapReportFile> {
+    if !file_path.exists() {
+        return Ok(SwapReportFile::default());
+    }
+    let content = std::fs::read_to_string(file_path)?;
+    serde_json::from_str(&content).map_err(std::io::Error::other)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -777,6 +805,7 @@ mod tests {
             output_swap_amounts: vec![],
             output_change_utxos: vec![],
             output_swap_utxos: vec![],
+            routes: vec![],
         }
     }
 
@@ -800,4 +829,31 @@ mod tests {
 
         std::fs::remove_dir_all(data_dir).unwrap();
     }
+
+    #[test]
+    fn taker_reports_load_back_in_order() {
+        let data_dir = test_data_dir("taker_load_reports");
+        assert!(TakerReport::load_for_wallet(&data_dir, Some("bob.wallet"))
+            .unwrap()
+            .is_empty());
+
+        let first = sample_taker_report();
+        let mut second = sample_taker_report();
+        second.swap_id = "swap-2".to_string();
+        second.status = SwapStatus::Failed;
+        first
+            .save_for_wallet(&data_dir, Some("bob.wallet"))
+            .unwrap();
+        second
+            .save_for_wallet(&data_dir, Some("bob.wallet"))
+            .unwrap();
+
+        let loaded = TakerReport::load_for_wallet(&data_dir, Some("bob.wallet")).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].swap_id, "swap-1");
+        assert_eq!(loaded[1].swap_id, "swap-2");
+        assert_eq!(loaded[1].status, SwapStatus::Failed);
+
+        std::fs::remove_dir_all(data_dir).unwrap();
+    }
 }
@@ -9,6 +9,7 @@ use std::{
     fmt::{self, Debug},
     sync::Mutex,
     thread,
+    time::Instant,
 };
 
 use bitcoind::bitcoincore_rpc::{
@@ -751,7 +752,9 @@ impl<B: BlockchainBackend> Wallet<B> {
     /// Sync the wallet, then persist to disk.
     pub fn sync_and_save(&mut self) -> Result<(), WalletError> {
         log::info!("Sync Started for {:?}", &self.store.file_name);
+        let started = Instant::now();
         self.sync_no_fail();
+        self.last_sync_duration = Some(started.elapsed());
         self.save_to_disk()?;
         log::info!("Synced & Saved {:?}", &self.store.file_name);
         Ok(())
@@ -4,14 +4,16 @@
 //! direct sends. It leverages Bitcoin Core's RPC for wallet synchronization and implements various
 //! parsing mechanisms for transaction inputs and outputs.
 
+use std::str::FromStr;
+
 use bitcoin::{
-    absolute::LockTime, script::PushBytesBuf, transaction::Version, Address, Amount, OutPoint,
-    ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
+    absolute::LockTime, bip32::Xpub, script::PushBytesBuf, transaction::Version, Address, Amount,
+    Network, NetworkKind, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness,
 };
 use bitcoind::bitcoincore_rpc::json::ListUnspentResultEntry;
 
 use crate::{
-    utill::calculate_fee_sats,
+    utill::{calculate_fee_sats, compute_checksum, parse_checked_address},
     wallet::{api::UTXOSpendInfo, FidelityError},
 };
 
@@ -35,6 +37,97 @@ pub enum Destination {
     MultiDynamic(Amount, Vec<Address>),
 }
 
+/// Where the coins of completed incoming swapcoins are swept to.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SweepDestination {
+    /// A fresh internal address of this wallet.
+    #[default]
+    Wallet,
+    /// An external address, e.g. a merchant's payment address.
+    Address(Address),
+    /// An external descriptor, e.g. of a cold wallet. Only the single-key
+    /// `wpkh(<xpub>/<chain>/*)` and `tr(<xpub>/<chain>/*)` forms are supported.
+    Descriptor {
+        /// The descriptor, with its checksum.
+        descriptor: String,
+        /// Derivation index paid by the first sweep. Further sweeps use the following indexes.
+        next_index: u32,
+    },
+}
+
+impl SweepDestination {
+    /// Parses an address, a descriptor or a bare xpub (read as `wpkh(<xpub>/0/*)`).
+    ///
+    /// Descriptors and xpubs are paid from derivation index `next_index` on.
+    pub fn parse(s: &str, network: Network, next_index: u32) -> Result<Self, WalletError> {
+        let s = s.trim();
+        if let Ok(address) = parse_checked_address(s, network) {
+            return Ok(Self::Address(address));
+        }
+
+        let body = if let Ok(xpub) = Xpub::from_str(s) {
+            format!("wpkh({xpub}/0/*)")
+        } else {
+            let (body, checksum) = match s.split_once('#') {
+                Some((body, checksum)) => (body, Some(checksum)),
+                None => (s, None),
+            };
+            if let Some(checksum) = checksum {
+                if compute_checksum(body)? != checksum {
+                    return Err(WalletError::General(format!(
+                        "Invalid descriptor checksum: {s}"
+                    )));
+                }
+            }
+            body.to_string()
+        };
+
+        let invalid = || {
+            WalletError::General(format!(
+                "Invalid destination '{s}'. Use an address, an xpub, or a wpkh(<xpub>/<chain>/*) or tr(<xpub>/<chain>/*) descriptor"
+            ))
+        };
+        let inner = body
+            .strip_prefix("wpkh(")
+            .or_else(|| body.strip_prefix("tr("))
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+        let parts: Vec<&str> = inner.rsplitn(3, '/').collect();
+        if parts.len() != 3 || parts[0] != "*" || parts[1].parse::<u32>().is_err() {
+            return Err(invalid());
+        }
+        let xpub = Xpub::from_str(parts[2]).map_err(|_| invalid())?;
+        if xpub.network != NetworkKind::from(network) {
+            return Err(WalletError::General(format!(
+                "Destination xpub is not for {network}"
+            )));
+        }
+
+        Ok(Self::Descriptor {
+            descriptor: format!("{body}#{}", compute_checksum(&body)?),
+            next_index,
+        })
+    }
+
+    /// Whether the swept coins leave this wallet.
+    pub fn is_external(&self) -> bool {
+        !matches!(self, Self::Wallet)
+    }
+}
+
+impl std::fmt::Display for SweepDestination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wallet => write!(f, "wallet"),
+            Self::Address(address) => write!(f, "{address}"),
+            Self::Descriptor {
+                descriptor,
+                next_index,
+            } => write!(f, "{descriptor} (from index {next_index})"),
+        }
+    }
+}
+
 impl<B: BlockchainBackend> Wallet<B> {
     /// API to perform spending from wallet UTXOs, including descriptor coins and swap coins.
     ///
@@ -83,13 +176,14 @@ impl<B: BlockchainBackend> Wallet<B> {
     /// Redeem a Fidelity Bond.
     /// This function creates a spending transaction from the fidelity bond, signs and broadcasts it.
     /// Returns the txid of the spending tx, and mark the bond as spent.
+    /// Returns `None` if the bond was already spent.
     #[hotpath::measure]
     pub fn redeem_fidelity(
         &mut self,
         idx: u32,
         feerate: f64,
         destination_address_type: AddressType,
-    ) -> Result<(), WalletError> {
+    ) -> Result<Option<Txid>, WalletError> {
         let bond = self
             .store
             .fidelity_bond
@@ -98,7 +192,10 @@ impl<B: BlockchainBackend> Wallet<B> {
 
         if bond.is_spent {
             log::info!("Fidelity bond already spent.");
-            return Ok(());
+            return Ok(None);
+        }
+        if bond.external_key {
+            return Err(FidelityError::ExternalKey.into());
         }
 
         let expired_fidelity_spend_info = UTXOSpendInfo::FidelityBondCoin {
@@ -132,7 +229,7 @@ impl<B: BlockchainBackend> Wallet<B> {
                     .ok_or(FidelityError::BondDoesNotExist)?;
                 bond.is_spent = true;
 
-                return Ok(());
+                return Ok(None);
             }
         };
 
@@ -154,7 +251,7 @@ impl<B: BlockchainBackend> Wallet<B> {
             bond.is_spent = true;
         }
 
-        Ok(())
+        Ok(Some(txid))
     }
 
     /// Creates a [`Transaction`] spending given UTXOs to a [`Destination`] with fee calculated from `feerate`.
@@ -483,3 +580,42 @@ impl<B: BlockchainBackend> Wallet<B> {
         Ok(tx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XPUB: &str = "tpubD6NzVbkrYhZ4XgiXtGrdW5XDAPFCL9h7we1vwNCpn8tGbBcgfVYjXyhWo4E1xkh56hjod1RhGjxbaTLV3X4FyWuejifB9jusQ46QzG87VKp";
+
+    #[test]
+    fn test_parse_sweep_destination() {
+        let address = "bcrt1q6rz28mcfaxtmd6v789l9rrlrusdprr9pz3cppk";
+        assert!(matches!(
+            SweepDestination::parse(address, Network::Regtest, 0).unwrap(),
+            SweepDestination::Address(_)
+        ));
+        assert!(SweepDestination::parse(address, Network::Bitcoin, 0).is_err());
+
+        // A bare xpub is read as its receive chain, with the checksum added.
+        let body = format!("wpkh({XPUB}/0/*)");
+        let expected = SweepDestination::Descriptor {
+            descriptor: format!("{body}#{}", compute_checksum(&body).unwrap()),
+            next_index: 5,
+        };
+        assert_eq!(
+            SweepDestination::parse(XPUB, Network::Regtest, 5).unwrap(),
+            expected
+        );
+        assert_eq!(
+            SweepDestination::parse(&body, Network::Regtest, 5).unwrap(),
+            expected
+        );
+
+        let tr = format!("tr({XPUB}/1/*)");
+        assert!(SweepDestination::parse(&tr, Network::Regtest, 0).is_ok());
+        assert!(SweepDestination::parse(&format!("{tr}#aaaaaaaa"), Network::Regtest, 0).is_err());
+        assert!(SweepDestination::parse(&tr, Network::Bitcoin, 0).is_err());
+        assert!(SweepDestination::parse(&format!("sh({XPUB}/0/*)"), Network::Regtest, 0).is_err());
+        assert!(SweepDestination::parse("not a destination", Network::Regtest, 0).is_err());
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
 
 use super::{error::WalletError, fidelity::FidelityBond};
 
-use bitcoin::{bip32::Xpriv, Network, OutPoint, ScriptBuf};
+use bitcoin::{bip32::Xpriv, Network, OutPoint, ScriptBuf, Txid};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
@@ -138,6 +138,30 @@ impl WalletStore {
         Ok(())
     }
 
+    /// Assign `swap_id` to the swapcoins stored without one whose contract txid is in
+    /// `contract_txids`. Returns how many were assigned.
+    ///
+    /// Legacy incoming swapcoins were saved without a swap id before per-swap recovery, as
+    /// were all swapcoins of older wallets.
+    pub(crate) fn assign_swap_id(&mut self, swap_id: &str, contract_txids: &[Txid]) -> usize {
+        let mut assigned = 0;
+        let incoming = self
+            .incoming_swapcoins
+            .values_mut()
+            .map(|sc| (&sc.contract_tx, &mut sc.swap_id));
+        let outgoing = self
+            .outgoing_swapcoins
+            .values_mut()
+            .map(|sc| (&sc.contract_tx, &mut sc.swap_id));
+        for (contract_tx, id) in incoming.chain(outgoing) {
+            if id.is_none() && contract_txids.contains(&contract_tx.compute_txid()) {
+                *id = Some(swap_id.to_string());
+                assigned += 1;
+            }
+        }
+        assigned
+    }
+
     /// Reads from a path (errors if path doesn't exist).
     /// If `store_enc_material` is provided, attempts to decrypt the file using the
     /// provided key. Returns the deserialized `WalletStore` and the nonce.
@@ -185,4 +209,91 @@ mod tests {
         let (read_wallet, _nonce) = WalletStore::read_from_disk(&file_path, String::new()).unwrap();
         assert_eq!(original_wallet_store, read_wallet);
     }
+
+    #[test]
+    fn test_assign_swap_id_to_legacy_swapcoins() {
+        use bitcoin::{
+            absolute::LockTime, secp256k1::SecretKey, transaction::Version, Amount, PublicKey,
+            Transaction, TxOut,
+        };
+
+        let temp_dir = tempdir().unwrap();
+        let master_key = Xpriv::new_master(Network::Regtest, &[7; 16]).unwrap();
+        let mut store = WalletStore::init(
+            "test_wallet".to_string(),
+            &temp_dir.path().join("test_wallet.cbor"),
+            Network::Regtest,
+            master_key,
+            None,
+            &None,
+        )
+        .unwrap();
+
+        let privkey = SecretKey::from_slice(&[1; 32]).unwrap();
+        let other_pubkey = PublicKey::from_private_key(
+            &bitcoin::secp256k1::Secp256k1::new(),
+            &bitcoin::PrivateKey::new(SecretKey::from_slice(&[2; 32]).unwrap(), Network::Regtest),
+        );
+        let contract_tx = |sats| Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: Amount::from_sat(sats),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        };
+        let incoming = |sats, swap_id: Option<&str>| {
+            let mut coin = IncomingSwapCoin::new_legacy(
+                privkey,
+                other_pubkey,
+                contract_tx(sats),
+                ScriptBuf::new(),
+                privkey,
+                Amount::from_sat(sats),
+            );
+            coin.swap_id = swap_id.map(str::to_string);
+            (coin.contract_tx.compute_txid().to_string(), coin)
+        };
+
+        let (untagged_key, untagged) = incoming(1_000, None);
+        let (other_key, other) = incoming(2_000, Some("other"));
+        let (unrelated_key, unrelated) = incoming(3_000, None);
+        let outgoing = OutgoingSwapCoin::new_legacy(
+            privkey,
+            other_pubkey,
+            contract_tx(4_000),
+            ScriptBuf::new(),
+            privkey,
+            Amount::from_sat(4_000),
+        );
+        let outgoing_key = outgoing.contract_tx.compute_txid().to_string();
+        let record_txids = [
+            untagged.contract_tx.compute_txid(),
+            other.contract_tx.compute_txid(),
+            outgoing.contract_tx.compute_txid(),
+        ];
+        store
+            .incoming_swapcoins
+            .insert(untagged_key.clone(), untagged);
+        store.incoming_swapcoins.insert(other_key.clone(), other);
+        store
+            .incoming_swapcoins
+            .insert(unrelated_key.clone(), unrelated);
+        store
+            .outgoing_swapcoins
+            .insert(outgoing_key.clone(), outgoing);
+
+        assert_eq!(store.assign_swap_id("swap", &record_txids), 2);
+
+        let swap_id = |key: &str| store.incoming_swapcoins[key].swap_id.clone();
+        assert_eq!(swap_id(&untagged_key).as_deref(), Some("swap"));
+        // Swapcoins already tagged with another swap, or not in the record, are left alone.
+        assert_eq!(swap_id(&other_key).as_deref(), Some("other"));
+        assert_eq!(swap_id(&unrelated_key), None);
+        assert_eq!(
+            store.outgoing_swapcoins[&outgoing_key].swap_id.as_deref(),
+            Some("swap")
+        );
+    }
 }
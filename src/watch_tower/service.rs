@@ -7,9 +7,10 @@ use std::{
     sync::{
         atomic::AtomicBool,
         mpsc::{self, Sender as StdSender},
-        Arc,
+        Arc, Mutex,
     },
     thread,
+    time::{Duration, Instant},
 };
 
 use crate::watch_tower::{
@@ -26,17 +27,53 @@ impl Role for MakerRole {
     const RUN_DISCOVERY: bool = false;
 }
 
+/// Upper bounds, in seconds, of the [`QueryDelays`] buckets.
+pub const QUERY_DELAY_BUCKETS: [f64; 8] = [0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 15.0, 60.0];
+
+/// Histogram of how long watch queries waited for the watcher's answer, from the call to
+/// [`WatchService::query_spend`] until its event arrived.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryDelays {
+    /// Queries per bucket of [`QUERY_DELAY_BUCKETS`], not cumulative. Slower queries are only
+    /// counted in `count`.
+    pub buckets: [u64; QUERY_DELAY_BUCKETS.len()],
+    /// Total delay of all queries, in seconds.
+    pub sum_secs: f64,
+    /// Number of answered queries.
+    pub count: u64,
+}
+
+impl QueryDelays {
+    fn observe(&mut self, delay: Duration) {
+        let secs = delay.as_secs_f64();
+        if let Some(bucket) = QUERY_DELAY_BUCKETS.iter().position(|bound| secs <= *bound) {
+            self.buckets[bucket] += 1;
+        }
+        self.sum_secs += secs;
+        self.count += 1;
+    }
+}
+
 /// Client-facing service for sending watcher commands and receiving events.
 #[derive(Clone)]
 pub struct WatchService {
     tx: StdSender<WatcherCommand>,
     rx: CbReceiver<WatcherEvent>,
+    /// Held from a watch request until its reply, so that clones sharing the event queue
+    /// each receive the answer to their own request.
+    query_lock: Arc<Mutex<()>>,
+    query_delays: Arc<Mutex<QueryDelays>>,
 }
 
 impl WatchService {
     /// Creates a new service from the given command sender and event receiver.
     pub fn new(tx: StdSender<WatcherCommand>, rx: CbReceiver<WatcherEvent>) -> Self {
-        Self { tx, rx }
+        Self {
+            tx,
+            rx,
+            query_lock: Arc::new(Mutex::new(())),
+            query_delays: Arc::new(Mutex::new(QueryDelays::default())),
+        }
     }
 
     /// Registers an outpoint to be monitored for future spends.
@@ -52,6 +89,36 @@ impl WatchService {
         let _ = self.tx.send(WatcherCommand::WatchRequest { outpoint });
     }
 
+    /// Queries whether a previously registered outpoint has been spent and waits for the
+    /// answer.
+    ///
+    /// Unlike [`Self::watch_request`] followed by [`Self::wait_for_event`], this is safe to
+    /// call from several threads sharing the service: the watcher answers every query with
+    /// exactly one event, and no other query is sent until that event has been received.
+    pub fn query_spend(&self, outpoint: OutPoint) -> Option<WatcherEvent> {
+        let started = Instant::now();
+        let _guard = self
+            .query_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.tx
+            .send(WatcherCommand::WatchRequest { outpoint })
+            .ok()?;
+        let event = self.rx.recv().ok()?;
+        if let Ok(mut delays) = self.query_delays.lock() {
+            delays.observe(started.elapsed());
+        }
+        Some(event)
+    }
+
+    /// Delays of the queries answered so far, across all clones of this service.
+    pub fn query_delays(&self) -> QueryDelays {
+        self.query_delays
+            .lock()
+            .map(|delays| delays.clone())
+            .unwrap_or_default()
+    }
+
     /// Stops monitoring an outpoint by removing its watch entry from the
     /// registry. The `scriptPubKey` lets the watcher drop the Electrum
     /// subscription too without re-resolving it from the network.
@@ -124,3 +191,60 @@ pub fn start_maker_watch_service(
 
     Ok(WatchService::new(tx_requests, rx_responses))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::{hashes::Hash, Txid};
+
+    #[test]
+    fn test_query_spend_answers_each_caller() {
+        let (tx_requests, rx_requests) = mpsc::channel();
+        let (tx_events, rx_events) = unbounded();
+        let service = WatchService::new(tx_requests, rx_events);
+
+        // Answers each request with a spend of the queried outpoint, like the watcher does
+        // for a watched outpoint.
+        thread::spawn(move || {
+            while let Ok(WatcherCommand::WatchRequest { outpoint }) = rx_requests.recv() {
+                thread::sleep(std::time::Duration::from_millis(1));
+                let _ = tx_events.send(WatcherEvent::UtxoSpent {
+                    outpoint,
+                    spending_tx: None,
+                });
+            }
+        });
+
+        let callers: Vec<_> = (0..4u32)
+            .map(|vout| {
+                let service = service.clone();
+                thread::spawn(move || {
+                    let outpoint = OutPoint::new(Txid::all_zeros(), vout);
+                    for _ in 0..10 {
+                        match service.query_spend(outpoint) {
+                            Some(WatcherEvent::UtxoSpent { outpoint: got, .. }) => {
+                                assert_eq!(got, outpoint)
+                            }
+                            other => panic!("unexpected event {:?}", other),
+                        }
+                    }
+                })
+            })
+            .collect();
+        for caller in callers {
+            caller.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_query_delays_buckets() {
+        let mut delays = QueryDelays::default();
+        delays.observe(Duration::from_millis(3));
+        delays.observe(Duration::from_millis(200));
+        delays.observe(Duration::from_secs(120));
+
+        assert_eq!(delays.buckets, [1, 0, 0, 1, 0, 0, 0, 0]);
+        assert_eq!(delays.count, 3);
+        assert!((delays.sum_secs - 120.203).abs() < 1e-9);
+    }
+}
@@ -6,9 +6,7 @@
 
 use bitcoin::Amount;
 use coinswap::{
-    maker::{start_server, MakerBehavior},
-    protocol::common_messages::ProtocolVersion,
-    taker::{SwapParams, TakerBehavior},
+    maker::start_server, protocol::common_messages::ProtocolVersion, taker::SwapParams,
     wallet::AddressType,
 };
 
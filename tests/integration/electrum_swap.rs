@@ -7,9 +7,7 @@
 use super::test_framework::*;
 use bitcoin::Amount;
 use coinswap::{
-    maker::{start_server, MakerBehavior},
-    protocol::common_messages::ProtocolVersion,
-    taker::{SwapParams, TakerBehavior},
+    maker::start_server, protocol::common_messages::ProtocolVersion, taker::SwapParams,
     wallet::AddressType,
 };
 use log::info;
@@ -16,7 +16,6 @@ use bitcoin::{absolute::LockTime, Amount};
 use bitcoind::bitcoincore_rpc::RpcApi;
 use coinswap::{
     maker::start_server,
-    taker::TakerBehavior,
     utill::MIN_FEE_RATE,
     wallet::{AddressType, Destination},
 };
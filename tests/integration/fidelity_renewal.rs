@@ -5,7 +5,7 @@
 
 use bitcoin::Amount;
 use bitcoind::bitcoincore_rpc::RpcApi;
-use coinswap::{maker::start_server, taker::TakerBehavior, wallet::AddressType};
+use coinswap::{maker::start_server, wallet::AddressType};
 
 use super::test_framework::*;
 
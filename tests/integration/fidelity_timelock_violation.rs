@@ -6,9 +6,9 @@
 
 use bitcoin::Amount;
 use coinswap::{
-    maker::{start_server, MakerBehavior, MakerError, MakerServer, MakerServerConfig},
+    maker::{start_server, MakerError, MakerServer, MakerServerConfig},
     protocol::common_messages::ProtocolVersion,
-    taker::{error::TakerError, SwapParams, TakerBehavior},
+    taker::{error::TakerError, SwapParams},
     wallet::{AddressType, BitcoindBackend, WalletError},
 };
 
@@ -3,7 +3,7 @@
 //! and funding transaction creation produce the expected inputs and outputs.
 
 use bitcoin::{Address, Amount};
-use coinswap::{taker::TakerBehavior, utill::MIN_FEE_RATE, wallet::AddressType};
+use coinswap::{utill::MIN_FEE_RATE, wallet::AddressType};
 
 use super::test_framework::*;
 
@@ -2,14 +2,12 @@ use crate::test_framework;
 
 use bitcoin::Amount;
 use coinswap::{
-    maker::{start_server, MakerBehavior},
-    protocol::common_messages::ProtocolVersion,
-    taker::{SwapParams, TakerBehavior},
+    maker::start_server, protocol::common_messages::ProtocolVersion, taker::SwapParams,
     wallet::AddressType,
 };
 use serde_json::Value;
 use std::{env, path::PathBuf, sync::atomic::Ordering::Relaxed, thread};
-use test_framework::BitcoindBackend;
+use test_framework::{BitcoindBackend, MakerBehavior, TakerBehavior};
 
 fn write_filtered_report_by_prefixes(
     input_report_path: &std::path::Path,
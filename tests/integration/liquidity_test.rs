@@ -10,7 +10,7 @@ use bitcoin::Amount;
 use coinswap::{
     maker::{start_server, MakerServer},
     protocol::common_messages::ProtocolVersion,
-    taker::{SwapParams, TakerBehavior},
+    taker::SwapParams,
     utill::MIN_FEE_RATE,
     wallet::{AddressType, Destination},
 };
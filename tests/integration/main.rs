@@ -21,11 +21,13 @@ mod hotpath_profile;
 mod liquidity_test;
 mod malice1;
 mod malice2;
+mod multi_route_fault;
 mod multi_taker;
 mod offerbook_sync_race;
 mod skip_funding_recovery;
 mod standard_swap;
 mod taker_cli;
+mod takerd_auto_swap;
 mod taproot_concurrent_takers;
 mod taproot_hashlock_recovery;
 mod taproot_maker_abort1;
@@ -0,0 +1,148 @@
+//! Multi-route swap where one route drops after its funds are broadcast.
+//!
+//! The taker splits the swap into two routes over four makers. A single FundsBroadcast drop
+//! fault is shared by both route workers, so exactly one route fails and goes to recovery,
+//! while the other route completes.
+
+use bitcoin::Amount;
+use coinswap::{
+    maker::start_server,
+    protocol::common_messages::ProtocolVersion,
+    taker::SwapParams,
+    wallet::{AddressType, SwapStatus},
+};
+
+use super::test_framework::*;
+
+use log::{info, warn};
+use std::{
+    sync::atomic::Ordering::Relaxed,
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn multi_route_one_route_dropped() {
+    // ---- Setup ----
+    warn!("Running Test: Multi-route swap with one dropped route");
+
+    let makers_config_map = vec![(6102, None), (16102, None), (26102, None), (36102, None)];
+    let taker_behavior = vec![TakerBehavior::DropOneRouteAfterFundsBroadcast];
+    let maker_behaviors = vec![
+        MakerBehavior::Normal,
+        MakerBehavior::Normal,
+        MakerBehavior::Normal,
+        MakerBehavior::Normal,
+    ];
+
+    let (test_framework, mut takers, makers, block_generation_handle) =
+        TestFramework::init::<BitcoindBackend>(makers_config_map, taker_behavior, maker_behaviors);
+
+    let bitcoind = &test_framework.bitcoind;
+    let taker = takers.get_mut(0).unwrap();
+
+    // Fund the taker with 3 UTXOs of 0.05 BTC each
+    fund_taker(
+        taker,
+        bitcoind,
+        3,
+        Amount::from_btc(0.05).unwrap(),
+        AddressType::P2TR,
+    );
+
+    // Fund the makers with 4 UTXOs of 0.05 BTC each
+    fund_makers(
+        &makers,
+        bitcoind,
+        4,
+        Amount::from_btc(0.05).unwrap(),
+        AddressType::P2TR,
+    );
+
+    // Start the maker server threads
+    log::info!("Initiating Maker servers");
+
+    let maker_threads = makers
+        .iter()
+        .map(|maker| {
+            let maker_clone = maker.clone();
+            thread::spawn(move || {
+                start_server(maker_clone).unwrap();
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Wait for makers to complete setup
+    wait_for_makers_setup(&makers, 120);
+
+    // Initiate Coinswap
+    info!("Initiating multi-route coinswap protocol");
+
+    let swap_params = SwapParams::new(ProtocolVersion::Legacy, Amount::from_sat(1_000_000), 2)
+        .with_tx_count(3)
+        .with_required_confirms(1)
+        .with_routes(2);
+
+    generate_blocks(bitcoind, 1);
+
+    let summary = taker
+        .prepare_coinswap(swap_params)
+        .expect("Prepare should succeed");
+    assert_eq!(summary.routes.len(), 2);
+    let route_ids: Vec<String> = summary.routes.iter().map(|r| r.swap_id.clone()).collect();
+
+    // One route failing does not fail the whole swap.
+    let report = taker
+        .start_coinswap(&summary.swap_id)
+        .expect("The remaining route should complete");
+    info!("Multi-route swap report: {:?}", report);
+    taker.log_tracker_state();
+
+    assert_eq!(report.status, SwapStatus::Failed);
+    assert_eq!(report.routes, route_ids);
+    let error = report
+        .error_message
+        .clone()
+        .expect("The dropped route should be reported");
+    let failed: Vec<&String> = route_ids
+        .iter()
+        .filter(|id| error.contains(&format!("route {id}:")))
+        .collect();
+    assert_eq!(failed.len(), 1, "Exactly one route should fail: {}", error);
+    assert!(!report.output_swap_utxos.is_empty());
+
+    let history = taker.swap_history().unwrap();
+    for id in &route_ids {
+        let entry = history
+            .iter()
+            .find(|entry| &entry.swap_id == id)
+            .unwrap_or_else(|| panic!("Route {} missing from swap history", id));
+        if failed.contains(&id) {
+            assert_ne!(entry.status, Some(SwapStatus::Success));
+        } else {
+            assert_eq!(entry.status, Some(SwapStatus::Success));
+        }
+    }
+
+    // Wait for the dropped route's recovery loop to finish
+    info!("Waiting for the dropped route's recovery to complete...");
+    let recovery_timeout = Duration::from_secs(300);
+    let recovery_start = Instant::now();
+    while !taker.is_recovery_complete() {
+        if recovery_start.elapsed() > recovery_timeout {
+            panic!("Background recovery did not complete within timeout");
+        }
+        thread::sleep(Duration::from_secs(5));
+    }
+
+    // ---- Teardown ----
+    makers
+        .iter()
+        .for_each(|maker| maker.shutdown.store(true, Relaxed));
+    maker_threads
+        .into_iter()
+        .for_each(|thread| thread.join().unwrap());
+
+    test_framework.stop();
+    block_generation_handle.join().unwrap();
+}
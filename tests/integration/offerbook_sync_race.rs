@@ -3,8 +3,8 @@
 use bitcoin::Amount;
 use bitcoind::bitcoincore_rpc::RpcApi;
 use coinswap::{
-    maker::{start_server, MakerBehavior},
-    taker::{MakerProtocol, MakerState, TakerBehavior},
+    maker::start_server,
+    taker::{MakerProtocol, MakerState},
     wallet::AddressType,
     watch_tower::registry_storage::FileRegistry,
 };
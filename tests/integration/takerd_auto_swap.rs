@@ -0,0 +1,163 @@
+//! Tests the takerd auto-swap policy: a deposit received while takerd is down is swapped
+//! through the makers after the daemon starts again, while deposits already in the wallet
+//! when takerd was first started are left alone.
+
+use bitcoin::{Amount, OutPoint};
+use coinswap::{
+    maker::start_server,
+    protocol::common_messages::ProtocolVersion,
+    taker::daemon::{start_takerd, ScheduleOrigin, ScheduleState, TakerDaemon, TakerdConfig},
+    wallet::AddressType,
+};
+
+use super::test_framework::*;
+
+use log::{info, warn};
+use std::{
+    sync::{atomic::Ordering::Relaxed, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn test_takerd_auto_swap_after_restart() {
+    // ---- Setup ----
+    warn!("Running Test: takerd auto-swap after restart");
+
+    let makers_config_map = vec![(6102, Some(19051)), (16102, Some(19052))];
+    let taker_behavior = vec![TakerBehavior::Normal];
+    let maker_behaviors = vec![MakerBehavior::Normal, MakerBehavior::Normal];
+
+    let (test_framework, mut takers, makers, block_generation_handle) =
+        TestFramework::init::<BitcoindBackend>(makers_config_map, taker_behavior, maker_behaviors);
+
+    let bitcoind = &test_framework.bitcoind;
+    let taker = takers.remove(0);
+
+    // A deposit that is already in the wallet when takerd is first started.
+    fund_taker(
+        &taker,
+        bitcoind,
+        1,
+        Amount::from_btc(0.05).unwrap(),
+        AddressType::P2WPKH,
+    );
+    let first_deposit = {
+        let wallet = taker.get_wallet().read().unwrap();
+        let utxos = wallet.list_descriptor_utxo_spend_info();
+        assert_eq!(utxos.len(), 1);
+        OutPoint::new(utxos[0].0.txid, utxos[0].0.vout)
+    };
+    let deposit_address = taker
+        .get_wallet()
+        .write()
+        .unwrap()
+        .get_next_external_address(AddressType::P2WPKH)
+        .unwrap();
+
+    fund_makers(
+        &makers,
+        bitcoind,
+        4,
+        Amount::from_btc(0.05).unwrap(),
+        AddressType::P2TR,
+    );
+
+    log::info!("Initiating Maker servers");
+    let maker_threads = makers
+        .iter()
+        .map(|maker| {
+            let maker_clone = maker.clone();
+            thread::spawn(move || {
+                start_server(maker_clone).unwrap();
+            })
+        })
+        .collect::<Vec<_>>();
+    wait_for_makers_setup(&makers, 120);
+
+    let config = TakerdConfig {
+        rpc_port: 7000 + bip39::rand::random::<u16>() % 1000,
+        auto_swap: true,
+        auto_swap_min_amount: 100_000,
+        auto_swap_min_makers: 2,
+        auto_swap_max_makers: 2,
+        auto_swap_max_delay_secs: 0,
+        auto_swap_protocol: ProtocolVersion::Legacy,
+    };
+
+    // ---- First run: the existing deposit is only recorded as seen ----
+    info!("Starting takerd for the first time");
+    let daemon = Arc::new(TakerDaemon::new(taker, config.clone()).unwrap());
+    let daemon_clone = daemon.clone();
+    let takerd_thread = thread::spawn(move || start_takerd(daemon_clone).unwrap());
+    thread::sleep(Duration::from_secs(5));
+    daemon.shutdown();
+    takerd_thread.join().unwrap();
+    assert!(daemon.scheduled_swaps().is_empty());
+    drop(daemon);
+
+    // ---- A deposit arrives while takerd is down ----
+    let deposit_txid = send_to_address(bitcoind, &deposit_address, Amount::from_sat(1_000_000));
+    generate_blocks(bitcoind, 1);
+
+    // ---- Second run: the missed deposit is auto-swapped ----
+    info!("Restarting takerd");
+    let taker = test_framework.restart_taker::<BitcoindBackend>("taker1");
+    let daemon = Arc::new(TakerDaemon::new(taker, config).unwrap());
+    let daemon_clone = daemon.clone();
+    let takerd_thread = thread::spawn(move || start_takerd(daemon_clone).unwrap());
+
+    let deadline = Instant::now() + Duration::from_secs(600);
+    let swap = loop {
+        let swaps = daemon.scheduled_swaps();
+        if let Some(swap) = swaps.iter().find(|s| {
+            matches!(
+                s.state,
+                ScheduleState::Completed { .. } | ScheduleState::Failed { .. }
+            )
+        }) {
+            break swap.clone();
+        }
+        assert!(
+            Instant::now() < deadline,
+            "auto-swap did not finish in time"
+        );
+        thread::sleep(Duration::from_secs(5));
+    };
+
+    daemon.shutdown();
+    takerd_thread.join().unwrap();
+
+    let ScheduleOrigin::Deposit(outpoint) = swap.origin else {
+        panic!("Expected a deposit auto-swap, got {:?}", swap.origin);
+    };
+    assert_eq!(outpoint.txid, deposit_txid);
+    assert_ne!(outpoint, first_deposit);
+    assert_eq!(swap.outpoints, vec![outpoint]);
+    assert_eq!(swap.makers, 2);
+    assert!(
+        matches!(swap.state, ScheduleState::Completed { .. }),
+        "Auto-swap failed: {:?}",
+        swap.state
+    );
+
+    // Only the missed deposit was scheduled.
+    let scheduled = daemon.scheduled_swaps();
+    assert_eq!(scheduled.len(), 1);
+
+    let history = daemon.swap_history().unwrap();
+    assert!(history.iter().any(|entry| {
+        matches!(&swap.state, ScheduleState::Completed { swap_id } if *swap_id == entry.swap_id)
+    }));
+
+    // ---- Teardown ----
+    makers
+        .iter()
+        .for_each(|maker| maker.shutdown.store(true, Relaxed));
+    maker_threads
+        .into_iter()
+        .for_each(|thread| thread.join().unwrap());
+
+    test_framework.stop();
+    block_generation_handle.join().unwrap();
+}
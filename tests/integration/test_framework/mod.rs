@@ -38,9 +38,12 @@ use bitcoind::{
 
 pub use coinswap::wallet::{BitcoindBackend, ElectrumBackend};
 use coinswap::{
-    maker::{MakerBehavior, MakerServer, MakerServerConfig},
+    fault::{
+        FaultAction, FaultInjector, FaultPoint, FaultRole, FaultRule, FaultScenario, FaultStep,
+    },
+    maker::{MakerServer, MakerServerConfig},
     protocol::common_messages::ProtocolVersion,
-    taker::{Taker, TakerBehavior, TakerInitConfig},
+    taker::{Taker, TakerInitConfig},
     utill::setup_logger,
     wallet::{AddressType, BackendConfig, BlockchainBackend, ElectrumConfig, RPCConfig},
 };
@@ -424,6 +427,164 @@ pub fn verify_maker_pre_swap_balances<B: BlockchainBackend>(
     maker_spendable_balance
 }
 
+/// Misbehaving makers used by the tests, as fault scenarios.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MakerBehavior {
+    /// Normal operation.
+    #[default]
+    Normal,
+    /// Receive contract sigs and save swapcoins, but skip funding broadcast
+    /// and close the connection. Simulates last-maker misbehavior.
+    SkipFundingBroadcast,
+    /// Close connection when receiving ReqContractSigsForSender (abort2 scenarios).
+    CloseAtReqContractSigsForSender,
+    /// Close connection when receiving ProofOfFunding (abort2 scenario).
+    CloseAtProofOfFunding,
+    /// Close connection when receiving RespContractSigsForRecvrAndSender (abort3 scenario).
+    CloseAtContractSigsForRecvrAndSender,
+    /// Close connection when receiving ReqContractSigsForRecvr (abort3 scenario).
+    CloseAtContractSigsForRecvr,
+    /// Close connection when receiving the legacy private key handover (abort3 scenario).
+    CloseAtHashPreimage,
+    /// Broadcast contract transactions after setup, then close (malice scenario).
+    BroadcastContractAfterSetup,
+    /// Close connection after accepting the swap details (taproot maker abort).
+    CloseAfterAckResponse,
+    /// Close connection at private key handover phase (taproot maker abort).
+    CloseAtPrivateKeyHandover,
+    /// Close connection at contract sigs exchange (taproot recovery test).
+    CloseAtContractSigsExchange,
+    /// Close connection after completing the handover (taproot hashlock recovery test).
+    CloseAfterSweep,
+    /// Use an invalid fidelity bond timelock (fidelity timelock violation test).
+    InvalidFidelityTimelock,
+}
+
+impl MakerBehavior {
+    /// The fault rules reproducing this behavior.
+    pub fn faults(self) -> FaultInjector {
+        use FaultPoint::{Handled, Recv, Step};
+        let rules = match self {
+            Self::Normal => vec![],
+            Self::SkipFundingBroadcast => {
+                vec![(Step(FaultStep::FundingBroadcast), FaultAction::Drop)]
+            }
+            Self::CloseAtReqContractSigsForSender => {
+                vec![(Recv("ReqContractSigsForSender".into()), FaultAction::Drop)]
+            }
+            Self::CloseAtProofOfFunding => vec![(Recv("ProofOfFunding".into()), FaultAction::Drop)],
+            Self::CloseAtContractSigsForRecvrAndSender => vec![(
+                Recv("RespContractSigsForRecvrAndSender".into()),
+                FaultAction::Drop,
+            )],
+            Self::CloseAtContractSigsForRecvr => {
+                vec![(Recv("ReqContractSigsForRecvr".into()), FaultAction::Drop)]
+            }
+            Self::CloseAtHashPreimage => {
+                vec![(Recv("LegacyPrivateKeyHandover".into()), FaultAction::Drop)]
+            }
+            Self::BroadcastContractAfterSetup => vec![
+                (
+                    Handled("RespContractSigsForRecvrAndSender".into()),
+                    FaultAction::BroadcastContracts,
+                ),
+                (
+                    Handled("TaprootContractData".into()),
+                    FaultAction::BroadcastContracts,
+                ),
+            ],
+            Self::CloseAfterAckResponse => vec![(Handled("SwapDetails".into()), FaultAction::Drop)],
+            Self::CloseAtPrivateKeyHandover => {
+                vec![(Recv("TaprootPrivateKeyHandover".into()), FaultAction::Drop)]
+            }
+            Self::CloseAtContractSigsExchange => {
+                vec![(Recv("TaprootContractData".into()), FaultAction::Drop)]
+            }
+            Self::CloseAfterSweep => {
+                vec![(
+                    Handled("TaprootPrivateKeyHandover".into()),
+                    FaultAction::Drop,
+                )]
+            }
+            Self::InvalidFidelityTimelock => vec![(
+                Step(FaultStep::FidelityBond),
+                FaultAction::Corrupt("locktime".into()),
+            )],
+        };
+        injector(FaultRole::Maker, rules)
+    }
+}
+
+/// Misbehaving takers used by the tests, as fault scenarios.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TakerBehavior {
+    /// Normal behavior.
+    #[default]
+    Normal,
+    /// Close connection early (after maker selection).
+    CloseEarly,
+    /// Drop after funds/contracts are broadcast but before finalization.
+    /// Simulates a taker crash after funds are on-chain.
+    DropAfterFundsBroadcast,
+    /// Drop a single route of a multi-route swap after its funds are broadcast.
+    /// The other routes carry on.
+    DropOneRouteAfterFundsBroadcast,
+    /// Broadcast contract transactions after full setup, then close (malice scenario).
+    BroadcastContractAfterFullSetup,
+    /// Close connection after receiving AckSwapDetails (taproot taker abort).
+    CloseAtAckResponse,
+    /// Close connection when sending sender's contract data (taproot taker abort).
+    CloseAtSendersContract,
+    /// Close connection when receiving maker's contract data response (taproot taker abort).
+    CloseAtSendersContractFromMaker,
+}
+
+impl TakerBehavior {
+    /// The fault rules reproducing this behavior.
+    pub fn faults(self) -> FaultInjector {
+        use FaultPoint::{Recv, Send, Step};
+        let rules = match self {
+            Self::Normal => vec![],
+            Self::CloseEarly => vec![(Step(FaultStep::MakersSelected), FaultAction::Drop)],
+            Self::DropAfterFundsBroadcast => {
+                vec![(Step(FaultStep::FundsBroadcast), FaultAction::Drop)]
+            }
+            Self::DropOneRouteAfterFundsBroadcast => {
+                // Route workers share the taker's injector, so the rule fires in one route.
+                let mut rule = FaultRule::new(
+                    FaultRole::Taker,
+                    Step(FaultStep::FundsBroadcast),
+                    FaultAction::Drop,
+                );
+                rule.times = Some(1);
+                return FaultInjector::new(FaultRole::Taker, FaultScenario { faults: vec![rule] });
+            }
+            Self::BroadcastContractAfterFullSetup => vec![(
+                Step(FaultStep::FundsBroadcast),
+                FaultAction::BroadcastContracts,
+            )],
+            Self::CloseAtAckResponse => vec![(Recv("AckSwapDetails".into()), FaultAction::Drop)],
+            Self::CloseAtSendersContract => {
+                vec![(Send("TaprootContractData".into()), FaultAction::Drop)]
+            }
+            Self::CloseAtSendersContractFromMaker => {
+                vec![(Recv("TaprootContractData".into()), FaultAction::Drop)]
+            }
+        };
+        injector(FaultRole::Taker, rules)
+    }
+}
+
+fn injector(role: FaultRole, rules: Vec<(FaultPoint, FaultAction)>) -> FaultInjector {
+    let faults = rules
+        .into_iter()
+        .map(|(at, action)| FaultRule::new(role, at, action))
+        .collect();
+    FaultInjector::new(role, FaultScenario { faults })
+}
+
 /// Generic over electrum and bitcoind backends.
 pub trait TestBackend: BlockchainBackend {
     fn make_backend_config(
@@ -474,12 +635,38 @@ pub struct TestFramework {
     pub(super) electrsd: Option<ElectrsD>,
     pub(super) temp_dir: PathBuf,
     pub(super) nostr_relay_url: String,
+    zmq_addr: String,
     shutdown: AtomicBool,
     nostr_relay_shutdown: mpsc::Sender<()>,
     nostr_relay_handle: Option<JoinHandle<()>>,
 }
 
 impl TestFramework {
+    /// Initialize the taker `taker_id` (e.g. `taker1`) again over its existing data directory,
+    /// as a restarted taker process would. The previous instance must have been dropped.
+    pub fn restart_taker<B: TestBackend>(&self, taker_id: &str) -> Taker<B> {
+        let rpc_config = RPCConfig {
+            url: self.bitcoind.rpc_url().split_at(7).1.to_string(),
+            auth: Auth::CookieFile(self.bitcoind.params.cookie_file.clone()),
+            ..Default::default()
+        };
+        let electrum_url = self
+            .electrsd
+            .as_ref()
+            .map(|e| format!("tcp://{}", e.electrum_url));
+        let backend = B::make_backend_config(
+            taker_id.to_string(),
+            &rpc_config,
+            &self.zmq_addr,
+            &mut || electrum_url.clone().expect("electrs is running"),
+        );
+        let config = TakerInitConfig::default()
+            .with_data_dir(self.temp_dir.join(taker_id))
+            .with_backend(backend)
+            .with_nostr_relays(vec![self.nostr_relay_url.clone()]);
+        Taker::<B>::init(config).unwrap()
+    }
+
     /// Assert that a log message exists in the debug.log file
     pub fn assert_log(&self, expected_message: &str, log_path: &str) {
         match std::fs::read_to_string(log_path) {
@@ -566,7 +753,7 @@ impl TestFramework {
                         .with_backend(backend)
                         .with_nostr_relays(vec![nostr_relay_url.clone()]);
                     let mut taker = Taker::<B>::init(config).unwrap();
-                    taker.behavior = behavior;
+                    taker.faults = behavior.faults();
                     taker
                 })
                 .collect();
@@ -611,7 +798,7 @@ impl TestFramework {
                     };
 
                     let mut server = MakerServer::<B>::init(config).unwrap();
-                    server.behavior = maker_behaviors.get(i).copied().unwrap_or_default();
+                    server.faults = maker_behaviors.get(i).copied().unwrap_or_default().faults();
                     Arc::new(server)
                 })
                 .collect();
@@ -624,6 +811,7 @@ impl TestFramework {
             electrsd,
             temp_dir: temp_dir.clone(),
             nostr_relay_url,
+            zmq_addr,
             shutdown: AtomicBool::new(false),
             nostr_relay_shutdown,
             nostr_relay_handle: Some(nostr_relay_handle),
@@ -8,7 +8,7 @@ use bitcoin::{Amount, OutPoint};
 use coinswap::{
     maker::start_server,
     protocol::common_messages::ProtocolVersion,
-    taker::{SwapParams, TakerBehavior},
+    taker::SwapParams,
     utill::MIN_FEE_RATE,
     wallet::{AddressType, WalletError},
 };